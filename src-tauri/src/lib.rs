@@ -17,6 +17,17 @@ pub fn run() {
     .plugin(log_plugin)
     .plugin(tauri_plugin_shell::init())
     .plugin(tauri_plugin_process::init())
+    // Les abonnements aux journaux d'événements suivent la vie de la page qui les a créés
+    .on_page_load(|webview, payload| {
+      if matches!(payload.event(), tauri::webview::PageLoadEvent::Started) {
+        modules::event_viewer::subscription::stop_webview_subscriptions(webview.label());
+      }
+    })
+    .on_window_event(|window, event| {
+      if let tauri::WindowEvent::Destroyed = event {
+        modules::event_viewer::subscription::stop_webview_subscriptions(window.label());
+      }
+    })
     .invoke_handler(tauri::generate_handler![
       modules::admin::is_elevated,
       modules::admin::require_admin,
@@ -26,11 +37,22 @@ pub fn run() {
       modules::disks::optimize_volume,
       modules::disks::format_disk,
       modules::disks::get_disk_partitions,
+      modules::disks::health::get_disk_health,
       modules::devices::list_devices,
       modules::devices::enable_device,
       modules::devices::disable_device,
       modules::event_viewer::get_events,
-      modules::event_viewer::clear_event_log,
+      modules::event_viewer::get_event_details,
+      modules::event_viewer::logs::list_event_logs,
+      modules::event_viewer::logs::set_event_log_config,
+      modules::event_viewer::logs::clear_event_log,
+      modules::event_viewer::export::export_event_log,
+      modules::event_viewer::export::get_events_from_file,
+      modules::event_viewer::investigations::run_event_investigation,
+      modules::event_viewer::stats::get_event_stats,
+      modules::event_viewer::paging::get_events_page,
+      modules::event_viewer::subscription::subscribe_events,
+      modules::event_viewer::subscription::unsubscribe_events,
       modules::hardware::get_hardware_info,
       modules::network::list_network_adapters,
       modules::system::list_processes,
@@ -45,15 +67,56 @@ pub fn run() {
       modules::users::list_local_groups,
       modules::users::add_local_user,
       modules::users::delete_local_user,
+      modules::users::enable_local_user,
+      modules::users::disable_local_user,
+      modules::users::rename_local_user,
+      modules::users::set_local_user_password,
+      modules::users::update_local_user,
+      modules::users::get_local_group_members,
+      modules::users::add_local_group_member,
+      modules::users::remove_local_group_member,
+      modules::users::create_local_group,
+      modules::users::delete_local_group,
+      modules::users::list_user_rights,
+      modules::users::audit_local_accounts,
+      modules::users::export_local_account_audit,
+      modules::users::list_user_profiles,
+      modules::users::delete_user_profile,
+      modules::sessions::list_sessions,
+      modules::sessions::logoff_session,
+      modules::sessions::disconnect_session,
+      modules::sessions::send_session_message,
       modules::security::list_firewall_rules,
       modules::security::get_antivirus_status,
       modules::shares::list_shares,
       modules::shares::create_share,
       modules::shares::delete_share,
+      modules::shares::get_share_access,
+      modules::shares::grant_share_access,
+      modules::shares::revoke_share_access,
+      modules::shares::list_smb_sessions,
+      modules::shares::list_open_files,
+      modules::shares::close_smb_session,
+      modules::shares::close_open_file,
+      modules::shares::acl::get_path_acl,
+      modules::shares::acl::set_path_acl,
+      modules::shares::acl::get_effective_share_access,
+      modules::shares::client::list_mapped_drives,
+      modules::shares::client::map_network_drive,
+      modules::shares::client::unmap_network_drive,
+      modules::shares::client::get_smb_security_config,
       modules::tasks::list_scheduled_tasks,
       modules::tasks::enable_task,
       modules::tasks::disable_task,
       modules::tasks::run_task,
+      modules::tasks::stop_task,
+      modules::tasks::batch_task_operation,
+      modules::tasks::create_scheduled_task,
+      modules::tasks::delete_scheduled_task,
+      modules::tasks::export_task_xml,
+      modules::tasks::import_task_xml,
+      modules::tasks::get_task_details,
+      modules::tasks::audit::audit_scheduled_tasks,
       modules::backup::list_restore_points,
       modules::backup::create_restore_point,
       modules::updates::list_installed_updates,
@@ -0,0 +1,40 @@
+// --- Écriture CSV (RFC 4180) destinée à être ouverte dans Excel ---
+
+// Un tableur interprète une cellule commençant par l'un de ces caractères comme une formule:
+// une donnée contrôlée par un tiers (nom d'utilisateur d'un événement 4625...) deviendrait active
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+// Neutralise les formules (préfixe ') puis cite le champ si besoin; ';' est cité pour les
+// tableurs configurés en français, qui l'utilisent comme séparateur
+pub fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(FORMULA_PREFIXES) { format!("'{}", value) } else { value.to_string() };
+    if value.contains([',', ';', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_separators_and_quotes() {
+        assert_eq!(csv_field("simple"), "simple");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("a;b"), "\"a;b\"");
+        assert_eq!(csv_field("dit \"oui\""), "\"dit \"\"oui\"\"\"");
+        assert_eq!(csv_field("ligne 1\r\nligne 2"), "\"ligne 1\r\nligne 2\"");
+    }
+
+    #[test]
+    fn neutralizes_formulas() {
+        assert_eq!(csv_field("=HYPERLINK(\"http://x\")"), "\"'=HYPERLINK(\"\"http://x\"\")\"");
+        assert_eq!(csv_field("+33 1 23"), "'+33 1 23");
+        assert_eq!(csv_field("-2+3"), "'-2+3");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_field("\tcmd"), "'\tcmd");
+        assert_eq!(csv_field("a=b"), "a=b");
+    }
+}
@@ -0,0 +1,4 @@
+// Utilitaires partagés entre modules
+pub mod csv;
+pub mod oem;
+pub mod powershell;
@@ -0,0 +1,43 @@
+// --- Décodage de la sortie des outils console (quser, msg...) ---
+
+// Redirigés vers un tube, les outils natifs écrivent dans la page de code OEM de la console et non en UTF-8.
+// CP850 est la page OEM des installations d'Europe de l'Ouest (français, allemand, espagnol...); ses
+// lettres accentuées minuscules et É coïncident avec CP437 (anglais), seuls les caractères de dessin
+// de cadres diffèrent.
+const CP850_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', 'ø', '£', 'Ø', '×', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '®', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', 'Á', 'Â', 'À', '©', '╣', '║', '╗', '╝', '¢', '¥', '┐',
+    '└', '┴', '┬', '├', '─', '┼', 'ã', 'Ã', '╚', '╔', '╩', '╦', '╠', '═', '╬', '¤',
+    'ð', 'Ð', 'Ê', 'Ë', 'È', 'ı', 'Í', 'Î', 'Ï', '┘', '┌', '█', '▄', '¦', 'Ì', '▀',
+    'Ó', 'ß', 'Ô', 'Ò', 'õ', 'Õ', 'µ', 'þ', 'Þ', 'Ú', 'Û', 'Ù', 'ý', 'Ý', '¯', '´',
+    '\u{00AD}', '±', '‗', '¾', '¶', '§', '÷', '¸', '°', '¨', '·', '¹', '³', '²', '■', '\u{00A0}',
+];
+
+// Sortie d'un outil console: UTF-8 si elle est valide (PowerShell, page de code 65001), sinon CP850
+pub fn decode_console_output(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&b| if b < 0x80 { b as char } else { CP850_HIGH[(b - 0x80) as usize] }).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_oem_bytes() {
+        // "Déco", "ÉTAT", "Unité" en CP850
+        assert_eq!(decode_console_output(b"D\x82co"), "Déco");
+        assert_eq!(decode_console_output(b"\x90TAT"), "ÉTAT");
+        assert_eq!(decode_console_output(b"Unit\x82 \xff"), "Unit\u{e9} \u{a0}");
+    }
+
+    #[test]
+    fn keeps_utf8_output() {
+        assert_eq!(decode_console_output("Déco".as_bytes()), "Déco");
+        assert_eq!(decode_console_output(b"Active"), "Active");
+    }
+}
@@ -0,0 +1,62 @@
+// --- Construction de scripts PowerShell ---
+
+// Variable d'environnement utilisée pour transmettre un mot de passe à PowerShell.
+// Le secret n'apparaît ainsi ni dans la ligne de commande du processus ni dans les logs.
+pub const SECRET_ENV_VAR: &str = "KRB_TOOL_SECRET";
+
+// Délimiteurs d'une chaîne littérale PowerShell: l'apostrophe ASCII et les guillemets simples
+// typographiques, que PowerShell accepte aussi (l'apostrophe courbe est courante en saisie française)
+const SINGLE_QUOTES: [char; 5] = ['\'', '\u{2018}', '\u{2019}', '\u{201A}', '\u{201B}'];
+
+// Chaîne littérale PowerShell entre apostrophes (aucune expansion de $ ni de `).
+// Chaque délimiteur est doublé, ce qui le rend littéral à l'intérieur de la chaîne.
+pub fn ps_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        if SINGLE_QUOTES.contains(&c) {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+    quoted.push('\'');
+    quoted
+}
+
+// Lecture du secret en clair dans un script (paramètres -Password de type String)
+pub fn secret_env_ref() -> String {
+    format!("$env:{}", SECRET_ENV_VAR)
+}
+
+// Convertit le secret en SecureString ($Password) puis l'efface du processus PowerShell
+pub fn secure_password_preamble() -> String {
+    format!(
+        "$Password = ConvertTo-SecureString -String {} -AsPlainText -Force; Remove-Item Env:\\{};",
+        secret_env_ref(), SECRET_ENV_VAR
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_literal_strings() {
+        assert_eq!(ps_quote("C:\\Partages\\Compta"), "'C:\\Partages\\Compta'");
+        assert_eq!(ps_quote("l'équipe $env:USERNAME `n"), "'l''équipe $env:USERNAME `n'");
+    }
+
+    #[test]
+    fn doubles_typographic_quotes() {
+        assert_eq!(ps_quote("l\u{2019}équipe'; calc"), "'l\u{2019}\u{2019}équipe''; calc'");
+        assert_eq!(ps_quote("\u{2018}a\u{201A}b\u{201B}"), "'\u{2018}\u{2018}a\u{201A}\u{201A}b\u{201B}\u{201B}'");
+    }
+
+    #[test]
+    fn builds_secret_preamble() {
+        assert_eq!(
+            secure_password_preamble(),
+            "$Password = ConvertTo-SecureString -String $env:KRB_TOOL_SECRET -AsPlainText -Force; Remove-Item Env:\\KRB_TOOL_SECRET;"
+        );
+    }
+}
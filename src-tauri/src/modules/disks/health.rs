@@ -0,0 +1,539 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::result::Result;
+use tauri::command;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+use crate::modules::hardware::decode::{disk_bus_type_name, disk_media_type_name};
+
+// --- Santé des disques (compteurs de fiabilité / SMART) ---
+// Windows: MSFT_PhysicalDisk + Get-StorageReliabilityCounter. Linux: smartctl -j (smartmontools).
+
+// Seuils de température (°C): les disques mécaniques supportent moins la chaleur que les SSD
+const HDD_TEMP_WARNING: u32 = 50;
+const HDD_TEMP_CRITICAL: u32 = 60;
+const SSD_TEMP_WARNING: u32 = 70;
+const SSD_TEMP_CRITICAL: u32 = 80;
+// Usure (% de l'endurance nominale consommée)
+const WEAR_WARNING: u32 = 80;
+const WEAR_CRITICAL: u32 = 100;
+// Secteurs réalloués ou en attente de réallocation
+const SECTORS_CRITICAL: u64 = 100;
+
+// Ordre croissant de gravité: le verdict d'un disque est le maximum de ses constats
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthVerdict {
+    Unknown, // Aucune donnée exploitable (souvent: privilèges insuffisants)
+    Good,
+    Warning,
+    Critical,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DiskHealth {
+    device: String, // DeviceId Windows ou chemin /dev
+    model: String,
+    serial_number: Option<String>,
+    size_bytes: Option<u64>,
+    media_type: String,
+    bus_type: String,
+    reported_health: HealthVerdict, // État annoncé par le firmware / le système
+    temperature_c: Option<u32>,
+    temperature_max_c: Option<u32>,
+    wear_percent: Option<u32>,
+    read_errors: Option<u64>,  // Erreurs de lecture non corrigées
+    write_errors: Option<u64>, // Erreurs d'écriture non corrigées
+    reallocated_sectors: Option<u64>,
+    pending_sectors: Option<u64>,
+    offline_uncorrectable: Option<u64>, // Secteurs illisibles relevés par l'analyse hors ligne (ATA 198)
+    power_on_hours: Option<u64>,
+    verdict: HealthVerdict,
+    issues: Vec<String>, // Raisons du verdict, en clair
+}
+
+impl DiskHealth {
+    fn new(device: String, model: String) -> Self {
+        DiskHealth {
+            device,
+            model,
+            serial_number: None,
+            size_bytes: None,
+            media_type: disk_media_type_name(0).to_string(),
+            bus_type: disk_bus_type_name(0).to_string(),
+            reported_health: HealthVerdict::Unknown,
+            temperature_c: None,
+            temperature_max_c: None,
+            wear_percent: None,
+            read_errors: None,
+            write_errors: None,
+            reallocated_sectors: None,
+            pending_sectors: None,
+            offline_uncorrectable: None,
+            power_on_hours: None,
+            verdict: HealthVerdict::Unknown,
+            issues: Vec::new(),
+        }
+    }
+
+    fn has_metrics(&self) -> bool {
+        self.temperature_c.is_some() || self.wear_percent.is_some() || self.read_errors.is_some()
+            || self.write_errors.is_some() || self.reallocated_sectors.is_some() || self.pending_sectors.is_some()
+            || self.offline_uncorrectable.is_some() || self.power_on_hours.is_some()
+    }
+}
+
+// Applique les seuils et renseigne verdict et issues
+fn evaluate_health(disk: &mut DiskHealth) {
+    let mut issues: Vec<(HealthVerdict, String)> = Vec::new();
+
+    match disk.reported_health {
+        HealthVerdict::Critical => issues.push((HealthVerdict::Critical, "Le disque se signale lui-même comme défaillant".to_string())),
+        HealthVerdict::Warning => issues.push((HealthVerdict::Warning, "Le disque signale un état dégradé".to_string())),
+        _ => {}
+    }
+
+    if let Some(temperature) = disk.temperature_c {
+        let (warning, critical) = if disk.media_type == "HDD" {
+            (HDD_TEMP_WARNING, HDD_TEMP_CRITICAL)
+        } else {
+            (SSD_TEMP_WARNING, SSD_TEMP_CRITICAL)
+        };
+        if temperature >= critical {
+            issues.push((HealthVerdict::Critical, format!("Température critique: {} °C (seuil {} °C)", temperature, critical)));
+        } else if temperature >= warning {
+            issues.push((HealthVerdict::Warning, format!("Température élevée: {} °C (seuil {} °C)", temperature, warning)));
+        }
+    }
+
+    if let Some(wear) = disk.wear_percent {
+        if wear >= WEAR_CRITICAL {
+            issues.push((HealthVerdict::Critical, format!("Endurance nominale épuisée: usure {} %", wear)));
+        } else if wear >= WEAR_WARNING {
+            issues.push((HealthVerdict::Warning, format!("Usure avancée: {} %", wear)));
+        }
+    }
+
+    for (label, count) in [("lecture", disk.read_errors), ("écriture", disk.write_errors)] {
+        if let Some(count) = count.filter(|&c| c > 0) {
+            issues.push((HealthVerdict::Warning, format!("{} erreur(s) de {} non corrigée(s)", count, label)));
+        }
+    }
+
+    for (label, count) in [
+        ("réalloué(s)", disk.reallocated_sectors),
+        ("en attente de réallocation", disk.pending_sectors),
+        ("illisible(s) lors de l'analyse hors ligne", disk.offline_uncorrectable),
+    ] {
+        if let Some(count) = count.filter(|&c| c > 0) {
+            let level = if count >= SECTORS_CRITICAL { HealthVerdict::Critical } else { HealthVerdict::Warning };
+            issues.push((level, format!("{} secteur(s) {}", count, label)));
+        }
+    }
+
+    let worst = issues.iter().map(|(level, _)| *level).max();
+    disk.verdict = match worst {
+        Some(level) => level,
+        None if disk.reported_health == HealthVerdict::Good || disk.has_metrics() => HealthVerdict::Good,
+        None => HealthVerdict::Unknown,
+    };
+    disk.issues = issues.into_iter().map(|(_, message)| message).collect();
+}
+
+// --- Windows ---
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsDiskHealth {
+    device_id: Option<String>,
+    friendly_name: Option<String>,
+    serial_number: Option<String>,
+    size: Option<u64>,
+    media_type: Option<u16>,
+    bus_type: Option<u16>,
+    health_status: Option<u16>,
+    // Get-StorageReliabilityCounter (null sans privilèges admin ou si le pilote ne les expose pas)
+    temperature: Option<u32>,
+    temperature_max: Option<u32>,
+    wear: Option<u32>,
+    read_errors_uncorrected: Option<u64>,
+    write_errors_uncorrected: Option<u64>,
+    power_on_hours: Option<u64>,
+}
+
+// MSFT_PhysicalDisk.HealthStatus: 0 sain, 1 avertissement, 2 défaillant, 5 inconnu
+fn verdict_from_health_status(status: Option<u16>) -> HealthVerdict {
+    match status {
+        Some(0) => HealthVerdict::Good,
+        Some(1) => HealthVerdict::Warning,
+        Some(2) => HealthVerdict::Critical,
+        _ => HealthVerdict::Unknown,
+    }
+}
+
+fn disk_health_from_ps(ps: PsDiskHealth) -> DiskHealth {
+    let mut disk = DiskHealth::new(
+        ps.device_id.unwrap_or_default(),
+        ps.friendly_name.map(|n| n.trim().to_string()).unwrap_or_else(|| "Disque inconnu".to_string()),
+    );
+    disk.serial_number = ps.serial_number.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    disk.size_bytes = ps.size;
+    disk.media_type = disk_media_type_name(ps.media_type.unwrap_or(0)).to_string();
+    disk.bus_type = disk_bus_type_name(ps.bus_type.unwrap_or(0)).to_string();
+    disk.reported_health = verdict_from_health_status(ps.health_status);
+    // Une température de 0 °C signifie « non rapportée »
+    disk.temperature_c = ps.temperature.filter(|&t| t > 0);
+    disk.temperature_max_c = ps.temperature_max.filter(|&t| t > 0);
+    disk.wear_percent = ps.wear;
+    disk.read_errors = ps.read_errors_uncorrected;
+    disk.write_errors = ps.write_errors_uncorrected;
+    disk.power_on_hours = ps.power_on_hours;
+    evaluate_health(&mut disk);
+    disk
+}
+
+async fn get_disk_health_windows(app: &AppHandle) -> Result<Vec<DiskHealth>, String> {
+    // Important: Get-StorageReliabilityCounter nécessite des privilèges admin
+    let command = "Get-CimInstance -Namespace root\\Microsoft\\Windows\\Storage -ClassName MSFT_PhysicalDisk | ForEach-Object { \
+        $r = Get-StorageReliabilityCounter -PhysicalDisk $_ -ErrorAction SilentlyContinue; \
+        [PSCustomObject]@{ DeviceId = $_.DeviceId; FriendlyName = $_.FriendlyName; SerialNumber = $_.SerialNumber; Size = $_.Size; \
+        MediaType = $_.MediaType; BusType = $_.BusType; HealthStatus = $_.HealthStatus; \
+        Temperature = $r.Temperature; TemperatureMax = $r.TemperatureMax; Wear = $r.Wear; \
+        ReadErrorsUncorrected = $r.ReadErrorsUncorrected; WriteErrorsUncorrected = $r.WriteErrorsUncorrected; PowerOnHours = $r.PowerOnHours } \
+        } | ConvertTo-Json -Compress";
+    let output = app.shell()
+        .command("powershell")
+        .args(["-Command", command])
+        .output()
+        .await
+        .map_err(|e| format!("Erreur lors de l'exécution de Get-StorageReliabilityCounter: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Lecture de la santé des disques échouée: {:?} \nErreur: {}",
+            output.status, String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json_str = stdout.trim();
+    if json_str.is_empty() {
+        return Ok(vec![]);
+    }
+    let parsed: Vec<PsDiskHealth> = if json_str.starts_with('[') {
+        serde_json::from_str(json_str).map_err(|e| format!("Erreur parsing JSON (tableau) santé disques: {}", e))?
+    } else {
+        vec![serde_json::from_str(json_str).map_err(|e| format!("Erreur parsing JSON (objet unique) santé disques: {}", e))?]
+    };
+    Ok(parsed.into_iter().map(disk_health_from_ps).collect())
+}
+
+// --- Linux (smartctl) ---
+
+fn json_u64(value: &Value, path: &[&str]) -> Option<u64> {
+    path.iter().try_fold(value, |v, key| v.get(key))?.as_u64()
+}
+
+// Valeur brute d'un attribut SMART ATA
+fn ata_attribute(report: &Value, id: u64) -> Option<&Value> {
+    report.pointer("/ata_smart_attributes/table")?.as_array()?
+        .iter()
+        .find(|attribute| attribute.get("id").and_then(Value::as_u64) == Some(id))
+}
+
+fn ata_raw(report: &Value, id: u64) -> Option<u64> {
+    ata_attribute(report, id).and_then(|a| json_u64(a, &["raw", "value"]))
+}
+
+// Usure ATA: endurance consommée (smartctl 7.3+), sinon valeur normalisée des attributs de durée de vie
+// (177 Wear_Leveling_Count, 231 SSD_Life_Left, 233 Media_Wearout_Indicator: 100 = neuf).
+// Ces identifiants n'ont ce sens que sur un SSD: sur un disque dur, 231 est souvent Temperature_Celsius.
+fn ata_wear_percent(report: &Value) -> Option<u32> {
+    if let Some(used) = json_u64(report, &["endurance_used", "current_percent"]) {
+        return Some(used as u32);
+    }
+    if json_u64(report, &["rotation_rate"]) != Some(0) {
+        return None;
+    }
+    [177, 231, 233].iter()
+        .find_map(|&id| ata_attribute(report, id).and_then(|a| a.get("value")).and_then(Value::as_u64))
+        .map(|remaining| 100u64.saturating_sub(remaining) as u32)
+}
+
+fn parse_smartctl_report(device: &str, json: &str) -> Result<DiskHealth, String> {
+    let report: Value = serde_json::from_str(json).map_err(|e| format!("Erreur parsing JSON smartctl {}: {}", device, e))?;
+    // Bits 0 et 1 du code de sortie: ligne de commande invalide ou périphérique inaccessible
+    if json_u64(&report, &["smartctl", "exit_status"]).is_some_and(|status| status & 0b11 != 0) {
+        let message = report.pointer("/smartctl/messages/0/string").and_then(Value::as_str).unwrap_or("périphérique inaccessible");
+        return Err(format!("smartctl {}: {}", device, message));
+    }
+
+    let model = report.get("model_name").or_else(|| report.get("scsi_model_name"))
+        .and_then(Value::as_str).unwrap_or("Disque inconnu");
+    let mut disk = DiskHealth::new(device.to_string(), model.to_string());
+    disk.serial_number = report.get("serial_number").and_then(Value::as_str).map(str::to_string);
+    disk.size_bytes = json_u64(&report, &["user_capacity", "bytes"]);
+
+    let protocol = report.pointer("/device/protocol").and_then(Value::as_str).unwrap_or("");
+    let nvme = protocol.eq_ignore_ascii_case("NVMe");
+    disk.bus_type = match protocol {
+        "" => disk_bus_type_name(0).to_string(),
+        "ATA" => "SATA".to_string(),
+        other => other.to_string(),
+    };
+    disk.media_type = match json_u64(&report, &["rotation_rate"]) {
+        _ if nvme => "SSD",
+        Some(0) => "SSD",
+        Some(_) => "HDD",
+        None => disk_media_type_name(0),
+    }.to_string();
+
+    disk.reported_health = match report.pointer("/smart_status/passed").and_then(Value::as_bool) {
+        Some(true) => HealthVerdict::Good,
+        Some(false) => HealthVerdict::Critical,
+        None => HealthVerdict::Unknown,
+    };
+    disk.temperature_c = json_u64(&report, &["temperature", "current"]).map(|t| t as u32);
+    disk.temperature_max_c = json_u64(&report, &["temperature", "lifetime_max"]).map(|t| t as u32);
+    disk.power_on_hours = json_u64(&report, &["power_on_time", "hours"]);
+
+    if nvme {
+        let log = report.get("nvme_smart_health_information_log").cloned().unwrap_or(Value::Null);
+        disk.wear_percent = json_u64(&log, &["percentage_used"]).map(|p| p as u32);
+        disk.read_errors = json_u64(&log, &["media_errors"]);
+        // critical_warning: masque de bits (réserve, température, fiabilité, lecture seule...)
+        if json_u64(&log, &["critical_warning"]).is_some_and(|w| w != 0) {
+            disk.reported_health = disk.reported_health.max(HealthVerdict::Warning);
+        }
+    } else if protocol == "SCSI" {
+        disk.reallocated_sectors = json_u64(&report, &["scsi_grown_defect_list"]);
+        disk.read_errors = json_u64(&report, &["scsi_error_counter_log", "read", "total_uncorrected_errors"]);
+        disk.write_errors = json_u64(&report, &["scsi_error_counter_log", "write", "total_uncorrected_errors"]);
+    } else {
+        disk.wear_percent = ata_wear_percent(&report);
+        disk.reallocated_sectors = ata_raw(&report, 5);
+        disk.pending_sectors = ata_raw(&report, 197);
+        // 187 Reported_Uncorrect; 198 Offline_Uncorrectable compte des secteurs, pas des erreurs de lecture
+        disk.read_errors = ata_raw(&report, 187);
+        disk.offline_uncorrectable = ata_raw(&report, 198);
+    }
+
+    evaluate_health(&mut disk);
+    Ok(disk)
+}
+
+// Disque listé mais illisible (droits root manquants, pont USB non supporté...)
+fn unreadable_disk(device: &str, error: String) -> DiskHealth {
+    let mut disk = DiskHealth::new(device.to_string(), "Disque inconnu".to_string());
+    disk.issues.push(error);
+    disk
+}
+
+async fn run_smartctl(app: &AppHandle, args: &[&str]) -> Result<String, String> {
+    // Le code de sortie de smartctl est un masque de bits: non nul dès qu'un problème SMART est détecté,
+    // le JSON reste alors valide. Les erreurs fatales sont lues dans smartctl.exit_status.
+    let output = app.shell()
+        .command("smartctl")
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("smartctl introuvable (paquet smartmontools requis): {}", e))?;
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+async fn get_disk_health_smartctl(app: &AppHandle) -> Result<Vec<DiskHealth>, String> {
+    // Important: smartctl nécessite les droits root pour interroger les disques
+    let scan: Value = serde_json::from_str(&run_smartctl(app, &["--scan", "-j"]).await?)
+        .map_err(|e| format!("Erreur parsing JSON smartctl --scan: {}", e))?;
+    let devices: Vec<(String, String)> = scan.get("devices").and_then(Value::as_array).map(|devices| {
+        devices.iter().filter_map(|d| Some((
+            d.get("name")?.as_str()?.to_string(),
+            d.get("type").and_then(Value::as_str).unwrap_or("auto").to_string(),
+        ))).collect()
+    }).unwrap_or_default();
+
+    let mut disks = Vec::new();
+    for (name, device_type) in devices {
+        let report = run_smartctl(app, &["-a", "-j", "-d", &device_type, &name]).await?;
+        match parse_smartctl_report(&name, &report) {
+            Ok(disk) => disks.push(disk),
+            Err(e) => {
+                println!("Avertissement: {}", e);
+                disks.push(unreadable_disk(&name, e));
+            }
+        }
+    }
+    Ok(disks)
+}
+
+#[command]
+pub async fn get_disk_health(app: AppHandle) -> Result<Vec<DiskHealth>, String> {
+    println!("Real: get_disk_health() called");
+    if cfg!(windows) {
+        get_disk_health_windows(&app).await
+    } else {
+        get_disk_health_smartctl(&app).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ps_disk(health_status: u16) -> PsDiskHealth {
+        PsDiskHealth {
+            device_id: Some("0".to_string()),
+            friendly_name: Some("Samsung SSD 980 PRO 1TB".to_string()),
+            serial_number: Some(" S5GXNF0R123456 ".to_string()),
+            size: Some(1_000_204_886_016),
+            media_type: Some(4),
+            bus_type: Some(17),
+            health_status: Some(health_status),
+            temperature: Some(41),
+            temperature_max: Some(0),
+            wear: Some(3),
+            read_errors_uncorrected: Some(0),
+            write_errors_uncorrected: Some(0),
+            power_on_hours: Some(5120),
+        }
+    }
+
+    #[test]
+    fn healthy_windows_disk_is_good() {
+        let disk = disk_health_from_ps(ps_disk(0));
+        assert_eq!(disk.verdict, HealthVerdict::Good);
+        assert!(disk.issues.is_empty());
+        assert_eq!(disk.media_type, "SSD");
+        assert_eq!(disk.bus_type, "NVMe");
+        assert_eq!(disk.serial_number.as_deref(), Some("S5GXNF0R123456"));
+        assert_eq!(disk.temperature_max_c, None);
+    }
+
+    #[test]
+    fn applies_thresholds() {
+        let mut ps = ps_disk(0);
+        ps.wear = Some(85);
+        ps.read_errors_uncorrected = Some(2);
+        let disk = disk_health_from_ps(ps);
+        assert_eq!(disk.verdict, HealthVerdict::Warning);
+        assert_eq!(disk.issues.len(), 2);
+
+        let mut hot_hdd = ps_disk(0);
+        hot_hdd.media_type = Some(3);
+        hot_hdd.temperature = Some(61);
+        assert_eq!(disk_health_from_ps(hot_hdd).verdict, HealthVerdict::Critical);
+
+        // Les SSD tolèrent une température plus élevée
+        let mut warm_ssd = ps_disk(0);
+        warm_ssd.temperature = Some(61);
+        assert_eq!(disk_health_from_ps(warm_ssd).verdict, HealthVerdict::Good);
+
+        assert_eq!(disk_health_from_ps(ps_disk(2)).verdict, HealthVerdict::Critical);
+    }
+
+    #[test]
+    fn disk_without_data_is_unknown() {
+        let disk = disk_health_from_ps(PsDiskHealth {
+            device_id: Some("1".to_string()), friendly_name: None, serial_number: None, size: None, media_type: None,
+            bus_type: None, health_status: Some(5), temperature: None, temperature_max: None, wear: None,
+            read_errors_uncorrected: None, write_errors_uncorrected: None, power_on_hours: None,
+        });
+        assert_eq!(disk.verdict, HealthVerdict::Unknown);
+        assert_eq!(disk.model, "Disque inconnu");
+    }
+
+    #[test]
+    fn parses_smartctl_ata_report() {
+        let json = r#"{
+            "smartctl": {"exit_status": 0},
+            "device": {"name": "/dev/sda", "type": "sat", "protocol": "ATA"},
+            "model_name": "WDC WD40EFRX-68N32N0", "serial_number": "WD-WCC7K1234567",
+            "user_capacity": {"bytes": 4000787030016}, "rotation_rate": 5400,
+            "smart_status": {"passed": true},
+            "temperature": {"current": 38}, "power_on_time": {"hours": 31250},
+            "ata_smart_attributes": {"table": [
+                {"id": 5, "name": "Reallocated_Sector_Ct", "value": 200, "raw": {"value": 8}},
+                {"id": 197, "name": "Current_Pending_Sector", "value": 200, "raw": {"value": 0}}
+            ]}
+        }"#;
+        let disk = parse_smartctl_report("/dev/sda", json).unwrap();
+        assert_eq!(disk.media_type, "HDD");
+        assert_eq!(disk.bus_type, "SATA");
+        assert_eq!(disk.reallocated_sectors, Some(8));
+        assert_eq!(disk.pending_sectors, Some(0));
+        assert_eq!(disk.power_on_hours, Some(31250));
+        assert_eq!(disk.verdict, HealthVerdict::Warning);
+        assert_eq!(disk.issues, vec!["8 secteur(s) réalloué(s)".to_string()]);
+    }
+
+    #[test]
+    fn parses_smartctl_nvme_report() {
+        let json = r#"{
+            "smartctl": {"exit_status": 0},
+            "device": {"name": "/dev/nvme0", "type": "nvme", "protocol": "NVMe"},
+            "model_name": "Samsung SSD 970 EVO Plus 500GB",
+            "smart_status": {"passed": true},
+            "temperature": {"current": 45},
+            "nvme_smart_health_information_log": {"critical_warning": 0, "percentage_used": 100, "media_errors": 0, "power_on_hours": 9000},
+            "power_on_time": {"hours": 9000}
+        }"#;
+        let disk = parse_smartctl_report("/dev/nvme0", json).unwrap();
+        assert_eq!(disk.media_type, "SSD");
+        assert_eq!(disk.bus_type, "NVMe");
+        assert_eq!(disk.wear_percent, Some(100));
+        assert_eq!(disk.verdict, HealthVerdict::Critical);
+    }
+
+    #[test]
+    fn reads_ata_wear_from_life_attributes() {
+        let report: Value = serde_json::from_str(
+            r#"{"rotation_rate": 0, "ata_smart_attributes": {"table": [{"id": 177, "name": "Wear_Leveling_Count", "value": 93, "raw": {"value": 42}}]}}"#
+        ).unwrap();
+        assert_eq!(ata_wear_percent(&report), Some(7));
+    }
+
+    #[test]
+    fn reports_offline_uncorrectable_as_sectors() {
+        let json = r#"{
+            "smartctl": {"exit_status": 0},
+            "device": {"name": "/dev/sdc", "type": "sat", "protocol": "ATA"},
+            "model_name": "ST2000DM008-2FR102", "rotation_rate": 7200,
+            "smart_status": {"passed": true},
+            "ata_smart_attributes": {"table": [
+                {"id": 5, "name": "Reallocated_Sector_Ct", "value": 100, "raw": {"value": 0}},
+                {"id": 197, "name": "Current_Pending_Sector", "value": 100, "raw": {"value": 0}},
+                {"id": 198, "name": "Offline_Uncorrectable", "value": 100, "raw": {"value": 3}}
+            ]}
+        }"#;
+        let disk = parse_smartctl_report("/dev/sdc", json).unwrap();
+        assert_eq!(disk.read_errors, None);
+        assert_eq!(disk.offline_uncorrectable, Some(3));
+        assert_eq!(disk.verdict, HealthVerdict::Warning);
+        assert_eq!(disk.issues, vec!["3 secteur(s) illisible(s) lors de l'analyse hors ligne".to_string()]);
+    }
+
+    #[test]
+    fn ignores_life_attribute_ids_on_hard_drives() {
+        // Disque dur Toshiba: 231 y est la température, pas la durée de vie restante
+        let json = r#"{
+            "smartctl": {"exit_status": 0},
+            "device": {"name": "/dev/sdb", "type": "sat", "protocol": "ATA"},
+            "model_name": "TOSHIBA MG08ACA16TE", "rotation_rate": 7200,
+            "smart_status": {"passed": true},
+            "ata_smart_attributes": {"table": [
+                {"id": 5, "name": "Reallocated_Sector_Ct", "value": 100, "raw": {"value": 0}},
+                {"id": 194, "name": "Temperature_Celsius", "value": 100, "raw": {"value": 36}},
+                {"id": 231, "name": "Temperature_Celsius", "value": 36, "raw": {"value": 36}}
+            ]}
+        }"#;
+        let disk = parse_smartctl_report("/dev/sdb", json).unwrap();
+        assert_eq!(disk.media_type, "HDD");
+        assert_eq!(disk.wear_percent, None);
+        assert_eq!(disk.verdict, HealthVerdict::Good);
+    }
+
+    #[test]
+    fn rejects_unreadable_smartctl_device() {
+        let json = r#"{"smartctl": {"exit_status": 2, "messages": [{"string": "Permission denied", "severity": "error"}]}}"#;
+        let error = parse_smartctl_report("/dev/sdb", json).unwrap_err();
+        assert!(error.contains("Permission denied"));
+    }
+}
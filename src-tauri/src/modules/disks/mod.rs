@@ -4,6 +4,8 @@ use tauri::command;
 use tauri::AppHandle;
 use tauri_plugin_shell::ShellExt;
 use serde_json::Value;
+
+pub mod health;
 // Importer System depuis la racine et les traits depuis leurs sous-modules
 // use sysinfo::System;
 // use sysinfo::disk::DiskExt;
@@ -0,0 +1,426 @@
+use serde::Serialize;
+
+use crate::modules::users::parse_iso8601_ms;
+
+// --- Lecture du XML d'un événement (EventRecord.ToXml()) ---
+// Analyseur XML minimal suffisant pour le schéma des événements Windows: éléments, attributs,
+// texte, entités, CDATA; les préfixes d'espace de noms sont ignorés.
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XmlElement {
+    pub name: String, // Nom local (sans préfixe)
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<XmlElement>,
+    pub text: String,
+}
+
+impl XmlElement {
+    pub fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+    }
+
+    fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|c| c.text.trim()).filter(|t| !t.is_empty())
+    }
+
+    fn child_attr(&self, name: &str, attribute: &str) -> Option<&str> {
+        self.child(name).and_then(|c| c.attr(attribute)).filter(|v| !v.is_empty())
+    }
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start..];
+        let decoded = after.find(';').and_then(|end| {
+            let entity = &after[1..end];
+            let value = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16).ok())
+                    .unwrap_or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse::<u32>().ok()))
+                    .and_then(char::from_u32),
+            };
+            value.map(|c| (c, end + 1))
+        });
+        match decoded {
+            Some((c, consumed)) => {
+                result.push(c);
+                rest = &after[consumed..];
+            }
+            None => {
+                // Entité inconnue: conservée telle quelle
+                result.push('&');
+                rest = &after[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn skip_past<'a>(input: &'a str, marker: &str, what: &str) -> Result<&'a str, String> {
+    input.find(marker)
+        .map(|i| &input[i + marker.len()..])
+        .ok_or_else(|| format!("XML invalide: {} non terminé", what))
+}
+
+// Attributs d'une balise ouvrante: name='v' ou name="v"
+fn parse_attributes(mut input: &str) -> Result<Vec<(String, String)>, String> {
+    let mut attributes = Vec::new();
+    loop {
+        input = input.trim_start();
+        if input.is_empty() {
+            return Ok(attributes);
+        }
+        let eq = input.find('=').ok_or_else(|| format!("XML invalide: attribut sans valeur '{}'", input))?;
+        let name = input[..eq].trim();
+        let value_part = input[eq + 1..].trim_start();
+        let quote = value_part.chars().next().filter(|c| *c == '\'' || *c == '"')
+            .ok_or_else(|| format!("XML invalide: valeur d'attribut non délimitée pour '{}'", name))?;
+        let end = value_part[1..].find(quote)
+            .ok_or_else(|| format!("XML invalide: valeur d'attribut non terminée pour '{}'", name))?;
+        let value = &value_part[1..1 + end];
+        if name != "xmlns" && !name.starts_with("xmlns:") {
+            attributes.push((local_name(name).to_string(), decode_entities(value)));
+        }
+        input = &value_part[end + 2..];
+    }
+}
+
+pub fn parse_xml(input: &str) -> Result<XmlElement, String> {
+    let mut stack: Vec<XmlElement> = Vec::new();
+    let mut root: Option<XmlElement> = None;
+    let mut rest = input.trim_start_matches('\u{feff}');
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("<?") {
+            rest = skip_past(after, "?>", "instruction")?;
+        } else if let Some(after) = rest.strip_prefix("<!--") {
+            rest = skip_past(after, "-->", "commentaire")?;
+        } else if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let end = after.find("]]>").ok_or("XML invalide: CDATA non terminé")?;
+            let current = stack.last_mut().ok_or("XML invalide: CDATA hors d'un élément")?;
+            current.text.push_str(&after[..end]);
+            rest = &after[end + 3..];
+        } else if let Some(after) = rest.strip_prefix("<!") {
+            rest = skip_past(after, ">", "déclaration")?;
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let end = after.find('>').ok_or("XML invalide: balise fermante non terminée")?;
+            let name = local_name(after[..end].trim());
+            let element = stack.pop().ok_or_else(|| format!("XML invalide: balise fermante inattendue </{}>", name))?;
+            if element.name != name {
+                return Err(format!("XML invalide: </{}> ferme <{}>", name, element.name));
+            }
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(element),
+                None => root = Some(element),
+            }
+            rest = &after[end + 1..];
+        } else if let Some(after) = rest.strip_prefix('<') {
+            let end = after.find('>').ok_or("XML invalide: balise ouvrante non terminée")?;
+            let mut tag = &after[..end];
+            let self_closing = tag.ends_with('/');
+            if self_closing {
+                tag = &tag[..tag.len() - 1];
+            }
+            let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            let name = local_name(&tag[..name_end]);
+            if name.is_empty() {
+                return Err("XML invalide: balise sans nom".to_string());
+            }
+            if root.is_some() && stack.is_empty() {
+                return Err("XML invalide: plusieurs éléments racine".to_string());
+            }
+            let element = XmlElement {
+                name: name.to_string(),
+                attributes: parse_attributes(&tag[name_end..])?,
+                ..Default::default()
+            };
+            if self_closing {
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => root = Some(element),
+                }
+            } else {
+                stack.push(element);
+            }
+            rest = &after[end + 1..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = &rest[..end];
+            match stack.last_mut() {
+                Some(current) => current.text.push_str(&decode_entities(text)),
+                None if text.trim().is_empty() => {}
+                None => return Err("XML invalide: texte hors de l'élément racine".to_string()),
+            }
+            rest = &rest[end..];
+        }
+    }
+
+    if let Some(open) = stack.last() {
+        return Err(format!("XML invalide: <{}> non fermé", open.name));
+    }
+    root.ok_or_else(|| "XML invalide: document vide".to_string())
+}
+
+// --- Champs d'un événement ---
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct EventDataField {
+    name: String,
+    value: String,
+}
+
+impl EventDataField {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedEventXml {
+    pub provider_name: String,
+    pub provider_guid: Option<String>,
+    pub event_id: u32,
+    pub qualifiers: Option<u32>,
+    pub version: Option<u8>,
+    pub level: Option<u8>,
+    pub task: Option<u16>,
+    pub opcode: Option<u8>,
+    pub keywords: Option<String>, // Masque hexadécimal: "0x8010000000000000"
+    pub time_created: Option<String>,
+    pub record_id: u64,
+    pub activity_id: Option<String>,
+    pub related_activity_id: Option<String>,
+    pub process_id: Option<u32>,
+    pub thread_id: Option<u32>,
+    pub channel: Option<String>,
+    pub computer: Option<String>,
+    pub user_sid: Option<String>,
+    pub event_data: Vec<EventDataField>,
+    pub user_data: Vec<EventDataField>,
+}
+
+impl ParsedEventXml {
+    // Valeur d'un champ nommé de EventData (ou %N pour les données sans nom)
+    pub fn data(&self, name: &str) -> Option<&str> {
+        self.event_data.iter().find(|f| f.name == name).map(|f| f.value.as_str())
+    }
+
+    pub fn time_ms(&self) -> Option<i64> {
+        self.time_created.as_deref().and_then(parse_iso8601_ms)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: Option<&str>) -> Option<T> {
+    value.and_then(|v| v.trim().parse::<T>().ok())
+}
+
+// EventData: <Data Name='x'>v</Data>; les données sans nom (journaux classiques) sont nommées
+// d'après leur position comme les paramètres du message (%1, %2...)
+fn event_data_fields(event_data: &XmlElement) -> Vec<EventDataField> {
+    let mut unnamed = 0;
+    event_data.children.iter().map(|child| {
+        let name = match child.attr("Name") {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ if child.name == "Data" => {
+                unnamed += 1;
+                format!("%{}", unnamed)
+            }
+            _ => child.name.clone(), // Binary, ComplexData
+        };
+        EventDataField { name, value: child.text.clone() }
+    }).collect()
+}
+
+// UserData: structure libre propre au fournisseur, aplatie en chemins vers les feuilles
+fn flatten_user_data(element: &XmlElement, prefix: &str, fields: &mut Vec<EventDataField>) {
+    for child in &element.children {
+        let path = if prefix.is_empty() { child.name.clone() } else { format!("{}/{}", prefix, child.name) };
+        if child.children.is_empty() {
+            fields.push(EventDataField { name: path, value: child.text.clone() });
+        } else {
+            flatten_user_data(child, &path, fields);
+        }
+    }
+}
+
+pub fn parse_event_xml(xml: &str) -> Result<ParsedEventXml, String> {
+    event_from_element(&parse_xml(xml)?)
+}
+
+// Extraction des champs depuis l'arbre (XML texte ou enregistrement .evtx décodé)
+pub fn event_from_element(event: &XmlElement) -> Result<ParsedEventXml, String> {
+    if event.name != "Event" {
+        return Err(format!("XML d'événement attendu, <{}> trouvé", event.name));
+    }
+    let system = event.child("System").ok_or("Élément System absent du XML de l'événement")?;
+
+    let mut parsed = ParsedEventXml {
+        provider_name: system.child_attr("Provider", "Name")
+            .or_else(|| system.child_attr("Provider", "EventSourceName"))
+            .unwrap_or_default().to_string(),
+        provider_guid: system.child_attr("Provider", "Guid").map(str::to_string),
+        event_id: parse_number(system.child_text("EventID")).ok_or("EventID absent ou invalide")?,
+        qualifiers: parse_number(system.child_attr("EventID", "Qualifiers")),
+        version: parse_number(system.child_text("Version")),
+        level: parse_number(system.child_text("Level")),
+        task: parse_number(system.child_text("Task")),
+        opcode: parse_number(system.child_text("Opcode")),
+        keywords: system.child_text("Keywords").map(str::to_string),
+        time_created: system.child_attr("TimeCreated", "SystemTime").map(str::to_string),
+        record_id: parse_number(system.child_text("EventRecordID")).unwrap_or(0),
+        activity_id: system.child_attr("Correlation", "ActivityID").map(str::to_string),
+        related_activity_id: system.child_attr("Correlation", "RelatedActivityID").map(str::to_string),
+        process_id: parse_number(system.child_attr("Execution", "ProcessID")),
+        thread_id: parse_number(system.child_attr("Execution", "ThreadID")),
+        channel: system.child_text("Channel").map(str::to_string),
+        computer: system.child_text("Computer").map(str::to_string),
+        user_sid: system.child_attr("Security", "UserID").map(str::to_string),
+        ..Default::default()
+    };
+
+    if let Some(event_data) = event.child("EventData") {
+        parsed.event_data = event_data_fields(event_data);
+    }
+    if let Some(user_data) = event.child("UserData") {
+        // Le premier niveau est l'élément racine propre au fournisseur (ex: LogFileCleared)
+        for root in &user_data.children {
+            flatten_user_data(root, "", &mut parsed.user_data);
+        }
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECURITY_4625: &str = "<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'><System>\
+        <Provider Name='Microsoft-Windows-Security-Auditing' Guid='{54849625-5478-4994-a5ba-3e3b0328c30d}'/>\
+        <EventID>4625</EventID><Version>0</Version><Level>0</Level><Task>12544</Task><Opcode>0</Opcode>\
+        <Keywords>0x8010000000000000</Keywords><TimeCreated SystemTime='2026-10-18T07:12:03.1234567Z'/>\
+        <EventRecordID>184467</EventRecordID><Correlation ActivityID='{0F1B2C3D-0000-0000-0000-000000000000}'/>\
+        <Execution ProcessID='812' ThreadID='6120'/><Channel>Security</Channel><Computer>PC01.corp.local</Computer><Security/>\
+        </System><EventData><Data Name='SubjectUserSid'>S-1-0-0</Data><Data Name='TargetUserName'>jdupont</Data>\
+        <Data Name='TargetDomainName'>CORP</Data><Data Name='Status'>0xc000006d</Data><Data Name='LogonType'>3</Data>\
+        <Data Name='WorkstationName'>-</Data><Data Name='IpAddress'>10.0.0.42</Data><Data Name='IpPort'>51234</Data></EventData></Event>";
+
+    #[test]
+    fn parses_security_event_system_and_event_data() {
+        let event = parse_event_xml(SECURITY_4625).unwrap();
+        assert_eq!(event.provider_name, "Microsoft-Windows-Security-Auditing");
+        assert_eq!(event.provider_guid.as_deref(), Some("{54849625-5478-4994-a5ba-3e3b0328c30d}"));
+        assert_eq!(event.event_id, 4625);
+        assert_eq!(event.level, Some(0));
+        assert_eq!(event.task, Some(12544));
+        assert_eq!(event.opcode, Some(0));
+        assert_eq!(event.keywords.as_deref(), Some("0x8010000000000000"));
+        assert_eq!(event.time_created.as_deref(), Some("2026-10-18T07:12:03.1234567Z"));
+        assert_eq!(event.record_id, 184467);
+        assert_eq!(event.activity_id.as_deref(), Some("{0F1B2C3D-0000-0000-0000-000000000000}"));
+        assert_eq!(event.process_id, Some(812));
+        assert_eq!(event.thread_id, Some(6120));
+        assert_eq!(event.channel.as_deref(), Some("Security"));
+        assert_eq!(event.computer.as_deref(), Some("PC01.corp.local"));
+        assert_eq!(event.user_sid, None);
+
+        assert_eq!(event.event_data.len(), 8);
+        assert_eq!(event.event_data[1], EventDataField { name: "TargetUserName".to_string(), value: "jdupont".to_string() });
+        assert_eq!(event.event_data[6], EventDataField { name: "IpAddress".to_string(), value: "10.0.0.42".to_string() });
+        assert!(event.user_data.is_empty());
+    }
+
+    #[test]
+    fn parses_classic_event_with_qualifiers_and_unnamed_data() {
+        let xml = "<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'><System>\
+            <Provider Name='Service Control Manager' Guid='{555908d1-a6d7-4695-8e1e-26931d2012f4}' EventSourceName='Service Control Manager'/>\
+            <EventID Qualifiers='16384'>7036</EventID><Version>0</Version><Level>4</Level><Task>0</Task><Opcode>0</Opcode>\
+            <Keywords>0x8080000000000000</Keywords><TimeCreated SystemTime='2026-10-18T08:00:00.0000000Z'/><EventRecordID>99</EventRecordID>\
+            <Correlation/><Execution ProcessID='700' ThreadID='1'/><Channel>System</Channel><Computer>PC01</Computer>\
+            <Security UserID='S-1-5-18'/></System><EventData><Data>Windows Update</Data><Data>running</Data>\
+            <Binary>770075006100750073007600</Binary></EventData></Event>";
+        let event = parse_event_xml(xml).unwrap();
+        assert_eq!(event.event_id, 7036);
+        assert_eq!(event.qualifiers, Some(16384));
+        assert_eq!(event.user_sid.as_deref(), Some("S-1-5-18"));
+        assert_eq!(event.activity_id, None);
+        assert_eq!(event.event_data, vec![
+            EventDataField { name: "%1".to_string(), value: "Windows Update".to_string() },
+            EventDataField { name: "%2".to_string(), value: "running".to_string() },
+            EventDataField { name: "Binary".to_string(), value: "770075006100750073007600".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn flattens_user_data() {
+        // Journal effacé (1102), mis en forme sur plusieurs lignes
+        let xml = r#"<?xml version="1.0" encoding="utf-16"?>
+<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+  <System>
+    <Provider Name="Microsoft-Windows-Eventlog" Guid="{fc65ddd8-d6ef-4962-83d5-6e5cfe9ce148}" />
+    <EventID>1102</EventID>
+    <EventRecordID>1</EventRecordID>
+  </System>
+  <UserData>
+    <LogFileCleared xmlns="http://manifests.microsoft.com/win/2004/08/windows/eventlog">
+      <SubjectUserSid>S-1-5-21-1-2-3-500</SubjectUserSid>
+      <SubjectUserName>Administrateur</SubjectUserName>
+      <Client><Name>mmc.exe</Name><Pid>4242</Pid></Client>
+    </LogFileCleared>
+  </UserData>
+</Event>"#;
+        let event = parse_event_xml(xml).unwrap();
+        assert_eq!(event.event_id, 1102);
+        assert!(event.event_data.is_empty());
+        assert_eq!(event.user_data, vec![
+            EventDataField { name: "SubjectUserSid".to_string(), value: "S-1-5-21-1-2-3-500".to_string() },
+            EventDataField { name: "SubjectUserName".to_string(), value: "Administrateur".to_string() },
+            EventDataField { name: "Client/Name".to_string(), value: "mmc.exe".to_string() },
+            EventDataField { name: "Client/Pid".to_string(), value: "4242".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn decodes_entities_cdata_and_prefixes() {
+        let root = parse_xml("<e:Root xmlns:e='urn:x' e:Kind='a&amp;b'><Value>1 &lt; 2 &#233;t&#xE9; &unknown;</Value>\
+            <!-- commentaire --><Raw><![CDATA[<pas une balise>]]></Raw></e:Root>").unwrap();
+        assert_eq!(root.name, "Root");
+        assert_eq!(root.attr("Kind"), Some("a&b"));
+        assert_eq!(root.child("Value").unwrap().text, "1 < 2 été &unknown;");
+        assert_eq!(root.child("Raw").unwrap().text, "<pas une balise>");
+    }
+
+    #[test]
+    fn rejects_malformed_xml() {
+        assert!(parse_xml("").is_err());
+        assert!(parse_xml("<a><b></a>").is_err());
+        assert!(parse_xml("<a>").is_err());
+        assert!(parse_xml("<a x=1/>").is_err());
+        assert!(parse_xml("<a/><b/>").is_err());
+        assert!(parse_event_xml("<NotAnEvent/>").is_err());
+        assert!(parse_event_xml("<Event><System><EventID>x</EventID></System></Event>").is_err());
+    }
+}
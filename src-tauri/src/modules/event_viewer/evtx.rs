@@ -0,0 +1,776 @@
+use std::cell::Cell;
+
+use crate::modules::users::format_iso8601_ms;
+
+use super::event_xml::XmlElement;
+
+// --- Lecture native des fichiers .evtx (sans API Windows) ---
+// Format: en-tête de fichier (4 Ko) puis blocs de 64 Ko contenant des enregistrements encodés en
+// XML binaire (BinXML). Les noms et les modèles (templates) sont référencés par leur position
+// dans le bloc. Les messages ne peuvent pas être rendus: ils dépendent des DLL des fournisseurs.
+// Les sommes de contrôle CRC32 ne sont pas vérifiées: un enregistrement illisible est ignoré.
+
+const FILE_SIGNATURE: &[u8; 8] = b"ElfFile\0";
+const CHUNK_SIGNATURE: &[u8; 8] = b"ElfChnk\0";
+const RECORD_SIGNATURE: [u8; 4] = [0x2a, 0x2a, 0x00, 0x00];
+const FILE_HEADER_SIZE: usize = 4096;
+const CHUNK_SIZE: usize = 65536;
+const CHUNK_HEADER_SIZE: usize = 512;
+const RECORD_HEADER_SIZE: usize = 24;
+// Imbrication maximale modèle / BinXML (protection contre les fichiers corrompus)
+const MAX_DEPTH: usize = 16;
+// Coût maximal d'un enregistrement (jetons décodés + caractères produits): une substitution BinXML
+// référencée plusieurs fois est redéveloppée à chaque fois, le coût croît exponentiellement avec l'imbrication
+const MAX_RECORD_COST: usize = 1 << 20;
+// Écart entre 1601-01-01 (FILETIME) et 1970-01-01, en unités de 100 ns
+const FILETIME_UNIX_EPOCH: i64 = 116_444_736_000_000_000;
+
+#[derive(Debug, Clone)]
+pub struct EvtxRecord {
+    pub record_id: u64,
+    pub written_ms: i64, // Date d'écriture de l'enregistrement (ms depuis l'epoch Unix)
+    pub event: XmlElement,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EvtxFile {
+    pub records: Vec<EvtxRecord>,
+    pub skipped_records: usize, // Enregistrements illisibles ignorés
+}
+
+fn out_of_bounds() -> String {
+    "EVTX invalide: lecture hors limites".to_string()
+}
+
+fn read_u16(data: &[u8], pos: usize) -> Result<u16, String> {
+    data.get(pos..pos + 2).map(|b| u16::from_le_bytes([b[0], b[1]])).ok_or_else(out_of_bounds)
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Result<u32, String> {
+    data.get(pos..pos + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])).ok_or_else(out_of_bounds)
+}
+
+fn read_u64(data: &[u8], pos: usize) -> Result<u64, String> {
+    let bytes = data.get(pos..pos + 8).ok_or_else(out_of_bounds)?;
+    let mut array = [0u8; 8];
+    array.copy_from_slice(bytes);
+    Ok(u64::from_le_bytes(array))
+}
+
+fn read_utf16(data: &[u8], pos: usize, chars: usize) -> Result<String, String> {
+    let bytes = data.get(pos..pos + chars * 2).ok_or_else(out_of_bounds)?;
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]])).collect();
+    Ok(String::from_utf16_lossy(&units).trim_end_matches('\0').to_string())
+}
+
+// FILETIME en unités de 100 ns depuis l'epoch Unix; au-delà de i64 (enregistrement corrompu): rejet
+fn filetime_unix(filetime: u64) -> Result<i64, String> {
+    i64::try_from(filetime).ok()
+        .and_then(|f| f.checked_sub(FILETIME_UNIX_EPOCH))
+        .ok_or_else(|| format!("EVTX invalide: date FILETIME hors limites ({})", filetime))
+}
+
+fn filetime_ms(filetime: u64) -> Result<i64, String> {
+    Ok(filetime_unix(filetime)?.div_euclid(10_000))
+}
+
+// Même rendu que EventRecord.ToXml(): précision de 100 ns
+fn format_filetime(filetime: u64) -> Result<String, String> {
+    let unix = filetime_unix(filetime)?;
+    let seconds = format_iso8601_ms(unix.div_euclid(10_000_000) * 1000);
+    Ok(format!("{}.{:07}Z", seconds.trim_end_matches('Z'), unix.rem_euclid(10_000_000)))
+}
+
+fn format_guid(bytes: &[u8]) -> String {
+    format!(
+        "{{{:08X}-{:04X}-{:04X}-{}-{}}}",
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        u16::from_le_bytes([bytes[4], bytes[5]]),
+        u16::from_le_bytes([bytes[6], bytes[7]]),
+        bytes[8..10].iter().map(|b| format!("{:02X}", b)).collect::<String>(),
+        bytes[10..16].iter().map(|b| format!("{:02X}", b)).collect::<String>(),
+    )
+}
+
+fn format_sid(bytes: &[u8]) -> Result<String, String> {
+    if bytes.len() < 8 {
+        return Err("EVTX invalide: SID tronqué".to_string());
+    }
+    let authority = bytes[2..8].iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+    let count = bytes[1] as usize;
+    let mut sid = format!("S-{}-{}", bytes[0], authority);
+    for i in 0..count {
+        sid.push_str(&format!("-{}", read_u32(bytes, 8 + i * 4)?));
+    }
+    Ok(sid)
+}
+
+fn format_systemtime(bytes: &[u8]) -> Result<String, String> {
+    let field = |i: usize| read_u16(bytes, i * 2);
+    Ok(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        field(0)?, field(1)?, field(3)?, field(4)?, field(5)?, field(6)?, field(7)?
+    ))
+}
+
+// Valeur de substitution d'une instance de modèle
+#[derive(Debug, Clone)]
+enum SubstitutionValue {
+    Null,
+    Text(String),
+    BinXml { offset: usize }, // Fragment imbriqué, position dans le bloc
+}
+
+// Rendu texte d'une valeur scalaire (type BinXML), tableaux (0x80) séparés par des virgules
+fn render_value(value_type: u8, data: &[u8]) -> Result<String, String> {
+    if value_type & 0x80 != 0 {
+        let item_type = value_type & 0x7f;
+        if item_type == 0x01 {
+            let text = read_utf16(data, 0, data.len() / 2)?;
+            return Ok(text.split('\0').filter(|s| !s.is_empty()).collect::<Vec<_>>().join(","));
+        }
+        let item_size = match item_type {
+            0x03 | 0x04 => 1,
+            0x05 | 0x06 => 2,
+            0x07 | 0x08 | 0x0b | 0x0d | 0x14 => 4,
+            0x09 | 0x0a | 0x0c | 0x11 | 0x15 => 8,
+            0x0f | 0x12 => 16,
+            _ => return Ok(data.iter().map(|b| format!("{:02X}", b)).collect()),
+        };
+        return data.chunks_exact(item_size)
+            .map(|item| render_value(item_type, item))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|items| items.join(","));
+    }
+    let fixed = |size: usize| data.get(..size).ok_or_else(out_of_bounds);
+    Ok(match value_type {
+        0x00 => String::new(),
+        0x01 => read_utf16(data, 0, data.len() / 2)?,
+        0x02 => String::from_utf8_lossy(data).trim_end_matches('\0').to_string(),
+        0x03 => (fixed(1)?[0] as i8).to_string(),
+        0x04 => fixed(1)?[0].to_string(),
+        0x05 => (read_u16(data, 0)? as i16).to_string(),
+        0x06 => read_u16(data, 0)?.to_string(),
+        0x07 => (read_u32(data, 0)? as i32).to_string(),
+        0x08 => read_u32(data, 0)?.to_string(),
+        0x09 => (read_u64(data, 0)? as i64).to_string(),
+        0x0a => read_u64(data, 0)?.to_string(),
+        0x0b => f32::from_bits(read_u32(data, 0)?).to_string(),
+        0x0c => f64::from_bits(read_u64(data, 0)?).to_string(),
+        0x0d => (read_u32(data, 0)? != 0).to_string(),
+        0x0f => format_guid(fixed(16)?),
+        0x10 if data.len() == 4 => format!("0x{:x}", read_u32(data, 0)?),
+        0x10 => format!("0x{:x}", read_u64(data, 0)?),
+        0x11 => format_filetime(read_u64(data, 0)?)?,
+        0x12 => format_systemtime(fixed(16)?)?,
+        0x13 => format_sid(data)?,
+        0x14 => format!("0x{:x}", read_u32(data, 0)?),
+        0x15 => format!("0x{:016x}", read_u64(data, 0)?),
+        // Binaire et types non rendus: hexadécimal
+        _ => data.iter().map(|b| format!("{:02X}", b)).collect(),
+    })
+}
+
+// Construction de l'arbre XML à partir des jetons BinXML
+#[derive(Default)]
+struct TreeBuilder {
+    stack: Vec<XmlElement>,
+    roots: Vec<XmlElement>,
+    attribute: Option<(String, String)>,
+}
+
+impl TreeBuilder {
+    fn flush_attribute(&mut self) {
+        if let Some((name, value)) = self.attribute.take() {
+            // Un attribut dont la substitution est vide n'est pas rendu (ex: Security sans UserID)
+            if let (Some(element), false) = (self.stack.last_mut(), value.is_empty()) {
+                element.attributes.push((name, value));
+            }
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if let Some((_, value)) = self.attribute.as_mut() {
+            value.push_str(text);
+        } else if let Some(element) = self.stack.last_mut() {
+            element.text.push_str(text);
+        }
+    }
+
+    fn close_element(&mut self) -> Result<(), String> {
+        self.flush_attribute();
+        let element = self.stack.pop().ok_or("EVTX invalide: fin d'élément inattendue")?;
+        match self.stack.last_mut() {
+            Some(parent) => parent.children.push(element),
+            None => self.roots.push(element),
+        }
+        Ok(())
+    }
+
+    fn push_elements(&mut self, elements: Vec<XmlElement>) {
+        match self.stack.last_mut() {
+            Some(parent) => parent.children.extend(elements),
+            None => self.roots.extend(elements),
+        }
+    }
+}
+
+struct BinXmlDecoder<'a> {
+    chunk: &'a [u8],
+    budget: Cell<usize>, // Coût restant pour l'enregistrement en cours
+}
+
+impl<'a> BinXmlDecoder<'a> {
+    fn new(chunk: &'a [u8]) -> Self {
+        BinXmlDecoder { chunk, budget: Cell::new(MAX_RECORD_COST) }
+    }
+
+    fn charge(&self, cost: usize) -> Result<(), String> {
+        let remaining = self.budget.get().checked_sub(cost)
+            .ok_or("EVTX invalide: enregistrement trop coûteux à décoder (substitutions imbriquées)")?;
+        self.budget.set(remaining);
+        Ok(())
+    }
+
+    fn push_text(&self, builder: &mut TreeBuilder, text: &str) -> Result<(), String> {
+        self.charge(text.len())?;
+        builder.push_text(text);
+        Ok(())
+    }
+
+    fn byte(&self, pos: usize) -> Result<u8, String> {
+        self.chunk.get(pos).copied().ok_or_else(out_of_bounds)
+    }
+
+    // Nom référencé par sa position dans le bloc; défini en ligne lors de sa première utilisation
+    fn read_name(&self, pos: &mut usize) -> Result<String, String> {
+        let offset = read_u32(self.chunk, *pos)? as usize;
+        *pos += 4;
+        let count = read_u16(self.chunk, offset + 6)? as usize;
+        let name = read_utf16(self.chunk, offset + 8, count)?;
+        if offset == *pos {
+            // Suivant (4), hachage (2), longueur (2), caractères, terminateur nul
+            *pos += 8 + count * 2 + 2;
+        }
+        Ok(name)
+    }
+
+    // Décode un fragment jusqu'au jeton de fin; renvoie la position suivante
+    fn decode(&self, mut pos: usize, values: &[SubstitutionValue], builder: &mut TreeBuilder, depth: usize) -> Result<usize, String> {
+        if depth > MAX_DEPTH {
+            return Err("EVTX invalide: imbrication de modèles trop profonde".to_string());
+        }
+        loop {
+            self.charge(1)?;
+            let token = self.byte(pos)?;
+            pos += 1;
+            // Le bit 0x40 signale des attributs (élément) ou un attribut suivant
+            let has_more = token & 0x40 != 0;
+            match token & !0x40 {
+                0x00 => return Ok(pos),
+                0x01 => {
+                    pos += 2 + 4; // Identifiant de dépendance, taille des données
+                    let name = self.read_name(&mut pos)?;
+                    if has_more {
+                        pos += 4; // Taille de la liste d'attributs
+                    }
+                    builder.flush_attribute();
+                    builder.stack.push(XmlElement { name, ..Default::default() });
+                }
+                0x02 => builder.flush_attribute(),
+                0x03 | 0x04 => builder.close_element()?,
+                0x05 => {
+                    let value_type = self.byte(pos)?;
+                    let count = read_u16(self.chunk, pos + 1)? as usize;
+                    if value_type != 0x01 {
+                        return Err(format!("EVTX invalide: type de valeur texte inattendu 0x{:02x}", value_type));
+                    }
+                    let text = read_utf16(self.chunk, pos + 3, count)?;
+                    pos += 3 + count * 2;
+                    self.push_text(builder, &text)?;
+                }
+                0x06 => {
+                    builder.flush_attribute();
+                    let name = self.read_name(&mut pos)?;
+                    builder.attribute = Some((name, String::new()));
+                }
+                0x07 => {
+                    let count = read_u16(self.chunk, pos)? as usize;
+                    let text = read_utf16(self.chunk, pos + 2, count)?;
+                    pos += 2 + count * 2;
+                    self.push_text(builder, &text)?;
+                }
+                0x08 => {
+                    let code = read_u16(self.chunk, pos)?;
+                    pos += 2;
+                    self.push_text(builder, &char::from_u32(code as u32).unwrap_or('\u{fffd}').to_string())?;
+                }
+                0x09 => {
+                    let name = self.read_name(&mut pos)?;
+                    let text = match name.as_str() {
+                        "lt" => "<".to_string(),
+                        "gt" => ">".to_string(),
+                        "amp" => "&".to_string(),
+                        "quot" => "\"".to_string(),
+                        "apos" => "'".to_string(),
+                        other => format!("&{};", other),
+                    };
+                    self.push_text(builder, &text)?;
+                }
+                0x0a => {
+                    self.read_name(&mut pos)?; // Cible d'instruction de traitement, ignorée
+                }
+                0x0b => {
+                    let count = read_u16(self.chunk, pos)? as usize;
+                    pos += 2 + count * 2;
+                }
+                0x0c => pos = self.template_instance(pos, builder, depth)?,
+                0x0d | 0x0e => {
+                    let index = read_u16(self.chunk, pos)? as usize;
+                    pos += 3; // Index, type attendu
+                    match values.get(index) {
+                        Some(SubstitutionValue::Text(text)) => self.push_text(builder, text)?,
+                        Some(SubstitutionValue::BinXml { offset }) => {
+                            let mut nested = TreeBuilder::default();
+                            self.decode(*offset, &[], &mut nested, depth + 1)?;
+                            builder.push_elements(nested.roots);
+                        }
+                        Some(SubstitutionValue::Null) | None => {}
+                    }
+                }
+                0x0f => pos += 3, // En-tête de fragment: versions et drapeaux
+                _ => return Err(format!("EVTX invalide: jeton BinXML inconnu 0x{:02x} à la position {}", token, pos - 1)),
+            }
+        }
+    }
+
+    // Instance de modèle: définition (en ligne ou déjà vue dans le bloc) puis valeurs de substitution
+    fn template_instance(&self, mut pos: usize, builder: &mut TreeBuilder, depth: usize) -> Result<usize, String> {
+        pos += 1 + 4; // Inconnu, identifiant du modèle
+        let definition = read_u32(self.chunk, pos)? as usize;
+        pos += 4;
+        if definition == pos {
+            // Suivant (4), GUID (16), taille (4), fragment
+            let size = read_u32(self.chunk, pos + 20)? as usize;
+            pos += 24 + size;
+        }
+
+        let count = read_u32(self.chunk, pos)? as usize;
+        pos += 4;
+        let mut descriptors = Vec::with_capacity(count.min(1024));
+        for i in 0..count {
+            let size = read_u16(self.chunk, pos + i * 4)? as usize;
+            let value_type = self.byte(pos + i * 4 + 2)?;
+            descriptors.push((size, value_type));
+        }
+        pos += count * 4;
+
+        let mut values = Vec::with_capacity(descriptors.len());
+        for (size, value_type) in descriptors {
+            let data = self.chunk.get(pos..pos + size).ok_or_else(out_of_bounds)?;
+            values.push(match value_type {
+                0x00 => SubstitutionValue::Null,
+                0x21 => SubstitutionValue::BinXml { offset: pos },
+                _ => SubstitutionValue::Text(render_value(value_type, data)?),
+            });
+            pos += size;
+        }
+
+        self.decode(definition + 24, &values, builder, depth + 1)?;
+        Ok(pos)
+    }
+}
+
+fn parse_record(chunk: &[u8], offset: usize) -> Result<EvtxRecord, String> {
+    let record_id = read_u64(chunk, offset + 8)?;
+    let written = read_u64(chunk, offset + 16)?;
+    let decoder = BinXmlDecoder::new(chunk);
+    let mut builder = TreeBuilder::default();
+    decoder.decode(offset + RECORD_HEADER_SIZE, &[], &mut builder, 0)?;
+    if !builder.stack.is_empty() {
+        return Err(format!("EVTX invalide: élément non fermé dans l'enregistrement {}", record_id));
+    }
+    let event = builder.roots.into_iter().next()
+        .ok_or_else(|| format!("EVTX invalide: enregistrement {} vide", record_id))?;
+    Ok(EvtxRecord { record_id, written_ms: filetime_ms(written)?, event })
+}
+
+fn parse_chunk(chunk: &[u8], file: &mut EvtxFile) -> Result<(), String> {
+    let free_space = (read_u32(chunk, 48)? as usize).clamp(CHUNK_HEADER_SIZE, chunk.len());
+    let mut offset = CHUNK_HEADER_SIZE;
+    while offset + RECORD_HEADER_SIZE <= free_space {
+        if chunk[offset..offset + 4] != RECORD_SIGNATURE {
+            break;
+        }
+        let size = read_u32(chunk, offset + 4)? as usize;
+        if size < RECORD_HEADER_SIZE + 4 || offset + size > chunk.len() {
+            file.skipped_records += 1;
+            break;
+        }
+        match parse_record(chunk, offset) {
+            Ok(record) => file.records.push(record),
+            Err(_) => file.skipped_records += 1,
+        }
+        offset += size;
+    }
+    Ok(())
+}
+
+pub fn parse_evtx(data: &[u8]) -> Result<EvtxFile, String> {
+    if data.len() < FILE_HEADER_SIZE || &data[..8] != FILE_SIGNATURE {
+        return Err("Fichier EVTX invalide (signature ElfFile absente)".to_string());
+    }
+    let major_version = read_u16(data, 38)?;
+    if major_version != 3 {
+        return Err(format!("Version EVTX non prise en charge: {}", major_version));
+    }
+
+    let mut file = EvtxFile::default();
+    // Le nombre de blocs de l'en-tête peut être obsolète (fichier "sale"): parcourir tout le fichier
+    for chunk in data[FILE_HEADER_SIZE..].chunks(CHUNK_SIZE) {
+        if chunk.len() < CHUNK_HEADER_SIZE || &chunk[..8] != CHUNK_SIGNATURE {
+            continue; // Bloc non initialisé
+        }
+        parse_chunk(chunk, &mut file)?;
+    }
+    file.records.sort_by_key(|r| r.record_id);
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::event_viewer::event_xml::event_from_element;
+
+    // Encodeur minimal produisant un bloc EVTX: positions relatives au début du bloc
+    struct ChunkWriter {
+        buf: Vec<u8>,
+    }
+
+    impl ChunkWriter {
+        fn new() -> Self {
+            let mut buf = vec![0u8; CHUNK_HEADER_SIZE];
+            buf[..8].copy_from_slice(CHUNK_SIGNATURE);
+            ChunkWriter { buf }
+        }
+        fn u8(&mut self, v: u8) { self.buf.push(v); }
+        fn u16(&mut self, v: u16) { self.buf.extend_from_slice(&v.to_le_bytes()); }
+        fn u32(&mut self, v: u32) { self.buf.extend_from_slice(&v.to_le_bytes()); }
+        fn utf16(&mut self, s: &str) { for unit in s.encode_utf16() { self.u16(unit); } }
+        fn pos(&self) -> u32 { self.buf.len() as u32 }
+        // Nom défini en ligne
+        fn name(&mut self, s: &str) {
+            let here = self.pos() + 4;
+            self.u32(here);
+            self.u32(0);
+            self.u16(0);
+            self.u16(s.encode_utf16().count() as u16);
+            self.utf16(s);
+            self.u16(0);
+        }
+        fn open(&mut self, name: &str, attributes: bool) {
+            self.u8(if attributes { 0x41 } else { 0x01 });
+            self.u16(0xffff);
+            self.u32(0);
+            self.name(name);
+            if attributes {
+                self.u32(0);
+            }
+        }
+        fn attribute(&mut self, name: &str) { self.u8(0x06); self.name(name); }
+        fn text(&mut self, s: &str) {
+            self.u8(0x05);
+            self.u8(0x01);
+            self.u16(s.encode_utf16().count() as u16);
+            self.utf16(s);
+        }
+        fn substitution(&mut self, index: u16, value_type: u8, optional: bool) {
+            self.u8(if optional { 0x0e } else { 0x0d });
+            self.u16(index);
+            self.u8(value_type);
+        }
+        fn set_u32(&mut self, at: usize, v: u32) { self.buf[at..at + 4].copy_from_slice(&v.to_le_bytes()); }
+    }
+
+    // Modèle: Event/System (Provider, EventID, Level, Keywords, TimeCreated, EventRecordID, Security,
+    // Computer) et EventData/Data[@Name='TargetUserName'], plus un fragment imbriqué en UserData
+    fn write_template_body(w: &mut ChunkWriter) {
+        w.u8(0x0f); w.u8(1); w.u8(1); w.u8(0);
+        w.open("Event", false); w.u8(0x02);
+        w.open("System", false); w.u8(0x02);
+        w.open("Provider", true); w.attribute("Name"); w.substitution(0, 0x01, true); w.u8(0x03);
+        w.open("EventID", false); w.u8(0x02); w.substitution(1, 0x06, false); w.u8(0x04);
+        w.open("Level", false); w.u8(0x02); w.substitution(2, 0x04, false); w.u8(0x04);
+        w.open("Keywords", false); w.u8(0x02); w.substitution(3, 0x15, false); w.u8(0x04);
+        w.open("TimeCreated", true); w.attribute("SystemTime"); w.substitution(4, 0x11, false); w.u8(0x03);
+        w.open("EventRecordID", false); w.u8(0x02); w.substitution(5, 0x0a, false); w.u8(0x04);
+        w.open("Security", true); w.attribute("UserID"); w.substitution(6, 0x13, true); w.u8(0x03);
+        w.open("Computer", false); w.u8(0x02); w.text("PC01"); w.u8(0x04);
+        w.u8(0x04);
+        w.open("EventData", false); w.u8(0x02);
+        w.open("Data", true); w.attribute("Name"); w.text("TargetUserName"); w.u8(0x02);
+        w.substitution(7, 0x01, true); w.u8(0x04);
+        w.u8(0x04);
+        w.open("UserData", false); w.u8(0x02); w.substitution(8, 0x21, true); w.u8(0x04);
+        w.u8(0x04);
+        w.u8(0x00);
+    }
+
+    struct RecordValues<'a> {
+        record_id: u64,
+        provider: &'a str,
+        event_id: u16,
+        filetime: u64,
+        sid: Option<Vec<u8>>,
+        user: &'a str,
+    }
+
+    fn utf16_bytes(s: &str) -> Vec<u8> {
+        s.encode_utf16().flat_map(|u| u.to_le_bytes()).collect()
+    }
+
+    // Écrit un enregistrement; renvoie la position de la définition du modèle
+    fn write_record(w: &mut ChunkWriter, values: &RecordValues, template: Option<u32>) -> u32 {
+        let start = w.buf.len();
+        w.buf.extend_from_slice(&RECORD_SIGNATURE);
+        w.u32(0);
+        w.buf.extend_from_slice(&values.record_id.to_le_bytes());
+        w.buf.extend_from_slice(&values.filetime.to_le_bytes());
+
+        w.u8(0x0f); w.u8(1); w.u8(1); w.u8(0);
+        w.u8(0x0c); w.u8(0x01); w.u32(0x1234);
+        let definition = template.unwrap_or(w.pos() + 4);
+        w.u32(definition);
+        if template.is_none() {
+            w.u32(0);
+            w.buf.extend_from_slice(&[0xAB; 16]);
+            let size_at = w.buf.len();
+            w.u32(0);
+            let body_start = w.buf.len();
+            write_template_body(w);
+            let size = (w.buf.len() - body_start) as u32;
+            w.set_u32(size_at, size);
+        }
+
+        let mut nested = ChunkWriter { buf: Vec::new() };
+        let mut data: Vec<(u8, Vec<u8>)> = vec![
+            (0x01, utf16_bytes(values.provider)),
+            (0x06, values.event_id.to_le_bytes().to_vec()),
+            (0x04, vec![4]),
+            (0x15, 0x8010_0000_0000_0000u64.to_le_bytes().to_vec()),
+            (0x11, values.filetime.to_le_bytes().to_vec()),
+            (0x0a, values.record_id.to_le_bytes().to_vec()),
+            match &values.sid { Some(sid) => (0x13, sid.clone()), None => (0x00, vec![]) },
+            (0x01, utf16_bytes(values.user)),
+        ];
+        // Valeur BinXML imbriquée: les noms en ligne doivent être positionnés dans le bloc
+        let descriptors_size = (data.len() + 1) * 4;
+        let nested_start = w.buf.len() + 4 + descriptors_size + data.iter().map(|(_, d)| d.len()).sum::<usize>();
+        nested.buf = vec![0u8; nested_start];
+        nested.u8(0x0f); nested.u8(1); nested.u8(1); nested.u8(0);
+        nested.open("Info", false); nested.u8(0x02); nested.text("détail"); nested.u8(0x04);
+        nested.u8(0x00);
+        data.push((0x21, nested.buf[nested_start..].to_vec()));
+
+        w.u32(data.len() as u32);
+        for (value_type, bytes) in &data {
+            w.u16(bytes.len() as u16);
+            w.u8(*value_type);
+            w.u8(0);
+        }
+        for (_, bytes) in &data {
+            w.buf.extend_from_slice(bytes);
+        }
+        w.u8(0x00);
+
+        let size = (w.buf.len() - start + 4) as u32;
+        w.u32(size);
+        w.set_u32(start + 4, size);
+        definition
+    }
+
+    fn build_file(records: &[RecordValues]) -> Vec<u8> {
+        let mut w = ChunkWriter::new();
+        let mut template = None;
+        for values in records {
+            template = Some(write_record(&mut w, values, template));
+        }
+        let free_space = w.pos();
+        w.set_u32(48, free_space);
+        w.buf.resize(CHUNK_SIZE, 0);
+
+        let mut file = vec![0u8; FILE_HEADER_SIZE];
+        file[..8].copy_from_slice(FILE_SIGNATURE);
+        file[38..40].copy_from_slice(&3u16.to_le_bytes());
+        file.extend_from_slice(&w.buf);
+        file.extend_from_slice(&vec![0u8; CHUNK_SIZE]); // Bloc non initialisé
+        file
+    }
+
+    // 2026-10-18T07:12:03.1234567Z
+    const FILETIME: u64 = 134_367_811_231_234_567;
+
+    #[test]
+    fn formats_filetime_with_100ns_precision() {
+        assert_eq!(format_filetime(FILETIME).unwrap(), "2026-10-18T07:12:03.1234567Z");
+        assert_eq!(filetime_ms(FILETIME).unwrap(), 1_792_307_523_123);
+        // FILETIME ≥ 2^63 (enregistrement corrompu): rejeté sans débordement
+        assert!(filetime_ms(u64::MAX).is_err());
+        assert!(format_filetime(1 << 63).is_err());
+        assert!(render_value(0x11, &u64::MAX.to_le_bytes()).is_err());
+    }
+
+    #[test]
+    fn renders_scalar_values() {
+        assert_eq!(format_sid(&[1, 1, 0, 0, 0, 0, 0, 5, 18, 0, 0, 0]).unwrap(), "S-1-5-18");
+        let guid = [0x25, 0x96, 0x84, 0x54, 0x78, 0x54, 0x94, 0x49, 0xa5, 0xba, 0x3e, 0x3b, 0x03, 0x28, 0xc3, 0x0d];
+        assert_eq!(render_value(0x0f, &guid).unwrap(), "{54849625-5478-4994-A5BA-3E3B0328C30D}");
+        assert_eq!(render_value(0x14, &0x1fu32.to_le_bytes()).unwrap(), "0x1f");
+        assert_eq!(render_value(0x81, &utf16_bytes("a\0bc\0")).unwrap(), "a,bc");
+        assert_eq!(render_value(0x86, &[1, 0, 2, 0]).unwrap(), "1,2");
+        assert_eq!(render_value(0x0e, &[0xde, 0xad]).unwrap(), "DEAD");
+    }
+
+    #[test]
+    fn decodes_records_with_inline_and_shared_templates() {
+        let system_sid = vec![1, 1, 0, 0, 0, 0, 0, 5, 18, 0, 0, 0];
+        let data = build_file(&[
+            RecordValues { record_id: 41, provider: "Microsoft-Windows-Security-Auditing", event_id: 4625, filetime: FILETIME, sid: Some(system_sid), user: "jdupont" },
+            RecordValues { record_id: 42, provider: "Service Control Manager", event_id: 7036, filetime: FILETIME + 10_000_000, sid: None, user: "" },
+        ]);
+
+        let file = parse_evtx(&data).unwrap();
+        assert_eq!(file.skipped_records, 0);
+        assert_eq!(file.records.len(), 2);
+
+        let first = &file.records[0];
+        assert_eq!(first.record_id, 41);
+        assert_eq!(first.written_ms, filetime_ms(FILETIME).unwrap());
+        let event = event_from_element(&first.event).unwrap();
+        assert_eq!(event.provider_name, "Microsoft-Windows-Security-Auditing");
+        assert_eq!(event.event_id, 4625);
+        assert_eq!(event.level, Some(4));
+        assert_eq!(event.keywords.as_deref(), Some("0x8010000000000000"));
+        assert_eq!(event.time_created.as_deref(), Some("2026-10-18T07:12:03.1234567Z"));
+        assert_eq!(event.record_id, 41);
+        assert_eq!(event.user_sid.as_deref(), Some("S-1-5-18"));
+        assert_eq!(event.computer.as_deref(), Some("PC01"));
+        assert_eq!(event.event_data.len(), 1);
+        assert_eq!(event.user_data.len(), 0); // <Info> est la racine propre au fournisseur, sans enfants
+        assert_eq!(first.event.child("UserData").unwrap().child("Info").unwrap().text, "détail");
+
+        // Second enregistrement: modèle référencé par position, SID nul donc attribut absent
+        let second = event_from_element(&file.records[1].event).unwrap();
+        assert_eq!(second.event_id, 7036);
+        assert_eq!(second.provider_name, "Service Control Manager");
+        assert_eq!(second.time_created.as_deref(), Some("2026-10-18T07:12:04.1234567Z"));
+        assert_eq!(second.user_sid, None);
+        assert!(file.records[1].event.child("System").unwrap().child("Security").unwrap().attributes.is_empty());
+    }
+
+    #[test]
+    fn skips_corrupted_records_and_rejects_other_files() {
+        let mut data = build_file(&[
+            RecordValues { record_id: 1, provider: "A", event_id: 1, filetime: FILETIME, sid: None, user: "x" },
+        ]);
+        // Jeton inconnu au début du BinXML de l'enregistrement
+        data[FILE_HEADER_SIZE + CHUNK_HEADER_SIZE + RECORD_HEADER_SIZE] = 0x1f;
+        let file = parse_evtx(&data).unwrap();
+        assert_eq!(file.records.len(), 0);
+        assert_eq!(file.skipped_records, 1);
+
+        assert!(parse_evtx(b"not an evtx file").is_err());
+        let mut wrong_version = build_file(&[]);
+        wrong_version[38] = 2;
+        assert!(parse_evtx(&wrong_version).is_err());
+    }
+
+    // Enregistrement dont le modèle référence N fois la même substitution texte
+    fn build_repeated_substitution_file(references: usize, text_len: usize) -> Vec<u8> {
+        let mut w = ChunkWriter::new();
+        let start = w.buf.len();
+        w.buf.extend_from_slice(&RECORD_SIGNATURE);
+        w.u32(0);
+        w.buf.extend_from_slice(&1u64.to_le_bytes());
+        w.buf.extend_from_slice(&FILETIME.to_le_bytes());
+        w.u8(0x0f); w.u8(1); w.u8(1); w.u8(0);
+        w.u8(0x0c); w.u8(0x01); w.u32(0x1234);
+        let definition = w.pos() + 4;
+        w.u32(definition);
+        w.u32(0);
+        w.buf.extend_from_slice(&[0xAB; 16]);
+        let size_at = w.buf.len();
+        w.u32(0);
+        let body_start = w.buf.len();
+        w.u8(0x0f); w.u8(1); w.u8(1); w.u8(0);
+        w.open("Event", false); w.u8(0x02);
+        for _ in 0..references {
+            w.substitution(0, 0x01, false);
+        }
+        w.u8(0x04);
+        w.u8(0x00);
+        let size = (w.buf.len() - body_start) as u32;
+        w.set_u32(size_at, size);
+
+        let text = utf16_bytes(&"x".repeat(text_len));
+        w.u32(1);
+        w.u16(text.len() as u16); w.u8(0x01); w.u8(0);
+        w.buf.extend_from_slice(&text);
+        w.u8(0x00);
+        let size = (w.buf.len() - start + 4) as u32;
+        w.u32(size);
+        w.set_u32(start + 4, size);
+
+        let free_space = w.pos();
+        w.set_u32(48, free_space);
+        w.buf.resize(CHUNK_SIZE, 0);
+        let mut file = vec![0u8; FILE_HEADER_SIZE];
+        file[..8].copy_from_slice(FILE_SIGNATURE);
+        file[38..40].copy_from_slice(&3u16.to_le_bytes());
+        file.extend_from_slice(&w.buf);
+        file
+    }
+
+    #[test]
+    fn bounds_decoding_cost_per_record() {
+        // Quelques références: décodé normalement
+        let small = parse_evtx(&build_repeated_substitution_file(4, 10)).unwrap();
+        assert_eq!(small.records.len(), 1);
+        assert_eq!(small.records[0].event.text.len(), 40);
+
+        // 14 000 références à 2 000 caractères: 28 millions de caractères, enregistrement rejeté
+        let large = parse_evtx(&build_repeated_substitution_file(14_000, 2_000)).unwrap();
+        assert_eq!(large.records.len(), 0);
+        assert_eq!(large.skipped_records, 1);
+    }
+
+    #[test]
+    fn skips_records_with_out_of_range_filetime() {
+        let data = build_file(&[
+            RecordValues { record_id: 1, provider: "A", event_id: 1, filetime: u64::MAX, sid: None, user: "x" },
+            RecordValues { record_id: 2, provider: "A", event_id: 1, filetime: FILETIME, sid: None, user: "x" },
+        ]);
+        let file = parse_evtx(&data).unwrap();
+        assert_eq!(file.skipped_records, 1);
+        assert_eq!(file.records.len(), 1);
+        assert_eq!(file.records[0].record_id, 2);
+    }
+
+    // Fichier réel exporté sous Windows (voir fixtures/README.md), à lancer avec `cargo test -- --ignored`
+    #[test]
+    #[ignore = "nécessite fixtures/system_sample.evtx, exporté sous Windows"]
+    fn decodes_wevtutil_export_fixture() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/modules/event_viewer/fixtures/system_sample.evtx");
+        let data = std::fs::read(&path).unwrap_or_else(|e| panic!("Fixture illisible {}: {}", path.display(), e));
+        let file = parse_evtx(&data).unwrap();
+        assert_eq!(file.skipped_records, 0);
+        assert!(!file.records.is_empty());
+        for record in &file.records {
+            let event = event_from_element(&record.event).unwrap();
+            assert_eq!(event.record_id, record.record_id);
+            assert!(!event.provider_name.is_empty());
+            assert_eq!(event.channel.as_deref(), Some("System"));
+            assert!(event.time_ms().is_some());
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::result::Result;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::command;
+use tauri::AppHandle;
+
+use crate::modules::common::csv::csv_field;
+use crate::modules::common::powershell::ps_quote;
+
+use super::event_xml::{event_from_element, ParsedEventXml};
+use super::evtx::parse_evtx;
+use super::logs::run_wevtutil;
+use super::query::EventQuery;
+use super::{entry_from_ps, run_powershell_events, stream_powershell_lines, winevent_script, EventLogEntry, PsWinEvent, EVENT_SELECT_SCRIPT};
+
+// --- Export d'un journal et analyse hors ligne de fichiers .evtx ---
+// Les exports CSV et JSONL sont écrits au fil de l'eau: PowerShell émet une ligne JSON par événement,
+// aucun journal complet n'est chargé en mémoire (200 000 événements et plus pour Security).
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ExportFormat {
+    Evtx,      // Format natif (wevtutil epl), réouvrable dans l'Observateur d'événements
+    Csv,
+    JsonLines, // Un objet EventLogEntry par ligne
+}
+
+impl ExportFormat {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "evtx" => Ok(ExportFormat::Evtx),
+            "csv" => Ok(ExportFormat::Csv),
+            "jsonl" | "ndjson" | "json_lines" => Ok(ExportFormat::JsonLines),
+            other => Err(format!("Format d'export inconnu: '{}' (attendu: evtx, csv, jsonl)", other)),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ExportFormat::Evtx => "evtx",
+            ExportFormat::Csv => "csv",
+            ExportFormat::JsonLines => "jsonl",
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EventExportResult {
+    file_path: String,
+    format: String,
+    exported_events: Option<usize>, // Inconnu si le fichier EVTX exporté n'a pas pu être relu
+}
+
+// Écriture incrémentale d'un export CSV (RFC 4180, BOM UTF-8 pour Excel) ou JSONL
+struct EventExportWriter<W: Write> {
+    writer: W,
+    format: ExportFormat,
+    count: usize,
+}
+
+impl<W: Write> EventExportWriter<W> {
+    fn new(mut writer: W, format: ExportFormat) -> Result<Self, String> {
+        if format == ExportFormat::Csv {
+            writer.write_all("\u{feff}record_id,event_id,level,provider_name,time_created,message\r\n".as_bytes())
+                .map_err(|e| format!("Erreur écriture export: {}", e))?;
+        }
+        Ok(EventExportWriter { writer, format, count: 0 })
+    }
+
+    fn write_event(&mut self, event: &EventLogEntry) -> Result<(), String> {
+        let line = match self.format {
+            ExportFormat::Csv => {
+                let fields = [
+                    event.record_id.to_string(),
+                    event.event_id.to_string(),
+                    csv_field(&event.level),
+                    csv_field(&event.provider_name),
+                    csv_field(&event.time_created),
+                    csv_field(&event.message),
+                ];
+                format!("{}\r\n", fields.join(","))
+            }
+            _ => format!("{}\n", serde_json::to_string(event).map_err(|e| format!("Erreur sérialisation événement: {}", e))?),
+        };
+        self.writer.write_all(line.as_bytes()).map_err(|e| format!("Erreur écriture export: {}", e))?;
+        self.count += 1;
+        Ok(())
+    }
+
+    // Ligne JSON émise par PowerShell pour un événement; les lignes vides sont ignorées
+    fn write_json_line(&mut self, line: &str) -> Result<(), String> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(());
+        }
+        let ps_event: PsWinEvent = serde_json::from_str(line)
+            .map_err(|e| format!("Erreur parsing JSON événement: {}", e))?;
+        self.write_event(&entry_from_ps(ps_event))
+    }
+
+    fn finish(mut self) -> Result<usize, String> {
+        self.writer.flush().map_err(|e| format!("Erreur écriture export: {}", e))?;
+        Ok(self.count)
+    }
+}
+
+// Une ligne JSON compacte par événement, dans l'ordre chronologique comme un export EVTX
+fn export_stream_script(log_name: &str, xpath: &str) -> String {
+    format!(
+        "try {{ Get-WinEvent -LogName {} -FilterXPath {} -Oldest -ErrorAction Stop | {} | ForEach-Object {{ $_ | ConvertTo-Json -Depth 3 -Compress }} }} \
+         catch {{ if ($_.FullyQualifiedErrorId -notlike 'NoMatchingEventsFound*') {{ Write-Error $_.Exception.Message; exit 1 }} }}",
+        ps_quote(log_name), ps_quote(xpath), EVENT_SELECT_SCRIPT
+    )
+}
+
+async fn export_streamed(app: &AppHandle, log_name: &str, xpath: &str, file_path: &str, format: ExportFormat) -> Result<usize, String> {
+    let file = File::create(file_path).map_err(|e| format!("Erreur création '{}': {}", file_path, e))?;
+    let mut writer = EventExportWriter::new(BufWriter::new(file), format)?;
+
+    stream_powershell_lines(app, &export_stream_script(log_name, xpath), |line| writer.write_json_line(line)).await?;
+    writer.finish()
+}
+
+// Nombre d'enregistrements d'un fichier .evtx, relu avec l'analyseur natif
+fn count_evtx_records(file_path: &str) -> Option<usize> {
+    let data = fs::read(file_path).ok()?;
+    parse_evtx(&data).ok().map(|file| file.records.len())
+}
+
+async fn export_evtx(app: &AppHandle, log_name: &str, xpath: &str, file_path: &str) -> Result<(), String> {
+    // wevtutil est appelé directement: pas de citation PowerShell à gérer pour l'XPath
+    let args = vec![
+        "epl".to_string(), log_name.to_string(), file_path.to_string(), format!("/q:{}", xpath), "/ow:true".to_string(),
+    ];
+    run_wevtutil(app, &args).await?;
+    Ok(())
+}
+
+#[command]
+pub async fn export_event_log(
+    app: AppHandle,
+    log_name: String,
+    query: Option<EventQuery>, // Les journaux du filtre sont ignorés au profit de log_name
+    file_path: String,
+    format: String,
+) -> Result<EventExportResult, String> {
+    println!("Real: export_event_log(log: '{}', path: '{}', format: {}) called", log_name, file_path, format);
+    if log_name.trim().is_empty() {
+        return Err("Nom de journal invalide".to_string());
+    }
+    if file_path.trim().is_empty() {
+        return Err("Chemin de destination invalide".to_string());
+    }
+    let format = ExportFormat::parse(&format)?;
+    let query = query.unwrap_or_default().with_log(log_name.trim());
+    let file_path = file_path.trim().to_string();
+
+    let exported_events = match format {
+        ExportFormat::Evtx => {
+            export_evtx(&app, log_name.trim(), &query.to_xpath()?, &file_path).await?;
+            count_evtx_records(&file_path)
+        }
+        ExportFormat::Csv | ExportFormat::JsonLines => {
+            match export_streamed(&app, log_name.trim(), &query.to_xpath()?, &file_path, format).await {
+                Ok(count) => Some(count),
+                Err(e) => {
+                    // Pas de fichier tronqué laissé derrière un export échoué
+                    let _ = fs::remove_file(&file_path);
+                    return Err(e);
+                }
+            }
+        }
+    };
+
+    Ok(EventExportResult { file_path, format: format.name().to_string(), exported_events })
+}
+
+// --- Lecture hors ligne ---
+
+fn level_name(level: Option<u8>) -> &'static str {
+    match level {
+        Some(1) => "Critique",
+        Some(2) => "Erreur",
+        Some(3) => "Avertissement",
+        Some(0) | Some(4) => "Information",
+        Some(5) => "Commentaires",
+        _ => "Inconnu",
+    }
+}
+
+// Sans les DLL des fournisseurs, le message n'est pas rendu: on affiche les champs de l'événement
+fn entry_from_parsed(event: ParsedEventXml) -> EventLogEntry {
+    let message = event.event_data.iter().chain(event.user_data.iter())
+        .map(|field| format!("{}: {}", field.name(), field.value()))
+        .collect::<Vec<_>>()
+        .join("; ");
+    EventLogEntry {
+        record_id: event.record_id,
+        event_id: event.event_id,
+        level: level_name(event.level).to_string(),
+        provider_name: event.provider_name,
+        time_created: event.time_created.unwrap_or_else(|| "N/A".to_string()),
+        message,
+    }
+}
+
+// Filtre un fichier .evtx avec l'analyseur natif; du plus récent au plus ancien comme get_events
+fn read_evtx_events(data: &[u8], query: &EventQuery, max_events: usize, now_ms: i64) -> Result<Vec<EventLogEntry>, String> {
+    let matcher = query.matcher(now_ms)?;
+    let file = parse_evtx(data)?;
+    if file.skipped_records > 0 {
+        println!("{} enregistrement(s) EVTX illisible(s) ignoré(s)", file.skipped_records);
+    }
+    let mut events = Vec::new();
+    for record in file.records.iter().rev() {
+        let Ok(event) = event_from_element(&record.event) else { continue };
+        if matcher.matches(&event) {
+            events.push(entry_from_parsed(event));
+            if events.len() >= max_events {
+                break;
+            }
+        }
+    }
+    Ok(events)
+}
+
+#[command]
+pub async fn get_events_from_file(
+    app: AppHandle,
+    file_path: String,
+    query: Option<EventQuery>, // Les journaux du filtre sont ignorés
+    max_events: u32,
+) -> Result<Vec<EventLogEntry>, String> {
+    println!("Real: get_events_from_file(path: '{}', query: {:?}, max: {}) called", file_path, query, max_events);
+    let file_path = file_path.trim().to_string();
+    if !Path::new(&file_path).is_file() {
+        return Err(format!("Fichier introuvable: {}", file_path));
+    }
+    let query = query.unwrap_or_default();
+    let max_events = max_events.max(1);
+
+    if cfg!(windows) {
+        // Sous Windows, Get-WinEvent rend aussi les messages des fournisseurs installés
+        let command = winevent_script(&format!("-Path {}", ps_quote(&file_path)), &query.to_xpath()?, max_events, false);
+        return run_powershell_events(&app, &command).await;
+    }
+
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Horloge système invalide: {}", e))?
+        .as_millis() as i64;
+    tauri::async_runtime::spawn_blocking(move || {
+        let data = fs::read(&file_path).map_err(|e| format!("Erreur lecture '{}': {}", file_path, e))?;
+        read_evtx_events(&data, &query, max_events as usize, now_ms)
+    })
+    .await
+    .map_err(|e| format!("Erreur lors de l'analyse du fichier EVTX: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::event_viewer::event_xml::parse_event_xml;
+
+    fn entry(record_id: u64, message: &str) -> EventLogEntry {
+        EventLogEntry {
+            record_id,
+            event_id: 7036,
+            level: "Information".to_string(),
+            provider_name: "Service Control Manager".to_string(),
+            time_created: "2026-10-18T07:12:03.1234567Z".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    fn export_to_string(events: &[EventLogEntry], format: ExportFormat) -> String {
+        let mut buffer = Vec::new();
+        let mut writer = EventExportWriter::new(&mut buffer, format).unwrap();
+        for event in events {
+            writer.write_event(event).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), events.len());
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn parses_export_formats() {
+        assert_eq!(ExportFormat::parse(" EVTX ").unwrap(), ExportFormat::Evtx);
+        assert_eq!(ExportFormat::parse("csv").unwrap(), ExportFormat::Csv);
+        assert_eq!(ExportFormat::parse("ndjson").unwrap(), ExportFormat::JsonLines);
+        assert!(ExportFormat::parse("xml").is_err());
+    }
+
+    #[test]
+    fn writes_csv_with_quoting() {
+        let csv = export_to_string(&[entry(1, "simple"), entry(2, "Le service \"Spooler\", arrêté\r\nligne 2")], ExportFormat::Csv);
+        let lines: Vec<&str> = csv.trim_start_matches('\u{feff}').split("\r\n").collect();
+        assert_eq!(lines[0], "record_id,event_id,level,provider_name,time_created,message");
+        assert_eq!(lines[1], "1,7036,Information,Service Control Manager,2026-10-18T07:12:03.1234567Z,simple");
+        assert_eq!(lines[2], "2,7036,Information,Service Control Manager,2026-10-18T07:12:03.1234567Z,\"Le service \"\"Spooler\"\", arrêté");
+        assert_eq!(lines[3], "ligne 2\"");
+
+        // Nom d'utilisateur contrôlé par l'attaquant, repris dans le message
+        let csv = export_to_string(&[entry(3, "=cmd|' /C calc'!A0")], ExportFormat::Csv);
+        assert!(csv.ends_with(",'=cmd|' /C calc'!A0\r\n"));
+    }
+
+    #[test]
+    fn writes_one_json_object_per_line() {
+        let jsonl = export_to_string(&[entry(1, "a\nb"), entry(2, "c")], ExportFormat::JsonLines);
+        let lines: Vec<&str> = jsonl.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["record_id"], 1);
+        assert_eq!(first["message"], "a\nb");
+    }
+
+    #[test]
+    fn converts_streamed_powershell_lines() {
+        let mut buffer = Vec::new();
+        let mut writer = EventExportWriter::new(&mut buffer, ExportFormat::Csv).unwrap();
+        // Sortie de ConvertTo-Json -Compress (PowerShell 5.1) pour un événement, puis une ligne vide
+        writer.write_json_line("{\"RecordId\":12,\"Id\":7036,\"LevelDisplayName\":\"Information\",\"ProviderName\":\"Service Control Manager\",\
+            \"TimeCreated\":{\"value\":\"\\/Date(1792307523123)\\/\",\"DateTime\":\"dimanche 18 octobre 2026 07:12:03\"},\
+            \"Message\":\"Le service Spooler est entré dans l'état : arrêté.\"}\r\n").unwrap();
+        writer.write_json_line("\r\n").unwrap();
+        assert!(writer.write_json_line("{tronqué").is_err());
+        assert_eq!(writer.finish().unwrap(), 1);
+
+        let csv = String::from_utf8(buffer).unwrap();
+        assert!(csv.ends_with("12,7036,Information,Service Control Manager,dimanche 18 octobre 2026 07:12:03,\
+            Le service Spooler est entré dans l'état : arrêté.\r\n"));
+    }
+
+    #[test]
+    fn summarizes_fields_when_message_is_not_rendered() {
+        let event = parse_event_xml(
+            "<Event><System><Provider Name='Microsoft-Windows-Security-Auditing'/><EventID>4625</EventID><Level>0</Level>\
+             <EventRecordID>7</EventRecordID></System><EventData><Data Name='TargetUserName'>jdupont</Data>\
+             <Data Name='IpAddress'>10.0.0.42</Data></EventData></Event>"
+        ).unwrap();
+        let entry = entry_from_parsed(event);
+        assert_eq!(entry.record_id, 7);
+        assert_eq!(entry.level, "Information");
+        assert_eq!(entry.time_created, "N/A");
+        assert_eq!(entry.message, "TargetUserName: jdupont; IpAddress: 10.0.0.42");
+    }
+}
@@ -0,0 +1,598 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::result::Result;
+use tauri::command;
+use tauri::AppHandle;
+
+use super::event_xml::ParsedEventXml;
+use super::fetch_event_records;
+use super::query::EventQuery;
+
+// --- Investigations prédéfinies ---
+// Chaque investigation interroge un ensemble d'événements connu puis renvoie une synthèse agrégée
+// (regroupements, compteurs, premières/dernières occurrences) plutôt que les événements bruts.
+
+const DEFAULT_DAYS: u32 = 7;
+const DEFAULT_MAX_EVENTS: u32 = 5000;
+// Un 41 (Kernel-Power) et un 6008 (EventLog) écrits au même redémarrage décrivent le même arrêt
+const SHUTDOWN_MERGE_WINDOW_MS: i64 = 5 * 60 * 1000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum InvestigationPreset {
+    UnexpectedShutdowns,
+    FailedLogons,
+    AccountLockouts,
+    ServiceCrashes,
+    DiskErrors,
+    WindowsUpdateFailures,
+}
+
+const DISK_PROVIDERS: [&str; 7] = ["disk", "Ntfs", "Microsoft-Windows-Ntfs", "volmgr", "storahci", "stornvme", "iaStorA"];
+const DISK_EVENT_IDS: [u32; 9] = [7, 11, 51, 52, 153, 55, 98, 129, 140];
+
+impl InvestigationPreset {
+    fn title(self) -> &'static str {
+        match self {
+            InvestigationPreset::UnexpectedShutdowns => "Arrêts inattendus",
+            InvestigationPreset::FailedLogons => "Échecs d'ouverture de session",
+            InvestigationPreset::AccountLockouts => "Verrouillages de comptes",
+            InvestigationPreset::ServiceCrashes => "Arrêts inattendus de services",
+            InvestigationPreset::DiskErrors => "Erreurs disque et système de fichiers",
+            InvestigationPreset::WindowsUpdateFailures => "Échecs Windows Update",
+        }
+    }
+
+    fn query(self) -> EventQuery {
+        match self {
+            InvestigationPreset::UnexpectedShutdowns => EventQuery::for_log("System").with_event_ids(&[41, 6008]),
+            InvestigationPreset::FailedLogons => EventQuery::for_log("Security").with_event_ids(&[4625]),
+            InvestigationPreset::AccountLockouts => EventQuery::for_log("Security").with_event_ids(&[4740]),
+            InvestigationPreset::ServiceCrashes => EventQuery::for_log("System")
+                .with_event_ids(&[7031, 7034])
+                .with_providers(&["Service Control Manager"]),
+            InvestigationPreset::DiskErrors => EventQuery::for_log("System")
+                .with_event_ids(&DISK_EVENT_IDS)
+                .with_providers(&DISK_PROVIDERS),
+            InvestigationPreset::WindowsUpdateFailures => EventQuery::for_log("System")
+                .with_event_ids(&[20, 25, 31])
+                .with_providers(&["Microsoft-Windows-WindowsUpdateClient"]),
+        }
+    }
+}
+
+// Compteur d'occurrences commun aux regroupements
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct Occurrences {
+    count: usize,
+    first_seen: Option<String>, // ISO 8601 UTC
+    last_seen: Option<String>,
+}
+
+impl Occurrences {
+    fn add(&mut self, time: Option<&str>) {
+        self.count += 1;
+        if let Some(time) = time {
+            // Même format ISO partout: l'ordre lexicographique est l'ordre chronologique
+            match self.first_seen.as_deref() {
+                Some(first) if first <= time => {}
+                _ => self.first_seen = Some(time.to_string()),
+            }
+            match self.last_seen.as_deref() {
+                Some(last) if last >= time => {}
+                _ => self.last_seen = Some(time.to_string()),
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ShutdownIncident {
+    time: Option<String>,
+    event_ids: Vec<u32>,
+    bugcheck_code: Option<String>,          // Code d'écran bleu (41), absent pour une coupure d'alimentation
+    previous_shutdown_time: Option<String>, // Heure d'arrêt rapportée par le 6008 (format local)
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FailedLogonGroup {
+    account: String, // DOMAINE\utilisateur
+    source_ip: String,
+    workstations: Vec<String>,
+    logon_types: Vec<u32>,
+    reasons: Vec<String>,
+    #[serde(flatten)]
+    occurrences: Occurrences,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct LockoutGroup {
+    account: String,
+    sid: Option<String>,
+    caller_computers: Vec<String>, // Postes à l'origine des échecs ayant verrouillé le compte
+    #[serde(flatten)]
+    occurrences: Occurrences,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ServiceCrashGroup {
+    service: String,
+    crashes_with_recovery: usize, // 7031: une action de récupération est configurée
+    crashes: usize,               // 7034: sans action de récupération
+    #[serde(flatten)]
+    occurrences: Occurrences,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct DiskErrorGroup {
+    provider: String,
+    event_id: u32,
+    device: String,
+    description: String,
+    #[serde(flatten)]
+    occurrences: Occurrences,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct UpdateFailureGroup {
+    update: String,
+    error_code: String,
+    event_ids: Vec<u32>,
+    #[serde(flatten)]
+    occurrences: Occurrences,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InvestigationSummary {
+    UnexpectedShutdowns { incidents: Vec<ShutdownIncident>, with_bugcheck: usize },
+    FailedLogons { total: usize, distinct_accounts: usize, distinct_sources: usize, groups: Vec<FailedLogonGroup> },
+    AccountLockouts { total: usize, groups: Vec<LockoutGroup> },
+    ServiceCrashes { total: usize, groups: Vec<ServiceCrashGroup> },
+    DiskErrors { total: usize, groups: Vec<DiskErrorGroup> },
+    WindowsUpdateFailures { total: usize, groups: Vec<UpdateFailureGroup> },
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct InvestigationReport {
+    preset: InvestigationPreset,
+    title: String,
+    days: u32,
+    events_analyzed: usize,
+    truncated: bool, // Limite d'événements atteinte: la synthèse ne couvre pas toute la période
+    summary: InvestigationSummary,
+}
+
+// Valeur significative d'un champ ("-" et vide sont les valeurs "non renseigné" de Windows)
+fn field<'a>(event: &'a ParsedEventXml, name: &str) -> Option<&'a str> {
+    event.data(name).map(str::trim).filter(|v| !v.is_empty() && *v != "-")
+}
+
+fn push_unique<T: PartialEq>(values: &mut Vec<T>, value: T) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+// Les plus fréquents d'abord, puis les plus récents
+fn by_frequency(a: &Occurrences, b: &Occurrences) -> std::cmp::Ordering {
+    b.count.cmp(&a.count).then_with(|| b.last_seen.cmp(&a.last_seen))
+}
+
+fn summarize_shutdowns(events: &[ParsedEventXml]) -> InvestigationSummary {
+    let mut sorted: Vec<&ParsedEventXml> = events.iter().collect();
+    sorted.sort_by_key(|e| e.time_ms());
+
+    let mut incidents: Vec<(Option<i64>, ShutdownIncident)> = Vec::new();
+    for event in sorted {
+        let time_ms = event.time_ms();
+        let merge = match (incidents.last(), time_ms) {
+            (Some((Some(start), _)), Some(t)) => t - start <= SHUTDOWN_MERGE_WINDOW_MS,
+            _ => false,
+        };
+        if !merge {
+            incidents.push((time_ms, ShutdownIncident {
+                time: event.time_created.clone(),
+                event_ids: vec![],
+                bugcheck_code: None,
+                previous_shutdown_time: None,
+            }));
+        }
+        let Some((_, incident)) = incidents.last_mut() else { continue };
+        push_unique(&mut incident.event_ids, event.event_id);
+        match event.event_id {
+            41 => {
+                let code = field(event, "BugcheckCode").and_then(|c| c.parse::<u64>().ok()).filter(|c| *c != 0);
+                if let Some(code) = code {
+                    incident.bugcheck_code = Some(format!("0x{:08X}", code));
+                }
+            }
+            6008 => {
+                // Données classiques: %1 = heure, %2 = date
+                let parts: Vec<&str> = [field(event, "%2"), field(event, "%1")].into_iter().flatten().collect();
+                if !parts.is_empty() {
+                    incident.previous_shutdown_time = Some(parts.join(" "));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let with_bugcheck = incidents.iter().filter(|(_, i)| i.bugcheck_code.is_some()).count();
+    // Les plus récents d'abord
+    let incidents = incidents.into_iter().rev().map(|(_, i)| i).collect();
+    InvestigationSummary::UnexpectedShutdowns { incidents, with_bugcheck }
+}
+
+// Codes NTSTATUS des échecs d'ouverture de session (Status / SubStatus du 4625)
+fn logon_failure_reason(code: &str) -> String {
+    let reason = match code.to_ascii_uppercase().trim_start_matches("0X") {
+        "C0000064" => "Compte inexistant",
+        "C000006A" => "Mot de passe incorrect",
+        "C000006D" => "Nom d'utilisateur ou mot de passe incorrect",
+        "C000006E" => "Restriction de compte",
+        "C000006F" => "Ouverture de session hors des heures autorisées",
+        "C0000070" => "Poste de travail non autorisé",
+        "C0000071" => "Mot de passe expiré",
+        "C0000072" => "Compte désactivé",
+        "C0000133" => "Horloges désynchronisées",
+        "C000015B" => "Type d'ouverture de session non accordé",
+        "C0000193" => "Compte expiré",
+        "C0000224" => "Changement de mot de passe requis",
+        "C0000234" => "Compte verrouillé",
+        "C0000413" => "Échec d'authentification (pare-feu d'authentification)",
+        _ => return format!("Code {}", code),
+    };
+    reason.to_string()
+}
+
+fn summarize_failed_logons(events: &[ParsedEventXml]) -> InvestigationSummary {
+    let mut groups: BTreeMap<(String, String), FailedLogonGroup> = BTreeMap::new();
+    for event in events {
+        let user = field(event, "TargetUserName").unwrap_or("(inconnu)");
+        let account = match field(event, "TargetDomainName") {
+            Some(domain) => format!("{}\\{}", domain, user),
+            None => user.to_string(),
+        };
+        let source_ip = field(event, "IpAddress").unwrap_or("(local)").to_string();
+        let key = (account.to_lowercase(), source_ip.clone());
+        let group = groups.entry(key).or_insert_with(|| FailedLogonGroup {
+            account,
+            source_ip,
+            workstations: vec![],
+            logon_types: vec![],
+            reasons: vec![],
+            occurrences: Occurrences::default(),
+        });
+        group.occurrences.add(event.time_created.as_deref());
+        if let Some(workstation) = field(event, "WorkstationName") {
+            push_unique(&mut group.workstations, workstation.to_string());
+        }
+        if let Some(logon_type) = field(event, "LogonType").and_then(|t| t.parse::<u32>().ok()) {
+            push_unique(&mut group.logon_types, logon_type);
+        }
+        // Le SubStatus précise le Status générique 0xC000006D
+        let status = field(event, "Status");
+        let code = match (status, field(event, "SubStatus")) {
+            (Some(s), Some(sub)) if s.eq_ignore_ascii_case("0xc000006d") && sub != "0x0" => Some(sub),
+            (Some(s), _) => Some(s),
+            (None, sub) => sub,
+        };
+        if let Some(code) = code {
+            push_unique(&mut group.reasons, logon_failure_reason(code));
+        }
+    }
+
+    let distinct_accounts = groups.keys().map(|(a, _)| a).collect::<BTreeSet<_>>().len();
+    let distinct_sources = groups.keys().map(|(_, s)| s).collect::<BTreeSet<_>>().len();
+    let mut groups: Vec<FailedLogonGroup> = groups.into_values().collect();
+    groups.sort_by(|a, b| by_frequency(&a.occurrences, &b.occurrences));
+    InvestigationSummary::FailedLogons { total: events.len(), distinct_accounts, distinct_sources, groups }
+}
+
+fn summarize_lockouts(events: &[ParsedEventXml]) -> InvestigationSummary {
+    let mut groups: BTreeMap<String, LockoutGroup> = BTreeMap::new();
+    for event in events {
+        let account = field(event, "TargetUserName").unwrap_or("(inconnu)").to_string();
+        let group = groups.entry(account.to_lowercase()).or_insert_with(|| LockoutGroup {
+            account,
+            sid: field(event, "TargetSid").map(str::to_string),
+            caller_computers: vec![],
+            occurrences: Occurrences::default(),
+        });
+        group.occurrences.add(event.time_created.as_deref());
+        // Dans le 4740, TargetDomainName contient le nom de l'ordinateur appelant
+        if let Some(caller) = field(event, "TargetDomainName") {
+            push_unique(&mut group.caller_computers, caller.to_string());
+        }
+    }
+    let mut groups: Vec<LockoutGroup> = groups.into_values().collect();
+    groups.sort_by(|a, b| by_frequency(&a.occurrences, &b.occurrences));
+    InvestigationSummary::AccountLockouts { total: events.len(), groups }
+}
+
+fn summarize_service_crashes(events: &[ParsedEventXml]) -> InvestigationSummary {
+    let mut groups: BTreeMap<String, ServiceCrashGroup> = BTreeMap::new();
+    for event in events {
+        let service = field(event, "param1").or_else(|| field(event, "%1")).unwrap_or("(inconnu)").to_string();
+        let group = groups.entry(service.to_lowercase()).or_insert_with(|| ServiceCrashGroup {
+            service,
+            crashes_with_recovery: 0,
+            crashes: 0,
+            occurrences: Occurrences::default(),
+        });
+        group.occurrences.add(event.time_created.as_deref());
+        if event.event_id == 7031 {
+            group.crashes_with_recovery += 1;
+        } else {
+            group.crashes += 1;
+        }
+    }
+    let mut groups: Vec<ServiceCrashGroup> = groups.into_values().collect();
+    groups.sort_by(|a, b| by_frequency(&a.occurrences, &b.occurrences));
+    InvestigationSummary::ServiceCrashes { total: events.len(), groups }
+}
+
+fn disk_error_description(provider: &str, event_id: u32) -> &'static str {
+    match (provider.to_ascii_lowercase().as_str(), event_id) {
+        ("disk", 7) => "Bloc défectueux détecté",
+        ("disk", 11) => "Erreur du contrôleur",
+        ("disk", 51) => "Erreur lors d'une opération de pagination",
+        ("disk", 52) => "Défaillance prédite par le disque (SMART)",
+        ("disk", 153) => "Opération d'E/S relancée",
+        (_, 55) => "Structure du système de fichiers corrompue",
+        (_, 98) => "Volume à vérifier (chkdsk)",
+        (_, 140) => "Échec de l'écriture des données en attente",
+        (_, 129) => "Réinitialisation du périphérique de stockage",
+        _ => "Erreur de stockage",
+    }
+}
+
+fn summarize_disk_errors(events: &[ParsedEventXml]) -> InvestigationSummary {
+    let mut groups: BTreeMap<(String, u32, String), DiskErrorGroup> = BTreeMap::new();
+    for event in events {
+        // Périphérique: premier paramètre des événements classiques, champ nommé sinon
+        let device = field(event, "DriveName")
+            .or_else(|| field(event, "VolumeName"))
+            .or_else(|| field(event, "DeviceName"))
+            .or_else(|| field(event, "%1"))
+            .unwrap_or("(inconnu)")
+            .to_string();
+        let key = (event.provider_name.to_lowercase(), event.event_id, device.to_lowercase());
+        let group = groups.entry(key).or_insert_with(|| DiskErrorGroup {
+            provider: event.provider_name.clone(),
+            event_id: event.event_id,
+            device,
+            description: disk_error_description(&event.provider_name, event.event_id).to_string(),
+            occurrences: Occurrences::default(),
+        });
+        group.occurrences.add(event.time_created.as_deref());
+    }
+    let mut groups: Vec<DiskErrorGroup> = groups.into_values().collect();
+    groups.sort_by(|a, b| by_frequency(&a.occurrences, &b.occurrences));
+    InvestigationSummary::DiskErrors { total: events.len(), groups }
+}
+
+fn summarize_update_failures(events: &[ParsedEventXml]) -> InvestigationSummary {
+    let mut groups: BTreeMap<(String, String), UpdateFailureGroup> = BTreeMap::new();
+    for event in events {
+        // 25: échec de la recherche de mises à jour, sans titre
+        let update = field(event, "updateTitle").unwrap_or("(recherche de mises à jour)").to_string();
+        let error_code = field(event, "errorCode").unwrap_or("(inconnu)").to_string();
+        let key = (update.clone(), error_code.to_lowercase());
+        let group = groups.entry(key).or_insert_with(|| UpdateFailureGroup {
+            update,
+            error_code,
+            event_ids: vec![],
+            occurrences: Occurrences::default(),
+        });
+        group.occurrences.add(event.time_created.as_deref());
+        push_unique(&mut group.event_ids, event.event_id);
+    }
+    let mut groups: Vec<UpdateFailureGroup> = groups.into_values().collect();
+    groups.sort_by(|a, b| by_frequency(&a.occurrences, &b.occurrences));
+    InvestigationSummary::WindowsUpdateFailures { total: events.len(), groups }
+}
+
+fn summarize(preset: InvestigationPreset, events: &[ParsedEventXml]) -> InvestigationSummary {
+    match preset {
+        InvestigationPreset::UnexpectedShutdowns => summarize_shutdowns(events),
+        InvestigationPreset::FailedLogons => summarize_failed_logons(events),
+        InvestigationPreset::AccountLockouts => summarize_lockouts(events),
+        InvestigationPreset::ServiceCrashes => summarize_service_crashes(events),
+        InvestigationPreset::DiskErrors => summarize_disk_errors(events),
+        InvestigationPreset::WindowsUpdateFailures => summarize_update_failures(events),
+    }
+}
+
+#[command]
+pub async fn run_event_investigation(
+    app: AppHandle,
+    preset: InvestigationPreset,
+    days: Option<u32>,
+    max_events: Option<u32>,
+) -> Result<InvestigationReport, String> {
+    println!("Real: run_event_investigation({:?}, days: {:?}) called", preset, days);
+    let days = days.unwrap_or(DEFAULT_DAYS).max(1);
+    let max_events = max_events.unwrap_or(DEFAULT_MAX_EVENTS).max(1);
+    let query = preset.query().with_within_ms(days as u64 * 86_400_000);
+
+    // Security requiert des privilèges élevés: l'erreur d'accès est remontée telle quelle
+    let events = fetch_event_records(&app, &query, max_events).await?;
+    Ok(InvestigationReport {
+        preset,
+        title: preset.title().to_string(),
+        days,
+        events_analyzed: events.len(),
+        truncated: events.len() >= max_events as usize,
+        summary: summarize(preset, &events),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::event_viewer::event_xml::parse_event_xml;
+
+    fn event(provider: &str, id: u32, time: &str, data: &[(&str, &str)]) -> ParsedEventXml {
+        let fields: String = data.iter().map(|(name, value)| {
+            if name.starts_with('%') {
+                format!("<Data>{}</Data>", value)
+            } else {
+                format!("<Data Name='{}'>{}</Data>", name, value)
+            }
+        }).collect();
+        parse_event_xml(&format!(
+            "<Event><System><Provider Name='{}'/><EventID>{}</EventID><TimeCreated SystemTime='{}'/></System>\
+             <EventData>{}</EventData></Event>",
+            provider, id, time, fields
+        )).unwrap()
+    }
+
+    #[test]
+    fn every_preset_compiles_to_a_valid_query() {
+        for preset in [
+            InvestigationPreset::UnexpectedShutdowns, InvestigationPreset::FailedLogons, InvestigationPreset::AccountLockouts,
+            InvestigationPreset::ServiceCrashes, InvestigationPreset::DiskErrors, InvestigationPreset::WindowsUpdateFailures,
+        ] {
+            assert!(preset.query().with_within_ms(86_400_000).to_xpath().is_ok(), "{:?}", preset);
+        }
+        let preset: InvestigationPreset = serde_json::from_str("\"failed_logons\"").unwrap();
+        assert_eq!(preset, InvestigationPreset::FailedLogons);
+    }
+
+    #[test]
+    fn merges_kernel_power_and_eventlog_shutdown_events() {
+        let events = vec![
+            event("Microsoft-Windows-Kernel-Power", 41, "2026-10-10T08:00:05.0000000Z", &[("BugcheckCode", "0")]),
+            event("EventLog", 6008, "2026-10-10T08:00:30.0000000Z", &[("%1", "07:55:12"), ("%2", "10/10/2026")]),
+            event("Microsoft-Windows-Kernel-Power", 41, "2026-10-15T12:00:00.0000000Z", &[("BugcheckCode", "209")]),
+        ];
+        let InvestigationSummary::UnexpectedShutdowns { incidents, with_bugcheck } = summarize_shutdowns(&events) else {
+            panic!("type de synthèse inattendu");
+        };
+        assert_eq!(incidents.len(), 2);
+        assert_eq!(with_bugcheck, 1);
+        // Le plus récent d'abord: écran bleu 0xD1 (DRIVER_IRQL_NOT_LESS_OR_EQUAL)
+        assert_eq!(incidents[0].bugcheck_code.as_deref(), Some("0x000000D1"));
+        assert_eq!(incidents[0].event_ids, vec![41]);
+        assert_eq!(incidents[1].event_ids, vec![41, 6008]);
+        assert_eq!(incidents[1].bugcheck_code, None);
+        assert_eq!(incidents[1].previous_shutdown_time.as_deref(), Some("10/10/2026 07:55:12"));
+        assert_eq!(incidents[1].time.as_deref(), Some("2026-10-10T08:00:05.0000000Z"));
+    }
+
+    #[test]
+    fn groups_failed_logons_by_account_and_source() {
+        let failure = |user: &str, ip: &str, time: &str, status: &str, sub: &str| event(
+            "Microsoft-Windows-Security-Auditing", 4625, time,
+            &[("TargetUserName", user), ("TargetDomainName", "CORP"), ("IpAddress", ip), ("LogonType", "3"),
+              ("WorkstationName", "-"), ("Status", status), ("SubStatus", sub)],
+        );
+        let events = vec![
+            failure("jdupont", "10.0.0.42", "2026-10-18T07:00:00.0000000Z", "0xc000006d", "0xc000006a"),
+            failure("JDUPONT", "10.0.0.42", "2026-10-18T07:05:00.0000000Z", "0xc000006d", "0xc000006a"),
+            failure("jdupont", "10.0.0.42", "2026-10-18T06:55:00.0000000Z", "0xc0000234", "0x0"),
+            failure("admin", "10.0.0.42", "2026-10-18T07:01:00.0000000Z", "0xc000006d", "0xc0000064"),
+            failure("jdupont", "-", "2026-10-18T08:00:00.0000000Z", "0xc000006d", "0xc000006a"),
+        ];
+        let InvestigationSummary::FailedLogons { total, distinct_accounts, distinct_sources, groups } = summarize_failed_logons(&events) else {
+            panic!("type de synthèse inattendu");
+        };
+        assert_eq!(total, 5);
+        assert_eq!(distinct_accounts, 2);
+        assert_eq!(distinct_sources, 2);
+        assert_eq!(groups.len(), 3);
+
+        let top = &groups[0];
+        assert_eq!(top.account, "CORP\\jdupont");
+        assert_eq!(top.source_ip, "10.0.0.42");
+        assert_eq!(top.occurrences.count, 3);
+        assert_eq!(top.occurrences.first_seen.as_deref(), Some("2026-10-18T06:55:00.0000000Z"));
+        assert_eq!(top.occurrences.last_seen.as_deref(), Some("2026-10-18T07:05:00.0000000Z"));
+        assert_eq!(top.reasons, vec!["Mot de passe incorrect".to_string(), "Compte verrouillé".to_string()]);
+        assert_eq!(top.logon_types, vec![3]);
+        assert!(top.workstations.is_empty());
+
+        // À fréquence égale, le plus récent d'abord
+        assert_eq!(groups[1].source_ip, "(local)");
+        assert_eq!(groups[2].reasons, vec!["Compte inexistant".to_string()]);
+    }
+
+    #[test]
+    fn groups_lockouts_with_caller_computers() {
+        let lockout = |time: &str, caller: &str| event(
+            "Microsoft-Windows-Security-Auditing", 4740, time,
+            &[("TargetUserName", "jdupont"), ("TargetDomainName", caller), ("TargetSid", "S-1-5-21-1-2-3-1104")],
+        );
+        let events = vec![
+            lockout("2026-10-18T07:00:00.0000000Z", "PC-COMPTA"),
+            lockout("2026-10-18T09:00:00.0000000Z", "SRV-RDS"),
+            lockout("2026-10-18T08:00:00.0000000Z", "PC-COMPTA"),
+        ];
+        let InvestigationSummary::AccountLockouts { total, groups } = summarize_lockouts(&events) else {
+            panic!("type de synthèse inattendu");
+        };
+        assert_eq!(total, 3);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].sid.as_deref(), Some("S-1-5-21-1-2-3-1104"));
+        assert_eq!(groups[0].caller_computers, vec!["PC-COMPTA".to_string(), "SRV-RDS".to_string()]);
+        assert_eq!(groups[0].occurrences.last_seen.as_deref(), Some("2026-10-18T09:00:00.0000000Z"));
+    }
+
+    #[test]
+    fn counts_service_crashes_by_kind() {
+        let events = vec![
+            event("Service Control Manager", 7031, "2026-10-18T07:00:00.0000000Z", &[("param1", "Spouleur d'impression"), ("param2", "1")]),
+            event("Service Control Manager", 7034, "2026-10-18T08:00:00.0000000Z", &[("param1", "Spouleur d'impression"), ("param2", "2")]),
+            event("Service Control Manager", 7034, "2026-10-18T09:00:00.0000000Z", &[("param1", "MonService")]),
+        ];
+        let InvestigationSummary::ServiceCrashes { total, groups } = summarize_service_crashes(&events) else {
+            panic!("type de synthèse inattendu");
+        };
+        assert_eq!(total, 3);
+        assert_eq!(groups[0].service, "Spouleur d'impression");
+        assert_eq!((groups[0].crashes_with_recovery, groups[0].crashes), (1, 1));
+        assert_eq!(groups[1].service, "MonService");
+    }
+
+    #[test]
+    fn groups_disk_errors_by_device() {
+        let events = vec![
+            event("disk", 7, "2026-10-18T07:00:00.0000000Z", &[("%1", "\\Device\\Harddisk1\\DR1")]),
+            event("disk", 7, "2026-10-18T07:10:00.0000000Z", &[("%1", "\\Device\\Harddisk1\\DR1")]),
+            event("Microsoft-Windows-Ntfs", 55, "2026-10-18T07:20:00.0000000Z", &[("DriveName", "D:")]),
+            event("stornvme", 129, "2026-10-18T07:30:00.0000000Z", &[("%1", "\\Device\\RaidPort0")]),
+        ];
+        let InvestigationSummary::DiskErrors { total, groups } = summarize_disk_errors(&events) else {
+            panic!("type de synthèse inattendu");
+        };
+        assert_eq!(total, 4);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].device, "\\Device\\Harddisk1\\DR1");
+        assert_eq!(groups[0].description, "Bloc défectueux détecté");
+        assert_eq!(groups[0].occurrences.count, 2);
+        assert!(groups.iter().any(|g| g.device == "D:" && g.description == "Structure du système de fichiers corrompue"));
+        assert!(groups.iter().any(|g| g.event_id == 129 && g.description == "Réinitialisation du périphérique de stockage"));
+    }
+
+    #[test]
+    fn groups_update_failures_by_update_and_error() {
+        let provider = "Microsoft-Windows-WindowsUpdateClient";
+        let events = vec![
+            event(provider, 20, "2026-10-18T07:00:00.0000000Z", &[("updateTitle", "KB5031356"), ("errorCode", "0x80070643")]),
+            event(provider, 20, "2026-10-19T07:00:00.0000000Z", &[("updateTitle", "KB5031356"), ("errorCode", "0x80070643")]),
+            event(provider, 31, "2026-10-19T08:00:00.0000000Z", &[("updateTitle", "KB5031356"), ("errorCode", "0x80240022")]),
+            event(provider, 25, "2026-10-19T09:00:00.0000000Z", &[("errorCode", "0x8024402c")]),
+        ];
+        let InvestigationSummary::WindowsUpdateFailures { total, groups } = summarize_update_failures(&events) else {
+            panic!("type de synthèse inattendu");
+        };
+        assert_eq!(total, 4);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].update, "KB5031356");
+        assert_eq!(groups[0].error_code, "0x80070643");
+        assert_eq!(groups[0].occurrences.count, 2);
+        assert!(groups.iter().any(|g| g.update == "(recherche de mises à jour)" && g.event_ids == vec![25]));
+    }
+}
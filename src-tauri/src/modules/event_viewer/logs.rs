@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+use std::result::Result;
+use tauri::command;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+// --- Configuration des journaux (canaux) via wevtutil ---
+// Contrairement à Clear-EventLog / Limit-EventLog, wevtutil gère aussi les canaux modernes
+// (Microsoft-Windows-*/Operational).
+
+// Taille minimale d'un journal; Windows arrondit la taille maximale au multiple de 64 Ko
+const MIN_LOG_SIZE_BYTES: u64 = 1024 * 1024;
+const LOG_SIZE_GRANULARITY: u64 = 64 * 1024;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventLogRetention {
+    Circular,   // Écrase les plus anciens événements
+    AutoBackup, // Archive le journal plein puis le vide
+    Retain,     // Conserve les événements: la journalisation s'arrête quand le journal est plein
+}
+
+impl EventLogRetention {
+    fn from_log_mode(mode: &str) -> Option<Self> {
+        match mode {
+            "Circular" => Some(EventLogRetention::Circular),
+            "AutoBackup" => Some(EventLogRetention::AutoBackup),
+            "Retain" => Some(EventLogRetention::Retain),
+            _ => None,
+        }
+    }
+
+    // Options wevtutil sl: /rt (retention) et /ab (autobackup)
+    fn wevtutil_args(self) -> [&'static str; 2] {
+        match self {
+            EventLogRetention::Circular => ["/rt:false", "/ab:false"],
+            EventLogRetention::AutoBackup => ["/rt:true", "/ab:true"],
+            EventLogRetention::Retain => ["/rt:true", "/ab:false"],
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsEventLogInfo {
+    log_name: String,
+    record_count: Option<u64>,
+    file_size: Option<u64>,
+    maximum_size_in_bytes: Option<u64>,
+    log_mode: Option<String>,
+    is_enabled: Option<bool>,
+    log_type: Option<String>,
+    is_classic_log: Option<bool>,
+    log_file_path: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EventLogInfo {
+    log_name: String,
+    record_count: Option<u64>, // Inconnu pour les journaux désactivés ou inaccessibles
+    file_size: Option<u64>,
+    max_size: Option<u64>,
+    retention: Option<EventLogRetention>,
+    enabled: bool,
+    log_type: String, // Administrative, Operational, Analytic, Debug
+    classic: bool,    // Journal classique (Application, System, Security...)
+    file_path: Option<String>,
+}
+
+pub(super) async fn run_wevtutil(app: &AppHandle, args: &[String]) -> Result<String, String> {
+    let output = app.shell()
+        .command("wevtutil")
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Erreur lors de l'exécution de wevtutil: {}", e))?;
+    if !output.status.success() {
+        // wevtutil écrit ses erreurs sur stderr ou stdout selon la commande
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        return Err(format!("wevtutil {} a échoué: {:?} \nErreur: {}{}",
+            args.first().map(String::as_str).unwrap_or(""), output.status, stderr.trim(), stdout.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn validate_log_name(log_name: &str) -> Result<&str, String> {
+    let log_name = log_name.trim();
+    // Un nom commençant par '/' serait interprété comme une option
+    if log_name.is_empty() || log_name.starts_with('/') {
+        return Err(format!("Nom de journal invalide: '{}'", log_name));
+    }
+    Ok(log_name)
+}
+
+fn set_config_args(log_name: &str, max_size_bytes: Option<u64>, retention: Option<EventLogRetention>, enabled: Option<bool>) -> Result<Vec<String>, String> {
+    let mut args = vec!["sl".to_string(), validate_log_name(log_name)?.to_string()];
+    if let Some(size) = max_size_bytes {
+        if size < MIN_LOG_SIZE_BYTES {
+            return Err(format!("Taille maximale trop petite: {} octets (minimum {} octets)", size, MIN_LOG_SIZE_BYTES));
+        }
+        let rounded = size.div_ceil(LOG_SIZE_GRANULARITY) * LOG_SIZE_GRANULARITY;
+        args.push(format!("/ms:{}", rounded));
+    }
+    if let Some(retention) = retention {
+        args.extend(retention.wevtutil_args().iter().map(|a| a.to_string()));
+    }
+    if let Some(enabled) = enabled {
+        args.push(format!("/e:{}", enabled));
+    }
+    if args.len() == 2 {
+        return Err("Aucune modification demandée".to_string());
+    }
+    Ok(args)
+}
+
+fn clear_args(log_name: &str, backup_path: Option<&str>) -> Result<Vec<String>, String> {
+    let mut args = vec!["cl".to_string(), validate_log_name(log_name)?.to_string()];
+    if let Some(path) = backup_path.map(str::trim).filter(|p| !p.is_empty()) {
+        if !path.to_ascii_lowercase().ends_with(".evtx") {
+            return Err(format!("Le fichier d'archive doit avoir l'extension .evtx: {}", path));
+        }
+        args.push(format!("/bu:{}", path));
+    }
+    Ok(args)
+}
+
+#[command]
+pub async fn list_event_logs(app: AppHandle) -> Result<Vec<EventLogInfo>, String> {
+    println!("Real: list_event_logs() called");
+    // Les journaux inaccessibles sans élévation sont ignorés (SilentlyContinue)
+    let command = "Get-WinEvent -ListLog * -ErrorAction SilentlyContinue | Select-Object LogName, RecordCount, FileSize, \
+        MaximumSizeInBytes, @{N='LogMode';E={$_.LogMode.ToString()}}, IsEnabled, @{N='LogType';E={$_.LogType.ToString()}}, \
+        IsClassicLog, LogFilePath | ConvertTo-Json -Depth 2 -Compress";
+    let output = app.shell()
+        .command("powershell")
+        .args(["-Command", command])
+        .output()
+        .await
+        .map_err(|e| format!("Erreur lors de l'exécution de Get-WinEvent -ListLog: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Get-WinEvent -ListLog a échoué: {:?} \nErreur: {}",
+            output.status, String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json_str = stdout.trim();
+    if json_str.is_empty() {
+        return Ok(vec![]);
+    }
+    let parsed: Vec<PsEventLogInfo> = if json_str.starts_with('[') {
+        serde_json::from_str(json_str).map_err(|e| format!("Erreur parsing JSON (tableau) journaux: {}", e))?
+    } else {
+        vec![serde_json::from_str(json_str).map_err(|e| format!("Erreur parsing JSON (objet unique) journaux: {}", e))?]
+    };
+
+    let mut logs: Vec<EventLogInfo> = parsed.into_iter().map(|ps| EventLogInfo {
+        retention: ps.log_mode.as_deref().and_then(EventLogRetention::from_log_mode),
+        log_name: ps.log_name,
+        record_count: ps.record_count,
+        file_size: ps.file_size,
+        max_size: ps.maximum_size_in_bytes,
+        enabled: ps.is_enabled.unwrap_or(false),
+        log_type: ps.log_type.unwrap_or_default(),
+        classic: ps.is_classic_log.unwrap_or(false),
+        file_path: ps.log_file_path,
+    }).collect();
+    logs.sort_by_key(|l| l.log_name.to_lowercase());
+    Ok(logs)
+}
+
+#[command]
+pub async fn set_event_log_config(
+    app: AppHandle,
+    log_name: String,
+    max_size_bytes: Option<u64>,
+    retention: Option<EventLogRetention>,
+    enabled: Option<bool>,
+) -> Result<(), String> {
+    println!("Real: set_event_log_config(log: '{}', max: {:?}, retention: {:?}, enabled: {:?}) called",
+        log_name, max_size_bytes, retention, enabled);
+    // Important: Nécessite des privilèges admin
+    let args = set_config_args(&log_name, max_size_bytes, retention, enabled)?;
+    run_wevtutil(&app, &args).await?;
+    Ok(())
+}
+
+#[command]
+pub async fn clear_event_log(app: AppHandle, log_name: String, backup_path: Option<String>) -> Result<(), String> {
+    println!("Real: clear_event_log(log: '{}', backup: {:?}) called", log_name, backup_path);
+    // Important: Nécessite des privilèges admin. Avec /bu, le journal n'est vidé que si l'archive a pu être écrite.
+    let args = clear_args(&log_name, backup_path.as_deref())?;
+    run_wevtutil(&app, &args).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_set_config_arguments() {
+        let args = set_config_args(
+            "Microsoft-Windows-TaskScheduler/Operational", Some(20 * 1024 * 1024 + 1), Some(EventLogRetention::AutoBackup), Some(true),
+        ).unwrap();
+        // Arrondi au multiple de 64 Ko supérieur: 20 Mo + 64 Ko
+        assert_eq!(args, vec![
+            "sl", "Microsoft-Windows-TaskScheduler/Operational", "/ms:21037056", "/rt:true", "/ab:true", "/e:true",
+        ]);
+
+        let circular = set_config_args("System", None, Some(EventLogRetention::Circular), None).unwrap();
+        assert_eq!(circular, vec!["sl", "System", "/rt:false", "/ab:false"]);
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert!(set_config_args("System", None, None, None).is_err());
+        assert!(set_config_args("System", Some(4096), None, None).is_err());
+        assert!(set_config_args(" ", Some(MIN_LOG_SIZE_BYTES), None, None).is_err());
+        assert!(set_config_args("/e:false", None, None, Some(true)).is_err());
+    }
+
+    #[test]
+    fn builds_clear_arguments_with_optional_backup() {
+        assert_eq!(clear_args("Application", None).unwrap(), vec!["cl", "Application"]);
+        assert_eq!(clear_args("Application", Some("  ")).unwrap(), vec!["cl", "Application"]);
+        assert_eq!(
+            clear_args("Security", Some("D:\\Archives\\Security 2026.evtx")).unwrap(),
+            vec!["cl", "Security", "/bu:D:\\Archives\\Security 2026.evtx"]
+        );
+        assert!(clear_args("Security", Some("D:\\Archives\\security.txt")).is_err());
+    }
+
+    #[test]
+    fn maps_log_modes() {
+        assert_eq!(EventLogRetention::from_log_mode("AutoBackup"), Some(EventLogRetention::AutoBackup));
+        assert_eq!(EventLogRetention::from_log_mode("Retain"), Some(EventLogRetention::Retain));
+        assert_eq!(EventLogRetention::from_log_mode("Other"), None);
+    }
+}
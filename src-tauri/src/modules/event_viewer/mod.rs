@@ -2,14 +2,32 @@ use serde::{Deserialize, Serialize};
 use std::result::Result;
 use tauri::command;
 use tauri::AppHandle;
+use tauri_plugin_shell::process::CommandEvent;
 use tauri_plugin_shell::ShellExt;
 use serde_json::Value;
 
+use crate::modules::common::powershell::ps_quote;
+
+pub mod event_xml;
+pub mod evtx;
+pub mod export;
+pub mod investigations;
+pub mod logs;
+pub mod paging;
+pub mod query;
+pub mod stats;
+pub mod subscription;
+
+use event_xml::EventDataField;
+use query::EventQuery;
+
 // Structure pour parser le JSON de Get-WinEvent
 // Les noms de champs PowerShell peuvent varier légèrement, ajuster si besoin.
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct PsWinEvent {
+    #[serde(default)]
+    record_id: u64,
     #[serde(rename = "Id")]
     event_id: u32,
     #[serde(rename = "LevelDisplayName")]
@@ -23,6 +41,7 @@ struct PsWinEvent {
 // Structure finale retournée au frontend
 #[derive(Serialize, Debug, Clone)]
 pub struct EventLogEntry {
+    record_id: u64, // Position de l'événement dans le journal (croissante)
     event_id: u32,
     level: String,
     provider_name: String,
@@ -30,183 +49,312 @@ pub struct EventLogEntry {
     message: String,
 }
 
-#[command]
-pub async fn get_events(
-    app: AppHandle, 
-    log_name: String, 
-    max_events: u32,
-    // Nouveaux filtres optionnels
-    level: Option<u8>,      // 1=Critical, 2=Error, 3=Warning, 4=Information, 5=Verbose
-    provider_name_filter: Option<String>,
-    event_id_filter: Option<i32>, // Peut être négatif pour exclure
-    start_time: Option<String>, // Format ISO 8601 ou similaire attendu par PS
-    end_time: Option<String>,
-) -> Result<Vec<EventLogEntry>, String> {
-    println!("Real: get_events(log: {}, max: {}, level: {:?}, provider: {:?}, id: {:?}, start: {:?}, end: {:?}) called", 
-        log_name, max_events, level, provider_name_filter, event_id_filter, start_time, end_time);
-
-    // Construire le filtre HashTable pour Get-WinEvent
-    let mut filter_parts = Vec::new();
-    filter_parts.push(format!("LogName='{}'", log_name));
-    if let Some(lvl) = level {
-        filter_parts.push(format!("Level={}", lvl));
-    }
-    if let Some(ref provider) = provider_name_filter {
-        if !provider.trim().is_empty() {
-             // Utiliser -like pour la flexibilité
-            filter_parts.push(format!("ProviderName='*{}*'", provider.replace('"', "'"))); 
-        }
+// Sélection des propriétés d'un événement, désérialisées dans PsWinEvent
+const EVENT_SELECT_SCRIPT: &str = "Select-Object -Property RecordId, Id, LevelDisplayName, ProviderName, @{Name='TimeCreated';Expression={$_.TimeCreated}}, Message";
+
+// Parse la sortie JSON (objet unique ou tableau) de Get-WinEvent | EVENT_SELECT_SCRIPT
+fn parse_event_entries(json_str: &str) -> Result<Vec<EventLogEntry>, String> {
+    let json_str = json_str.trim();
+    if json_str.is_empty() {
+        return Ok(vec![]);
     }
-     if let Some(id) = event_id_filter {
-        filter_parts.push(format!("Id={}", id)); // Get-WinEvent gère les ID négatifs pour exclusion
+    let parsed_events: Vec<PsWinEvent> = if json_str.starts_with('[') {
+        serde_json::from_str(json_str).map_err(|e| format!("Erreur parsing JSON (tableau) événements: {}", e))?
+    } else {
+        serde_json::from_str::<PsWinEvent>(json_str).map(|e| vec![e])
+            .map_err(|e| format!("Erreur parsing JSON (objet unique) événements: {}", e))?
+    };
+
+    // Mapper vers la structure finale
+    Ok(parsed_events.into_iter().map(entry_from_ps).collect())
+}
+
+fn entry_from_ps(ps_event: PsWinEvent) -> EventLogEntry {
+    let time_created_str = ps_event.time_created
+        .as_ref()
+        .and_then(|v| v.get("DateTime"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "N/A".to_string());
+
+    EventLogEntry {
+        record_id: ps_event.record_id,
+        event_id: ps_event.event_id,
+        level: ps_event.level.unwrap_or_else(|| "Inconnu".to_string()),
+        provider_name: ps_event.provider_name,
+        time_created: time_created_str,
+        message: ps_event.message.unwrap_or_default(),
     }
-     if let Some(ref start) = start_time {
-        if !start.trim().is_empty() {
-             filter_parts.push(format!("StartTime='{}'", start.replace('"', "'"))); 
-        }
+}
+
+// Script Get-WinEvent pour une source (-LogName ... ou -Path ...) et un filtre XPath.
+// L'absence de résultat produit '[]' (FullyQualifiedErrorId n'est pas localisé, contrairement au message d'erreur).
+fn winevent_script(source: &str, xpath: &str, max_events: u32, oldest: bool) -> String {
+    format!(
+        "try {{ Get-WinEvent {} -FilterXPath {} -MaxEvents {}{} -ErrorAction Stop | {} | ConvertTo-Json -Depth 3 -Compress }} \
+         catch {{ if ($_.FullyQualifiedErrorId -like 'NoMatchingEventsFound*') {{ Write-Output '[]' }} else {{ Write-Error $_.Exception.Message; exit 1 }} }}",
+        source, ps_quote(xpath), max_events, if oldest { " -Oldest" } else { "" }, EVENT_SELECT_SCRIPT
+    )
+}
+
+// Événements (EventRecord) de plusieurs journaux, fusionnés par date puis limités à max_events.
+// -MaxEvents sur "-LogName a,b" s'appliquerait au flux concaténé journal après journal: on lit donc
+// jusqu'à max_events par journal avant de trier.
+fn merged_events_expression(logs: &[String], xpath: &str, max_events: u32, oldest: bool) -> String {
+    let log_list = logs.iter().map(|l| ps_quote(l)).collect::<Vec<_>>().join(",");
+    let fetch = format!(
+        "foreach ($log in @({})) {{ try {{ Get-WinEvent -LogName $log -FilterXPath {} -MaxEvents {}{} -ErrorAction Stop }} \
+         catch {{ if ($_.FullyQualifiedErrorId -notlike 'NoMatchingEventsFound*') {{ Write-Error $_.Exception.Message; exit 1 }} }} }}",
+        log_list, ps_quote(xpath), max_events, if oldest { " -Oldest" } else { "" }
+    );
+    if logs.len() == 1 {
+        return format!("@({})", fetch);
     }
-     if let Some(ref end) = end_time {
-         if !end.trim().is_empty() {
-             filter_parts.push(format!("EndTime='{}'", end.replace('"', "'"))); 
-         }
+    format!(
+        "@(@({}) | Sort-Object -Property TimeCreated{} | Select-Object -First {})",
+        fetch, if oldest { "" } else { " -Descending" }, max_events
+    )
+}
+
+fn event_query_script(query: &EventQuery, max_events: u32, oldest: bool) -> Result<String, String> {
+    let logs = query.log_names()?;
+    if logs.len() == 1 {
+        return Ok(winevent_script(&format!("-LogName {}", ps_quote(&logs[0])), &query.to_xpath()?, max_events, oldest));
     }
-    let filter_hashtable = format!("@{{{}}}", filter_parts.join("; "));
-
-    // Construire la commande PowerShell
-    // Pour le journal de sécurité, utiliser une approche différente car il requiert souvent des privilèges élevés
-    let command = if log_name == "Security" {
-        // Construire un hashtable de filtres spécifique pour le journal de sécurité
-        let mut security_filter_parts = vec!["LogName='Security'".to_string()];
-        
-        // Ajouter les filtres supplémentaires si présents
-        if let Some(lvl) = level {
-            security_filter_parts.push(format!("Level={}", lvl));
-        }
-        if let Some(provider) = &provider_name_filter {
-            if !provider.trim().is_empty() {
-                security_filter_parts.push(format!("ProviderName='*{}*'", provider.replace('"', "'")));
-            }
-        }
-        if let Some(id) = event_id_filter {
-            security_filter_parts.push(format!("Id={}", id));
-        }
-        if let Some(start) = &start_time {
-            if !start.trim().is_empty() {
-                security_filter_parts.push(format!("StartTime='{}'", start.replace('"', "'")));
-            }
-        }
-        if let Some(end) = &end_time {
-            if !end.trim().is_empty() {
-                security_filter_parts.push(format!("EndTime='{}'", end.replace('"', "'")));
-            }
-        }
-        
-        // Construire la commande avec gestion d'erreurs intégrée
-        format!(
-            "try {{ Get-WinEvent -FilterHashtable @{{{}}} -MaxEvents {} -ErrorAction Stop | \
-             Select-Object -Property Id, LevelDisplayName, ProviderName, @{{Name='TimeCreated';Expression={{$_.TimeCreated}}}}, Message | \
-             ConvertTo-Json -Depth 3 -Compress }} catch [System.Exception] {{ \
-             if ($_.Exception.Message -like '*No events were found*') {{ \
-                Write-Output '[]' \
-             }} else {{ \
-                Write-Error $_.Exception.Message \
-                exit 1 \
-             }} }}",
-            security_filter_parts.join("; "),
-            max_events
-        )
-    } else {
-        // Pour les autres journaux, utiliser l'approche normale
-        let part1 = format!("Get-WinEvent -FilterHashtable {} -MaxEvents {} -ErrorAction SilentlyContinue | ", filter_hashtable, max_events);
-        let part2 = "Select-Object -Property Id, LevelDisplayName, ProviderName, @{Name='TimeCreated';Expression={$_.TimeCreated}}, Message | ";
-        let part3 = "ConvertTo-Json -Depth 3 -Compress";
-        vec![part1, part2.to_string(), part3.to_string()].join("")
-    };
+    Ok(format!(
+        "$events = {}; ConvertTo-Json -InputObject @($events | {}) -Depth 3 -Compress",
+        merged_events_expression(&logs, &query.to_xpath()?, max_events, oldest), EVENT_SELECT_SCRIPT
+    ))
+}
 
+async fn run_powershell_events(app: &AppHandle, command: &str) -> Result<Vec<EventLogEntry>, String> {
     let output = app.shell()
         .command("powershell")
-        .args(&["-Command", &command]) // Passer la commande construite
+        .args(["-Command", command])
         .output()
         .await
         .map_err(|e| format!("Erreur lors de l'exécution de Get-WinEvent: {}", e))?;
 
-    // Pour le journal de sécurité, nous avons géré les erreurs dans la commande PowerShell elle-même
-    if !output.status.success() && log_name != "Security" {
-        // Vérifier le contenu de stderr pour déterminer si c'est l'erreur "No events found"
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        
-        // Si l'erreur est "No events were found that match the specified selection criteria",
-        // renvoyer une liste vide plutôt qu'une erreur
-        if stderr.contains("No events were found that match the specified selection criteria") {
-            println!("Aucun événement trouvé pour les critères spécifiés dans le journal '{}'", log_name);
-            return Ok(vec![]);
+    if !output.status.success() {
+        return Err(format!("Get-WinEvent a échoué: {:?} \nErreur: {}",
+            output.status, String::from_utf8_lossy(&output.stderr)));
+    }
+    parse_event_entries(&String::from_utf8_lossy(&output.stdout))
+}
+
+// Exécute un script Get-WinEvent et transmet chaque ligne de sa sortie à on_line au fil de l'eau,
+// sans garder toute la sortie en mémoire. Une erreur de on_line arrête PowerShell.
+async fn stream_powershell_lines<F>(app: &AppHandle, command: &str, mut on_line: F) -> Result<(), String>
+where
+    F: FnMut(&str) -> Result<(), String>,
+{
+    let (mut rx, child) = app.shell()
+        .command("powershell")
+        .args(["-Command", command])
+        .spawn()
+        .map_err(|e| format!("Erreur lors de l'exécution de Get-WinEvent: {}", e))?;
+
+    let mut stderr = String::new();
+    let mut exit_code = None;
+    while let Some(event) = rx.recv().await {
+        match event {
+            CommandEvent::Stdout(bytes) => {
+                for line in String::from_utf8_lossy(&bytes).lines() {
+                    if let Err(e) = on_line(line) {
+                        let _ = child.kill();
+                        return Err(e);
+                    }
+                }
+            }
+            CommandEvent::Stderr(line) => stderr.push_str(&String::from_utf8_lossy(&line)),
+            CommandEvent::Error(e) => return Err(format!("Erreur lecture Get-WinEvent: {}", e)),
+            CommandEvent::Terminated(payload) => exit_code = payload.code,
+            _ => {}
         }
-        
-        return Err(format!("Get-WinEvent pour '{}' a échoué: {:?} \nErreur: {}", 
-            log_name, output.status, stderr));
     }
+    if exit_code != Some(0) {
+        return Err(format!("Get-WinEvent a échoué (code {:?}) \nErreur: {}", exit_code, stderr.trim()));
+    }
+    Ok(())
+}
 
-    let events_json_str = String::from_utf8_lossy(&output.stdout);
+async fn run_event_query(app: &AppHandle, query: &EventQuery, max_events: u32, oldest: bool) -> Result<Vec<EventLogEntry>, String> {
+    let command = event_query_script(query, max_events, oldest)?;
+    run_powershell_events(app, &command).await
+}
+
+// Événements complets (XML) pour les analyses faites côté Rust (EventData inclus)
+async fn fetch_event_records(app: &AppHandle, query: &EventQuery, max_events: u32) -> Result<Vec<event_xml::ParsedEventXml>, String> {
+    let logs = query.log_names()?;
+    let command = format!(
+        "$events = {}; $xml = @($events | ForEach-Object {{ $_.ToXml() }}); ConvertTo-Json -InputObject $xml -Compress",
+        merged_events_expression(&logs, &query.to_xpath()?, max_events, false)
+    );
+    let output = app.shell()
+        .command("powershell")
+        .args(["-Command", &command])
+        .output()
+        .await
+        .map_err(|e| format!("Erreur lors de l'exécution de Get-WinEvent: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Get-WinEvent a échoué: {:?} \nErreur: {}",
+            output.status, String::from_utf8_lossy(&output.stderr)));
+    }
 
-    if events_json_str.trim().is_empty() {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json_str = stdout.trim();
+    if json_str.is_empty() {
         return Ok(vec![]);
     }
+    let xml_list: Vec<String> = serde_json::from_str(json_str)
+        .map_err(|e| format!("Erreur parsing JSON (XML) événements: {}", e))?;
+    // Un événement au XML illisible est ignoré plutôt que de faire échouer l'analyse
+    Ok(xml_list.iter().filter_map(|xml| event_xml::parse_event_xml(xml).ok()).collect())
+}
 
-    // Parser la sortie JSON
-    let parsed_events: Vec<PsWinEvent> = if events_json_str.trim().starts_with('[') {
-        match serde_json::from_str(&events_json_str) {
-            Ok(events) => events,
-            Err(e) => {
-                println!("Erreur parsing JSON (tableau) événements '{}': {}", log_name, e);
-                // Si l'erreur est due à un JSON vide ou mal formé, retourner un vecteur vide
-                return Ok(vec![]);
-            }
-        }
-    } else {
-        match serde_json::from_str::<PsWinEvent>(&events_json_str) {
-            Ok(event) => vec![event],
-            Err(e) => {
-                println!("Erreur parsing JSON (objet unique) événements '{}': {}", log_name, e);
-                // Si l'erreur est due à un JSON vide ou mal formé, retourner un vecteur vide
-                return Ok(vec![]);
-            }
-        }
-    };
+#[command]
+pub async fn get_events(app: AppHandle, query: EventQuery, max_events: u32) -> Result<Vec<EventLogEntry>, String> {
+    println!("Real: get_events(query: {:?}, max: {}) called", query, max_events);
+    // Le journal Security requiert des privilèges élevés: l'erreur d'accès est remontée telle quelle
+    run_event_query(&app, &query, max_events.max(1), false).await
+}
 
-    // Mapper vers la structure finale
-    let final_events = parsed_events.into_iter().map(|ps_event| {
-        let time_created_str = ps_event.time_created
-            .as_ref()
-            .and_then(|v| v.get("DateTime"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| "N/A".to_string());
-
-        EventLogEntry {
-            event_id: ps_event.event_id,
-            level: ps_event.level.unwrap_or_else(|| "Inconnu".to_string()),
-            provider_name: ps_event.provider_name,
-            time_created: time_created_str,
-            message: ps_event.message.unwrap_or_default(),
-        }
-    }).collect();
+// --- Détails complets d'un événement ---
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsEventDetails {
+    xml: String,
+    message: Option<String>,
+    level_display_name: Option<String>,
+    task_display_name: Option<String>,
+    opcode_display_name: Option<String>,
+    #[serde(default)]
+    keywords_display_names: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EventDetails {
+    record_id: u64,
+    log_name: String,
+    event_id: u32,
+    qualifiers: Option<u32>,
+    version: Option<u8>,
+    level: Option<u8>,
+    level_name: Option<String>,
+    task: Option<u16>,
+    task_name: Option<String>,
+    opcode: Option<u8>,
+    opcode_name: Option<String>,
+    keywords: Option<String>, // Masque hexadécimal
+    keyword_names: Vec<String>,
+    provider_name: String,
+    provider_guid: Option<String>,
+    time_created: Option<String>, // ISO 8601 UTC (SystemTime)
+    computer: Option<String>,
+    user_sid: Option<String>,
+    process_id: Option<u32>,
+    thread_id: Option<u32>,
+    activity_id: Option<String>,
+    related_activity_id: Option<String>,
+    message: String,
+    event_data: Vec<EventDataField>, // Champs nommés de EventData
+    user_data: Vec<EventDataField>,  // Champs aplatis de UserData
+    xml: String,
+}
 
-    Ok(final_events)
+fn event_details_from_ps(log_name: &str, ps: PsEventDetails) -> Result<EventDetails, String> {
+    let parsed = event_xml::parse_event_xml(&ps.xml)?;
+    let non_empty = |v: Option<String>| v.filter(|s| !s.trim().is_empty());
+    Ok(EventDetails {
+        record_id: parsed.record_id,
+        log_name: parsed.channel.unwrap_or_else(|| log_name.to_string()),
+        event_id: parsed.event_id,
+        qualifiers: parsed.qualifiers,
+        version: parsed.version,
+        level: parsed.level,
+        level_name: non_empty(ps.level_display_name),
+        task: parsed.task,
+        task_name: non_empty(ps.task_display_name),
+        opcode: parsed.opcode,
+        opcode_name: non_empty(ps.opcode_display_name),
+        keywords: parsed.keywords,
+        keyword_names: ps.keywords_display_names.into_iter().filter(|k| !k.trim().is_empty()).collect(),
+        provider_name: parsed.provider_name,
+        provider_guid: parsed.provider_guid,
+        time_created: parsed.time_created,
+        computer: parsed.computer,
+        user_sid: parsed.user_sid,
+        process_id: parsed.process_id,
+        thread_id: parsed.thread_id,
+        activity_id: parsed.activity_id,
+        related_activity_id: parsed.related_activity_id,
+        message: ps.message.unwrap_or_default(),
+        event_data: parsed.event_data,
+        user_data: parsed.user_data,
+        xml: ps.xml,
+    })
 }
 
 #[command]
-pub async fn clear_event_log(app: AppHandle, log_name: String) -> Result<(), String> {
-     println!("Real: clear_event_log(log: '{}') called", log_name);
-     // Important: Nécessite des privilèges admin
-     if log_name.trim().is_empty() {
+pub async fn get_event_details(app: AppHandle, log_name: String, record_id: u64) -> Result<EventDetails, String> {
+    println!("Real: get_event_details(log: '{}', record: {}) called", log_name, record_id);
+    if log_name.trim().is_empty() {
         return Err("Nom de journal invalide".to_string());
-     }
-     let command = format!("Clear-EventLog -LogName \"{}\"", log_name.replace('"', "'"));
-     let output = app.shell().command("powershell").args(&["-Command", &command]).output().await
-        .map_err(|e| format!("Erreur Clear-EventLog: {}", e))?;
+    }
+    // Les noms d'affichage (tâche, opcode, mots-clés) proviennent des métadonnées du fournisseur
+    // et ne figurent pas dans le XML: ils sont lus côté PowerShell
+    let command = format!(
+        "$e = Get-WinEvent -LogName {} -FilterXPath '*[System[EventRecordID={}]]' -MaxEvents 1 -ErrorAction Stop; \
+         [PSCustomObject]@{{ Xml = $e.ToXml(); Message = $e.Message; LevelDisplayName = $e.LevelDisplayName; \
+         TaskDisplayName = $e.TaskDisplayName; OpcodeDisplayName = $e.OpcodeDisplayName; \
+         KeywordsDisplayNames = @($e.KeywordsDisplayNames | Where-Object {{ $_ }}) }} | ConvertTo-Json -Depth 3 -Compress",
+        ps_quote(&log_name), record_id
+    );
+    let output = app.shell()
+        .command("powershell")
+        .args(["-Command", &command])
+        .output()
+        .await
+        .map_err(|e| format!("Erreur lors de l'exécution de Get-WinEvent: {}", e))?;
+
     if !output.status.success() {
-        return Err(format!("Clear-EventLog '{}' a échoué: {:?} \nErreur: {}", 
-            log_name, output.status, String::from_utf8_lossy(&output.stderr)));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("NoMatchingEventsFound") {
+            return Err(format!("Événement {} introuvable dans le journal '{}'", record_id, log_name));
+        }
+        return Err(format!("Get-WinEvent a échoué: {:?} \nErreur: {}", output.status, stderr));
     }
-    Ok(())
-} 
\ No newline at end of file
+    let ps: PsEventDetails = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim())
+        .map_err(|e| format!("Erreur parsing JSON détails événement: {}", e))?;
+    event_details_from_ps(&log_name, ps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn merges_several_logs_before_limiting() {
+        let script = merged_events_expression(&logs(&["System", "Application"]), "*", 50, false);
+        // Limite appliquée à chaque journal, puis au résultat fusionné et trié
+        assert!(script.contains("foreach ($log in @('System','Application'))"));
+        assert!(script.contains("Get-WinEvent -LogName $log -FilterXPath '*' -MaxEvents 50 -ErrorAction Stop"));
+        assert!(script.ends_with("| Sort-Object -Property TimeCreated -Descending | Select-Object -First 50)"));
+
+        let oldest = merged_events_expression(&logs(&["System", "Application"]), "*", 10, true);
+        assert!(oldest.contains("-MaxEvents 10 -Oldest"));
+        assert!(oldest.ends_with("| Sort-Object -Property TimeCreated | Select-Object -First 10)"));
+    }
+
+    #[test]
+    fn single_log_keeps_log_order() {
+        let script = merged_events_expression(&logs(&["Security"]), "*[System[(EventID=4625)]]", 20, false);
+        assert!(!script.contains("Sort-Object"));
+        assert!(script.contains("-FilterXPath '*[System[(EventID=4625)]]' -MaxEvents 20 -ErrorAction Stop"));
+    }
+}
@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use std::result::Result;
+use tauri::command;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+use crate::modules::common::powershell::ps_quote;
+
+use super::query::EventQuery;
+use super::{entry_from_ps, EventLogEntry, PsWinEvent, EVENT_SELECT_SCRIPT};
+
+// --- Pagination par curseur (signets sur le RecordId) ---
+// Les RecordId croissent avec l'écriture dans un journal: un curseur reste valide quand de nouveaux
+// événements arrivent, contrairement à un décalage (offset). Ils ne sont comparables que dans
+// un même journal, d'où la restriction à un seul journal.
+
+const MAX_PAGE_SIZE: u32 = 1000;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PageDirection {
+    Older, // Événements antérieurs au curseur (sans curseur: les plus récents)
+    Newer, // Événements postérieurs au curseur (sans curseur: les plus anciens)
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TotalCountKind {
+    Exact,      // Pas de filtre: nombre d'enregistrements du journal
+    UpperBound, // Filtre actif: le journal contient au plus ce nombre d'événements correspondants
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EventPage {
+    events: Vec<EventLogEntry>, // Du plus récent au plus ancien, quel que soit le sens
+    older_cursor: Option<u64>,  // À passer avec direction=older pour la page précédente
+    newer_cursor: Option<u64>,  // À passer avec direction=newer pour la page suivante
+    has_older: bool,
+    has_newer: bool,
+    total_estimate: Option<u64>,
+    total_kind: TotalCountKind,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsEventPage {
+    record_count: Option<u64>,
+    #[serde(default)]
+    events: Vec<PsWinEvent>,
+}
+
+// Bornes (after, before) et ordre de lecture (-Oldest) d'une page
+fn page_bounds(direction: PageDirection, cursor: Option<u64>) -> (Option<u64>, Option<u64>, bool) {
+    match direction {
+        PageDirection::Older => (None, cursor, false),
+        PageDirection::Newer => (cursor, None, true),
+    }
+}
+
+// Une page est lue avec un événement de plus que demandé pour savoir s'il en reste au-delà
+fn build_page(
+    mut events: Vec<EventLogEntry>,
+    page_size: usize,
+    direction: PageDirection,
+    cursor: Option<u64>,
+    total_estimate: Option<u64>,
+    total_kind: TotalCountKind,
+) -> EventPage {
+    let more = events.len() > page_size;
+    events.truncate(page_size);
+    if direction == PageDirection::Newer {
+        events.reverse();
+    }
+    // Un curseur fourni provient d'une page voisine: il reste des événements de l'autre côté
+    let (has_older, has_newer) = match direction {
+        PageDirection::Older => (more, cursor.is_some()),
+        PageDirection::Newer => (cursor.is_some(), more),
+    };
+    // Page vide: le curseur reçu reste valable dans les deux sens (réessayer plus tard, revenir en arrière)
+    EventPage {
+        older_cursor: events.last().map(|e| e.record_id).or(cursor),
+        newer_cursor: events.first().map(|e| e.record_id).or(cursor),
+        events,
+        has_older,
+        has_newer,
+        total_estimate,
+        total_kind,
+    }
+}
+
+#[command]
+pub async fn get_events_page(
+    app: AppHandle,
+    query: EventQuery,
+    page_size: u32,
+    cursor: Option<u64>,
+    direction: Option<PageDirection>,
+) -> Result<EventPage, String> {
+    println!("Real: get_events_page(query: {:?}, size: {}, cursor: {:?}, direction: {:?}) called", query, page_size, cursor, direction);
+    let logs = query.log_names()?;
+    if logs.len() != 1 {
+        return Err("La pagination ne porte que sur un seul journal".to_string());
+    }
+    let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+    let direction = direction.unwrap_or(PageDirection::Older);
+
+    let mut query = query;
+    query.set_record_bounds(None, None);
+    let total_kind = if query.to_xpath()? == "*" { TotalCountKind::Exact } else { TotalCountKind::UpperBound };
+    let (after, before, oldest) = page_bounds(direction, cursor);
+    query.set_record_bounds(after, before);
+
+    let command = format!(
+        "$count = (Get-WinEvent -ListLog {0} -ErrorAction Stop).RecordCount; \
+         try {{ $events = @(Get-WinEvent -LogName {0} -FilterXPath {1} -MaxEvents {2}{3} -ErrorAction Stop | {4}) }} \
+         catch {{ if ($_.FullyQualifiedErrorId -like 'NoMatchingEventsFound*') {{ $events = @() }} else {{ Write-Error $_.Exception.Message; exit 1 }} }}; \
+         [PSCustomObject]@{{ RecordCount = $count; Events = $events }} | ConvertTo-Json -Depth 4 -Compress",
+        ps_quote(&logs[0]), ps_quote(&query.to_xpath()?), page_size + 1, if oldest { " -Oldest" } else { "" }, EVENT_SELECT_SCRIPT
+    );
+    let output = app.shell()
+        .command("powershell")
+        .args(["-Command", &command])
+        .output()
+        .await
+        .map_err(|e| format!("Erreur lors de l'exécution de Get-WinEvent: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Get-WinEvent a échoué: {:?} \nErreur: {}",
+            output.status, String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let page: PsEventPage = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim())
+        .map_err(|e| format!("Erreur parsing JSON page d'événements: {}", e))?;
+    let events = page.events.into_iter().map(entry_from_ps).collect();
+    Ok(build_page(events, page_size as usize, direction, cursor, page.record_count, total_kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(ids: &[u64]) -> Vec<EventLogEntry> {
+        ids.iter().map(|&record_id| EventLogEntry {
+            record_id,
+            event_id: 1,
+            level: "Information".to_string(),
+            provider_name: "Test".to_string(),
+            time_created: "N/A".to_string(),
+            message: String::new(),
+        }).collect()
+    }
+
+    fn ids(page: &EventPage) -> Vec<u64> {
+        page.events.iter().map(|e| e.record_id).collect()
+    }
+
+    #[test]
+    fn computes_bounds_per_direction() {
+        assert_eq!(page_bounds(PageDirection::Older, None), (None, None, false));
+        assert_eq!(page_bounds(PageDirection::Older, Some(500)), (None, Some(500), false));
+        assert_eq!(page_bounds(PageDirection::Newer, Some(500)), (Some(500), None, true));
+    }
+
+    #[test]
+    fn first_page_is_newest_with_older_remaining() {
+        // Lecture par ordre décroissant, 3 demandés + 1 de contrôle
+        let page = build_page(entries(&[100, 99, 98, 97]), 3, PageDirection::Older, None, Some(100), TotalCountKind::Exact);
+        assert_eq!(ids(&page), vec![100, 99, 98]);
+        assert!(page.has_older);
+        assert!(!page.has_newer);
+        assert_eq!(page.older_cursor, Some(98));
+        assert_eq!(page.newer_cursor, Some(100));
+    }
+
+    #[test]
+    fn last_older_page_has_no_more() {
+        let page = build_page(entries(&[2, 1]), 3, PageDirection::Older, Some(3), None, TotalCountKind::UpperBound);
+        assert_eq!(ids(&page), vec![2, 1]);
+        assert!(!page.has_older);
+        assert!(page.has_newer);
+    }
+
+    #[test]
+    fn newer_pages_are_returned_newest_first() {
+        // Lecture chronologique (-Oldest) après le curseur 98
+        let page = build_page(entries(&[99, 100, 101, 102]), 3, PageDirection::Newer, Some(98), None, TotalCountKind::Exact);
+        assert_eq!(ids(&page), vec![101, 100, 99]);
+        assert!(page.has_newer);
+        assert!(page.has_older);
+        assert_eq!(page.newer_cursor, Some(101));
+        assert_eq!(page.older_cursor, Some(99));
+    }
+
+    #[test]
+    fn empty_page_keeps_the_cursor() {
+        // Aucun nouvel événement: on peut réessayer plus tard avec le même curseur
+        let page = build_page(vec![], 50, PageDirection::Newer, Some(120), None, TotalCountKind::Exact);
+        assert!(page.events.is_empty());
+        assert_eq!(page.newer_cursor, Some(120));
+        assert_eq!(page.older_cursor, Some(120));
+        assert!(!page.has_newer);
+
+        // Début du journal atteint: même principe dans l'autre sens
+        let page = build_page(vec![], 50, PageDirection::Older, Some(1), None, TotalCountKind::Exact);
+        assert_eq!(page.older_cursor, Some(1));
+        assert_eq!(page.newer_cursor, Some(1));
+        assert!(!page.has_older);
+    }
+}
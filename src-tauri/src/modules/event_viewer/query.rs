@@ -0,0 +1,568 @@
+use serde::Deserialize;
+
+use crate::modules::users::{format_iso8601_ms, parse_iso8601_ms};
+
+use super::event_xml::ParsedEventXml;
+
+// --- Requête structurée d'événements, compilée en XPath pour Get-WinEvent -FilterXPath ---
+// Le moteur du journal d'événements n'accepte qu'un sous-ensemble d'XPath 1.0: pas de contains()
+// ni de starts-with(), d'où des correspondances exactes sur les fournisseurs et les champs.
+
+// Identifiant seul (4624) ou plage inclusive ({ "from": 4624, "to": 4634 })
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum EventIdFilter {
+    Single(u32),
+    Range { from: u32, to: u32 },
+}
+
+// Correspondance exacte sur un champ nommé de EventData (ex: TargetUserName = "jdupont")
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct EventDataMatch {
+    name: String,
+    value: String,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct EventQuery {
+    #[serde(default)]
+    logs: Vec<String>,                     // "System", "Microsoft-Windows-TaskScheduler/Operational"...
+    #[serde(default)]
+    event_ids: Vec<EventIdFilter>,         // Au moins un doit correspondre
+    #[serde(default)]
+    exclude_event_ids: Vec<EventIdFilter>,
+    #[serde(default)]
+    levels: Vec<u8>,                       // 1=Critical, 2=Error, 3=Warning, 4=Information, 5=Verbose
+    #[serde(default)]
+    providers: Vec<String>,                // Noms exacts des fournisseurs
+    #[serde(default)]
+    keywords: Vec<String>,                 // AuditSuccess, AuditFailure, Classic... ou masque "0x..."
+    user_sid: Option<String>,              // Security/@UserID
+    start_time: Option<String>,            // ISO 8601 (converti en UTC)
+    end_time: Option<String>,
+    within_ms: Option<u64>,                // Événements des N dernières millisecondes
+    after_record_id: Option<u64>,          // RecordId strictement supérieur
+    before_record_id: Option<u64>,         // RecordId strictement inférieur
+    #[serde(default)]
+    event_data: Vec<EventDataMatch>,       // Toutes doivent correspondre
+}
+
+// Mots-clés standard (winmeta.xml)
+const STANDARD_KEYWORDS: [(&str, u64); 8] = [
+    ("ResponseTime", 0x0001_0000_0000_0000),
+    ("WdiContext", 0x0002_0000_0000_0000),
+    ("WdiDiagnostic", 0x0004_0000_0000_0000),
+    ("Sqm", 0x0008_0000_0000_0000),
+    ("AuditFailure", 0x0010_0000_0000_0000),
+    ("AuditSuccess", 0x0020_0000_0000_0000),
+    ("CorrelationHint", 0x0040_0000_0000_0000),
+    ("Classic", 0x0080_0000_0000_0000),
+];
+
+const MAX_LEVEL: u8 = 5;
+
+// Littéral de chaîne XPath: XPath 1.0 n'a pas d'échappement, on choisit le délimiteur absent de la valeur
+fn xpath_literal(value: &str) -> Result<String, String> {
+    if !value.contains('\'') {
+        Ok(format!("'{}'", value))
+    } else if !value.contains('"') {
+        Ok(format!("\"{}\"", value))
+    } else {
+        Err(format!("Valeur non représentable en XPath (apostrophe et guillemet): {}", value))
+    }
+}
+
+fn event_id_condition(filter: &EventIdFilter) -> Result<String, String> {
+    match *filter {
+        EventIdFilter::Single(id) => Ok(format!("EventID={}", id)),
+        EventIdFilter::Range { from, to } if from == to => Ok(format!("EventID={}", from)),
+        EventIdFilter::Range { from, to } if from < to => Ok(format!("(EventID>={} and EventID<={})", from, to)),
+        EventIdFilter::Range { from, to } => Err(format!("Plage d'identifiants invalide: {}-{}", from, to)),
+    }
+}
+
+fn event_id_range(filter: &EventIdFilter) -> (u32, u32) {
+    match *filter {
+        EventIdFilter::Single(id) => (id, id),
+        EventIdFilter::Range { from, to } => (from, to),
+    }
+}
+
+fn any_of(conditions: Vec<String>) -> String {
+    if conditions.len() == 1 {
+        conditions.into_iter().next().unwrap_or_default()
+    } else {
+        format!("({})", conditions.join(" or "))
+    }
+}
+
+fn keyword_mask(keyword: &str) -> Result<u64, String> {
+    let keyword = keyword.trim();
+    if let Some(hex) = keyword.strip_prefix("0x").or_else(|| keyword.strip_prefix("0X")) {
+        return u64::from_str_radix(hex, 16).map_err(|_| format!("Masque de mots-clés invalide: {}", keyword));
+    }
+    STANDARD_KEYWORDS.iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(keyword))
+        .map(|(_, mask)| *mask)
+        .ok_or_else(|| format!("Mot-clé inconnu: {}", keyword))
+}
+
+fn is_valid_sid(sid: &str) -> bool {
+    let mut parts = sid.split('-');
+    parts.next() == Some("S")
+        && parts.next() == Some("1")
+        && {
+            let rest: Vec<&str> = parts.collect();
+            !rest.is_empty() && rest.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+        }
+}
+
+// Date ISO 8601 normalisée au format de @SystemTime (UTC, millisecondes)
+fn system_time_literal(value: &str) -> Result<String, String> {
+    let ms = parse_iso8601_ms(value.trim()).ok_or_else(|| format!("Date invalide (ISO 8601 attendu): {}", value))?;
+    let seconds = format_iso8601_ms(ms);
+    Ok(format!("'{}.{:03}Z'", seconds.trim_end_matches('Z'), ms.rem_euclid(1000)))
+}
+
+impl EventQuery {
+    pub fn for_log(log_name: &str) -> Self {
+        EventQuery::default().with_log(log_name)
+    }
+
+    // Même filtre restreint à un seul journal
+    pub fn with_log(mut self, log_name: &str) -> Self {
+        self.logs = vec![log_name.to_string()];
+        self
+    }
+
+    // Journaux interrogés, sans doublon
+    pub fn log_names(&self) -> Result<Vec<String>, String> {
+        let mut logs: Vec<String> = Vec::new();
+        for log in self.logs.iter().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+            if !logs.iter().any(|l| l.eq_ignore_ascii_case(log)) {
+                logs.push(log.to_string());
+            }
+        }
+        if logs.is_empty() {
+            return Err("Au moins un journal doit être spécifié.".to_string());
+        }
+        Ok(logs)
+    }
+
+    pub fn with_event_ids(mut self, event_ids: &[u32]) -> Self {
+        self.event_ids = event_ids.iter().map(|&id| EventIdFilter::Single(id)).collect();
+        self
+    }
+
+    pub fn with_providers(mut self, providers: &[&str]) -> Self {
+        self.providers = providers.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    pub fn with_within_ms(mut self, within_ms: u64) -> Self {
+        self.within_ms = Some(within_ms);
+        self
+    }
+
+    pub fn with_time_range(mut self, start_time: Option<String>, end_time: Option<String>) -> Self {
+        self.start_time = start_time;
+        self.end_time = end_time;
+        self
+    }
+
+    pub fn set_record_bounds(&mut self, after_record_id: Option<u64>, before_record_id: Option<u64>) {
+        self.after_record_id = after_record_id;
+        self.before_record_id = before_record_id;
+    }
+
+    pub fn to_xpath(&self) -> Result<String, String> {
+        let mut system = Vec::new();
+
+        if !self.event_ids.is_empty() {
+            let ids = self.event_ids.iter().map(event_id_condition).collect::<Result<Vec<_>, _>>()?;
+            system.push(any_of(ids));
+        }
+        if !self.exclude_event_ids.is_empty() {
+            let ids = self.exclude_event_ids.iter().map(event_id_condition).collect::<Result<Vec<_>, _>>()?;
+            system.push(format!("not({})", ids.join(" or ")));
+        }
+
+        if !self.levels.is_empty() {
+            let mut levels: Vec<u8> = Vec::new();
+            for &level in &self.levels {
+                if level == 0 || level > MAX_LEVEL {
+                    return Err(format!("Niveau invalide: {} (attendu: 1 à {})", level, MAX_LEVEL));
+                }
+                if !levels.contains(&level) {
+                    levels.push(level);
+                }
+                // Les journaux classiques enregistrent les informations avec le niveau 0 (LogAlways)
+                if level == 4 && !levels.contains(&0) {
+                    levels.push(0);
+                }
+            }
+            system.push(any_of(levels.iter().map(|l| format!("Level={}", l)).collect()));
+        }
+
+        let providers: Vec<&str> = self.providers.iter().map(|p| p.trim()).filter(|p| !p.is_empty()).collect();
+        if !providers.is_empty() {
+            let names = providers.iter()
+                .map(|p| xpath_literal(p).map(|l| format!("@Name={}", l)))
+                .collect::<Result<Vec<_>, _>>()?;
+            system.push(format!("Provider[{}]", names.join(" or ")));
+        }
+
+        if !self.keywords.is_empty() {
+            let mut mask = 0u64;
+            for keyword in &self.keywords {
+                mask |= keyword_mask(keyword)?;
+            }
+            system.push(format!("band(Keywords,{})", mask));
+        }
+
+        if let Some(sid) = self.user_sid.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            if !is_valid_sid(sid) {
+                return Err(format!("SID invalide: {}", sid));
+            }
+            system.push(format!("Security[@UserID='{}']", sid));
+        }
+
+        let mut time = Vec::new();
+        if let Some(start) = self.start_time.as_deref().filter(|s| !s.trim().is_empty()) {
+            time.push(format!("@SystemTime>={}", system_time_literal(start)?));
+        }
+        if let Some(end) = self.end_time.as_deref().filter(|s| !s.trim().is_empty()) {
+            time.push(format!("@SystemTime<={}", system_time_literal(end)?));
+        }
+        if let Some(within) = self.within_ms {
+            time.push(format!("timediff(@SystemTime)<={}", within));
+        }
+        if !time.is_empty() {
+            system.push(format!("TimeCreated[{}]", time.join(" and ")));
+        }
+
+        if let Some(after) = self.after_record_id {
+            system.push(format!("EventRecordID>{}", after));
+        }
+        if let Some(before) = self.before_record_id {
+            system.push(format!("EventRecordID<{}", before));
+        }
+
+        let mut clauses = Vec::new();
+        if !system.is_empty() {
+            clauses.push(format!("System[{}]", system.join(" and ")));
+        }
+        for data in &self.event_data {
+            if data.name.trim().is_empty() {
+                return Err("Nom de champ EventData vide.".to_string());
+            }
+            clauses.push(format!(
+                "EventData[Data[@Name={}]={}]",
+                xpath_literal(data.name.trim())?, xpath_literal(&data.value)?
+            ));
+        }
+
+        if clauses.is_empty() {
+            Ok("*".to_string())
+        } else {
+            Ok(format!("*[{}]", clauses.join(" and ")))
+        }
+    }
+}
+
+// --- Évaluation native (fichiers .evtx lus sans Get-WinEvent) ---
+// Mêmes règles que l'XPath compilé; les comparaisons de noms ignorent la casse comme le moteur Windows.
+
+pub struct EventMatcher {
+    event_ids: Vec<(u32, u32)>,
+    exclude_event_ids: Vec<(u32, u32)>,
+    levels: Vec<u8>,
+    providers: Vec<String>,
+    keyword_mask: Option<u64>,
+    user_sid: Option<String>,
+    start_ms: Option<i64>,
+    end_ms: Option<i64>,
+    after_record_id: Option<u64>,
+    before_record_id: Option<u64>,
+    event_data: Vec<(String, String)>,
+}
+
+impl EventQuery {
+    // now_ms: référence de within_ms (timediff)
+    pub fn matcher(&self, now_ms: i64) -> Result<EventMatcher, String> {
+        // Mêmes validations que pour Get-WinEvent
+        self.to_xpath()?;
+
+        let mut levels = self.levels.clone();
+        if levels.contains(&4) {
+            levels.push(0);
+        }
+        let keyword_mask = if self.keywords.is_empty() {
+            None
+        } else {
+            Some(self.keywords.iter().map(|k| keyword_mask(k)).collect::<Result<Vec<_>, _>>()?.into_iter().fold(0, |a, m| a | m))
+        };
+        let parse_bound = |value: &Option<String>| -> Option<i64> {
+            value.as_deref().filter(|s| !s.trim().is_empty()).and_then(|s| parse_iso8601_ms(s.trim()))
+        };
+        let mut start_ms = parse_bound(&self.start_time);
+        if let Some(within) = self.within_ms {
+            let since = now_ms - within as i64;
+            start_ms = Some(start_ms.map_or(since, |s| s.max(since)));
+        }
+
+        Ok(EventMatcher {
+            event_ids: self.event_ids.iter().map(event_id_range).collect(),
+            exclude_event_ids: self.exclude_event_ids.iter().map(event_id_range).collect(),
+            levels,
+            providers: self.providers.iter().map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect(),
+            keyword_mask,
+            user_sid: self.user_sid.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string),
+            start_ms,
+            end_ms: parse_bound(&self.end_time),
+            after_record_id: self.after_record_id,
+            before_record_id: self.before_record_id,
+            event_data: self.event_data.iter().map(|d| (d.name.trim().to_string(), d.value.clone())).collect(),
+        })
+    }
+}
+
+impl EventMatcher {
+    pub fn matches(&self, event: &ParsedEventXml) -> bool {
+        let in_ranges = |ranges: &[(u32, u32)]| ranges.iter().any(|&(from, to)| (from..=to).contains(&event.event_id));
+        if !self.event_ids.is_empty() && !in_ranges(&self.event_ids) {
+            return false;
+        }
+        if in_ranges(&self.exclude_event_ids) {
+            return false;
+        }
+        if !self.levels.is_empty() && !event.level.is_some_and(|l| self.levels.contains(&l)) {
+            return false;
+        }
+        if !self.providers.is_empty() && !self.providers.iter().any(|p| p.eq_ignore_ascii_case(&event.provider_name)) {
+            return false;
+        }
+        if let Some(mask) = self.keyword_mask {
+            let keywords = event.keywords.as_deref()
+                .and_then(|k| u64::from_str_radix(k.trim_start_matches("0x").trim_start_matches("0X"), 16).ok())
+                .unwrap_or(0);
+            if keywords & mask == 0 {
+                return false;
+            }
+        }
+        if let Some(sid) = &self.user_sid {
+            if !event.user_sid.as_deref().is_some_and(|s| s.eq_ignore_ascii_case(sid)) {
+                return false;
+            }
+        }
+        if self.start_ms.is_some() || self.end_ms.is_some() {
+            let Some(time) = event.time_created.as_deref().and_then(parse_iso8601_ms) else {
+                return false;
+            };
+            if self.start_ms.is_some_and(|start| time < start) || self.end_ms.is_some_and(|end| time > end) {
+                return false;
+            }
+        }
+        if self.after_record_id.is_some_and(|after| event.record_id <= after)
+            || self.before_record_id.is_some_and(|before| event.record_id >= before) {
+            return false;
+        }
+        self.event_data.iter().all(|(name, value)| {
+            event.event_data.iter().any(|field| field.name().eq_ignore_ascii_case(name) && field.value() == value)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(json: serde_json::Value) -> EventQuery {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn empty_query_selects_everything() {
+        assert_eq!(EventQuery::for_log("System").to_xpath().unwrap(), "*");
+    }
+
+    #[test]
+    fn compiles_id_lists_ranges_and_exclusions() {
+        let q = query(serde_json::json!({
+            "logs": ["Security"],
+            "event_ids": [4624, { "from": 4634, "to": 4647 }, { "from": 4672, "to": 4672 }],
+            "exclude_event_ids": [4640]
+        }));
+        assert_eq!(
+            q.to_xpath().unwrap(),
+            "*[System[(EventID=4624 or (EventID>=4634 and EventID<=4647) or EventID=4672) and not(EventID=4640)]]"
+        );
+
+        let single = query(serde_json::json!({ "logs": ["System"], "event_ids": [41] }));
+        assert_eq!(single.to_xpath().unwrap(), "*[System[EventID=41]]");
+    }
+
+    #[test]
+    fn rejects_inverted_range() {
+        let q = query(serde_json::json!({ "logs": ["System"], "event_ids": [{ "from": 10, "to": 5 }] }));
+        assert!(q.to_xpath().is_err());
+    }
+
+    #[test]
+    fn information_level_includes_log_always() {
+        let q = query(serde_json::json!({ "logs": ["Application"], "levels": [2, 4, 2] }));
+        assert_eq!(q.to_xpath().unwrap(), "*[System[(Level=2 or Level=4 or Level=0)]]");
+
+        let errors = query(serde_json::json!({ "logs": ["Application"], "levels": [1] }));
+        assert_eq!(errors.to_xpath().unwrap(), "*[System[Level=1]]");
+
+        let invalid = query(serde_json::json!({ "logs": ["Application"], "levels": [6] }));
+        assert!(invalid.to_xpath().is_err());
+    }
+
+    #[test]
+    fn compiles_providers_with_safe_quoting() {
+        let q = query(serde_json::json!({
+            "logs": ["Application"],
+            "providers": ["Application Error", " ", "Bob's Service"]
+        }));
+        assert_eq!(
+            q.to_xpath().unwrap(),
+            "*[System[Provider[@Name='Application Error' or @Name=\"Bob's Service\"]]]"
+        );
+
+        let unquotable = query(serde_json::json!({ "logs": ["Application"], "providers": ["a'b\"c"] }));
+        assert!(unquotable.to_xpath().is_err());
+    }
+
+    #[test]
+    fn combines_keyword_masks() {
+        let q = query(serde_json::json!({ "logs": ["Security"], "keywords": ["AuditFailure"] }));
+        assert_eq!(q.to_xpath().unwrap(), "*[System[band(Keywords,4503599627370496)]]");
+
+        let both = query(serde_json::json!({ "logs": ["Security"], "keywords": ["auditsuccess", "0x10000000000000"] }));
+        assert_eq!(both.to_xpath().unwrap(), format!("*[System[band(Keywords,{})]]", 0x0030_0000_0000_0000u64));
+
+        let unknown = query(serde_json::json!({ "logs": ["Security"], "keywords": ["Bogus"] }));
+        assert!(unknown.to_xpath().is_err());
+    }
+
+    #[test]
+    fn validates_user_sid() {
+        let q = query(serde_json::json!({ "logs": ["System"], "user_sid": "S-1-5-18" }));
+        assert_eq!(q.to_xpath().unwrap(), "*[System[Security[@UserID='S-1-5-18']]]");
+
+        let domain = query(serde_json::json!({ "logs": ["System"], "user_sid": "S-1-5-21-1004336348-1177238915-682003330-512" }));
+        assert!(domain.to_xpath().is_ok());
+
+        for bad in ["S-1-", "X-1-5-18", "S-1-5-18' or '1'='1"] {
+            let q = query(serde_json::json!({ "logs": ["System"], "user_sid": bad }));
+            assert!(q.to_xpath().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn normalizes_time_bounds_to_utc() {
+        let q = query(serde_json::json!({
+            "logs": ["System"],
+            "start_time": "2026-10-18T09:00:00+02:00",
+            "end_time": "2026-10-18T12:30:15.5Z"
+        }));
+        assert_eq!(
+            q.to_xpath().unwrap(),
+            "*[System[TimeCreated[@SystemTime>='2026-10-18T07:00:00.000Z' and @SystemTime<='2026-10-18T12:30:15.500Z']]]"
+        );
+
+        let within = query(serde_json::json!({ "logs": ["System"], "within_ms": 86400000 }));
+        assert_eq!(within.to_xpath().unwrap(), "*[System[TimeCreated[timediff(@SystemTime)<=86400000]]]");
+
+        let invalid = query(serde_json::json!({ "logs": ["System"], "start_time": "18/10/2026" }));
+        assert!(invalid.to_xpath().is_err());
+    }
+
+    #[test]
+    fn compiles_record_bounds() {
+        let mut q = EventQuery::for_log("System");
+        q.set_record_bounds(Some(100), Some(250));
+        assert_eq!(q.to_xpath().unwrap(), "*[System[EventRecordID>100 and EventRecordID<250]]");
+    }
+
+    #[test]
+    fn compiles_event_data_matches() {
+        let q = query(serde_json::json!({
+            "logs": ["Security"],
+            "event_ids": [4625],
+            "event_data": [
+                { "name": "TargetUserName", "value": "jdupont" },
+                { "name": "IpAddress", "value": "10.0.0.5" }
+            ]
+        }));
+        assert_eq!(
+            q.to_xpath().unwrap(),
+            "*[System[EventID=4625] and EventData[Data[@Name='TargetUserName']='jdupont'] and EventData[Data[@Name='IpAddress']='10.0.0.5']]"
+        );
+
+        let only_data = query(serde_json::json!({ "logs": ["Security"], "event_data": [{ "name": "TargetUserName", "value": "o'neil" }] }));
+        assert_eq!(only_data.to_xpath().unwrap(), "*[EventData[Data[@Name='TargetUserName']=\"o'neil\"]]");
+    }
+
+    #[test]
+    fn combines_all_system_conditions_in_order() {
+        let q = query(serde_json::json!({
+            "logs": ["Security"],
+            "event_ids": [4625],
+            "levels": [4],
+            "providers": ["Microsoft-Windows-Security-Auditing"],
+            "keywords": ["AuditFailure"],
+            "user_sid": "S-1-5-18",
+            "within_ms": 3600000
+        }));
+        assert_eq!(
+            q.to_xpath().unwrap(),
+            "*[System[EventID=4625 and (Level=4 or Level=0) and Provider[@Name='Microsoft-Windows-Security-Auditing'] \
+             and band(Keywords,4503599627370496) and Security[@UserID='S-1-5-18'] and TimeCreated[timediff(@SystemTime)<=3600000]]]"
+        );
+    }
+
+    #[test]
+    fn deduplicates_and_requires_logs() {
+        let q = query(serde_json::json!({ "logs": ["System", " system ", "Application", ""] }));
+        assert_eq!(q.log_names().unwrap(), vec!["System".to_string(), "Application".to_string()]);
+
+        let none = query(serde_json::json!({ "logs": [" "] }));
+        assert!(none.log_names().is_err());
+    }
+
+    #[test]
+    fn matcher_applies_the_same_rules_natively() {
+        let event = crate::modules::event_viewer::event_xml::parse_event_xml(
+            "<Event><System><Provider Name='Microsoft-Windows-Security-Auditing'/><EventID>4625</EventID><Level>0</Level>\
+             <Keywords>0x8010000000000000</Keywords><TimeCreated SystemTime='2026-10-18T07:12:03.1234567Z'/>\
+             <EventRecordID>500</EventRecordID><Security/></System>\
+             <EventData><Data Name='TargetUserName'>jdupont</Data></EventData></Event>"
+        ).unwrap();
+        let now = parse_iso8601_ms("2026-10-18T08:00:00Z").unwrap();
+        let matches = |json: serde_json::Value| query(json).matcher(now).unwrap().matches(&event);
+
+        assert!(matches(serde_json::json!({
+            "logs": ["Security"],
+            "event_ids": [{ "from": 4620, "to": 4630 }],
+            "levels": [4],
+            "providers": ["microsoft-windows-security-auditing"],
+            "keywords": ["AuditFailure"],
+            "start_time": "2026-10-18T09:00:00+02:00",
+            "within_ms": 3600000,
+            "after_record_id": 499,
+            "event_data": [{ "name": "TargetUserName", "value": "jdupont" }]
+        })));
+        assert!(!matches(serde_json::json!({ "exclude_event_ids": [4625] })));
+        assert!(!matches(serde_json::json!({ "levels": [2] })));
+        assert!(!matches(serde_json::json!({ "keywords": ["AuditSuccess"] })));
+        assert!(!matches(serde_json::json!({ "user_sid": "S-1-5-18" })));
+        assert!(!matches(serde_json::json!({ "within_ms": 60000 })));
+        assert!(!matches(serde_json::json!({ "end_time": "2026-10-18T07:00:00Z" })));
+        assert!(!matches(serde_json::json!({ "before_record_id": 500 })));
+        assert!(!matches(serde_json::json!({ "event_data": [{ "name": "TargetUserName", "value": "JDUPONT" }] })));
+        assert!(query(serde_json::json!({ "levels": [9] })).matcher(now).is_err());
+    }
+}
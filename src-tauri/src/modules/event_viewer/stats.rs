@@ -0,0 +1,292 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::result::Result;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::command;
+use tauri::AppHandle;
+
+use crate::modules::common::powershell::ps_quote;
+use crate::modules::users::{format_iso8601_ms, parse_iso8601_ms};
+
+use super::query::EventQuery;
+use super::stream_powershell_lines;
+
+// --- Statistiques d'un journal (niveaux, fournisseurs, histogramme temporel) ---
+// PowerShell émet une ligne compacte par événement; les lignes sont agrégées au fil de l'eau,
+// sans conserver les événements en mémoire.
+
+const DEFAULT_RANGE_MS: i64 = 7 * 86_400_000;
+// Nombre d'intervalles visé en mode automatique, et maximum accepté pour un intervalle imposé
+const TARGET_BUCKETS: i64 = 200;
+const MAX_BUCKETS: i64 = 5000;
+const TOP_PROVIDERS: usize = 20;
+
+const MINUTE_MS: i64 = 60_000;
+const HOUR_MS: i64 = 60 * MINUTE_MS;
+const DAY_MS: i64 = 24 * HOUR_MS;
+// Intervalles candidats du mode automatique
+const AUTO_BUCKETS_MS: [i64; 9] = [MINUTE_MS, 5 * MINUTE_MS, 15 * MINUTE_MS, 30 * MINUTE_MS, HOUR_MS, 3 * HOUR_MS, 6 * HOUR_MS, DAY_MS, 7 * DAY_MS];
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsBucket {
+    Auto,
+    Minute,
+    Hour,
+    Day,
+    Week,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct EventTimeRange {
+    start_time: Option<String>, // ISO 8601; par défaut: 7 jours avant la fin
+    end_time: Option<String>,   // ISO 8601; par défaut: maintenant
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct LevelCounts {
+    critical: usize,
+    error: usize,
+    warning: usize,
+    information: usize, // Inclut le niveau 0 (LogAlways) des journaux classiques
+    verbose: usize,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ProviderCount {
+    name: String,
+    count: usize,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TimeBucket {
+    start: String, // Début de l'intervalle (ISO 8601 UTC)
+    count: usize,
+    errors: usize,   // Critique + Erreur
+    warnings: usize,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EventStats {
+    log_name: String,
+    start_time: String,
+    end_time: String,
+    bucket_ms: i64,
+    total: usize,
+    levels: LevelCounts,
+    providers: Vec<ProviderCount>, // Les plus bruyants d'abord (TOP_PROVIDERS au plus)
+    distinct_providers: usize,
+    timeline: Vec<TimeBucket>,     // Continue: les intervalles vides sont inclus
+}
+
+fn bucket_size_ms(bucket: StatsBucket, range_ms: i64) -> Result<i64, String> {
+    let size = match bucket {
+        StatsBucket::Auto => {
+            return Ok(AUTO_BUCKETS_MS.iter().copied()
+                .find(|size| range_ms / size <= TARGET_BUCKETS)
+                .unwrap_or(7 * DAY_MS));
+        }
+        StatsBucket::Minute => MINUTE_MS,
+        StatsBucket::Hour => HOUR_MS,
+        StatsBucket::Day => DAY_MS,
+        StatsBucket::Week => 7 * DAY_MS,
+    };
+    if range_ms / size > MAX_BUCKETS {
+        return Err(format!("Intervalle trop fin pour la période: plus de {} intervalles", MAX_BUCKETS));
+    }
+    Ok(size)
+}
+
+// Agrégation incrémentale des lignes "niveau<TAB>horodatage ms<TAB>fournisseur"
+struct StatsAccumulator {
+    start_ms: i64,
+    bucket_ms: i64,
+    total: usize,
+    levels: LevelCounts,
+    providers: HashMap<String, usize>,
+    buckets: BTreeMap<i64, TimeBucket>,
+}
+
+impl StatsAccumulator {
+    fn new(start_ms: i64, end_ms: i64, bucket_ms: i64) -> Self {
+        // Intervalles alignés sur des frontières UTC rondes
+        let first = start_ms.div_euclid(bucket_ms);
+        let last = end_ms.div_euclid(bucket_ms);
+        let buckets = (first..=last).map(|index| {
+            (index, TimeBucket { start: format_iso8601_ms(index * bucket_ms), count: 0, errors: 0, warnings: 0 })
+        }).collect();
+        StatsAccumulator { start_ms, bucket_ms, total: 0, levels: LevelCounts::default(), providers: HashMap::new(), buckets }
+    }
+
+    fn add_line(&mut self, line: &str) {
+        let mut parts = line.trim_end_matches(['\r', '\n']).splitn(3, '\t');
+        let (Some(level), Some(time), Some(provider)) = (parts.next(), parts.next(), parts.next()) else { return };
+        let (Ok(level), Ok(time_ms)) = (level.trim().parse::<u8>(), time.trim().parse::<i64>()) else { return };
+
+        self.total += 1;
+        match level {
+            1 => self.levels.critical += 1,
+            2 => self.levels.error += 1,
+            3 => self.levels.warning += 1,
+            5 => self.levels.verbose += 1,
+            _ => self.levels.information += 1,
+        }
+        *self.providers.entry(provider.trim().to_string()).or_insert(0) += 1;
+
+        // Un événement légèrement hors période (horloge) est rattaché à l'intervalle extrême
+        let first = self.start_ms.div_euclid(self.bucket_ms);
+        let last = self.buckets.keys().next_back().copied().unwrap_or(first);
+        let index = time_ms.div_euclid(self.bucket_ms).clamp(first, last);
+        if let Some(bucket) = self.buckets.get_mut(&index) {
+            bucket.count += 1;
+            match level {
+                1 | 2 => bucket.errors += 1,
+                3 => bucket.warnings += 1,
+                _ => {}
+            }
+        }
+    }
+
+    fn finish(self, log_name: &str, end_ms: i64) -> EventStats {
+        let distinct_providers = self.providers.len();
+        let mut providers: Vec<ProviderCount> = self.providers.into_iter()
+            .map(|(name, count)| ProviderCount { name, count })
+            .collect();
+        providers.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+        providers.truncate(TOP_PROVIDERS);
+        EventStats {
+            log_name: log_name.to_string(),
+            start_time: format_iso8601_ms(self.start_ms),
+            end_time: format_iso8601_ms(end_ms),
+            bucket_ms: self.bucket_ms,
+            total: self.total,
+            levels: self.levels,
+            providers,
+            distinct_providers,
+            timeline: self.buckets.into_values().collect(),
+        }
+    }
+}
+
+fn resolve_range(range: &EventTimeRange, now_ms: i64) -> Result<(i64, i64), String> {
+    let parse = |value: &Option<String>| -> Result<Option<i64>, String> {
+        match value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            Some(v) => parse_iso8601_ms(v).map(Some).ok_or_else(|| format!("Date invalide (ISO 8601 attendu): {}", v)),
+            None => Ok(None),
+        }
+    };
+    let end_ms = parse(&range.end_time)?.unwrap_or(now_ms);
+    let start_ms = parse(&range.start_time)?.unwrap_or(end_ms - DEFAULT_RANGE_MS);
+    if start_ms >= end_ms {
+        return Err("La date de début doit précéder la date de fin".to_string());
+    }
+    Ok((start_ms, end_ms))
+}
+
+#[command]
+pub async fn get_event_stats(
+    app: AppHandle,
+    log_name: String,
+    range: Option<EventTimeRange>,
+    bucket: Option<StatsBucket>,
+) -> Result<EventStats, String> {
+    println!("Real: get_event_stats(log: '{}', range: {:?}, bucket: {:?}) called", log_name, range, bucket);
+    if log_name.trim().is_empty() {
+        return Err("Nom de journal invalide".to_string());
+    }
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Horloge système invalide: {}", e))?
+        .as_millis() as i64;
+    let (start_ms, end_ms) = resolve_range(&range.unwrap_or_default(), now_ms)?;
+    let bucket_ms = bucket_size_ms(bucket.unwrap_or(StatsBucket::Auto), end_ms - start_ms)?;
+
+    let query = EventQuery::for_log(log_name.trim())
+        .with_time_range(Some(format_iso8601_ms(start_ms)), Some(format_iso8601_ms(end_ms)));
+    let command = format!(
+        "try {{ Get-WinEvent -LogName {} -FilterXPath {} -ErrorAction Stop | ForEach-Object {{ \
+         \"$($_.Level)`t$(([DateTimeOffset]$_.TimeCreated).ToUnixTimeMilliseconds())`t$($_.ProviderName)\" }} }} \
+         catch {{ if ($_.FullyQualifiedErrorId -notlike 'NoMatchingEventsFound*') {{ Write-Error $_.Exception.Message; exit 1 }} }}",
+        ps_quote(log_name.trim()), ps_quote(&query.to_xpath()?)
+    );
+
+    let mut stats = StatsAccumulator::new(start_ms, end_ms, bucket_ms);
+    stream_powershell_lines(&app, &command, |line| {
+        stats.add_line(line);
+        Ok(())
+    }).await?;
+    Ok(stats.finish(log_name.trim(), end_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: i64 = 1_792_281_600_000; // 2026-10-18T00:00:00Z
+
+    #[test]
+    fn picks_automatic_bucket_sizes() {
+        assert_eq!(bucket_size_ms(StatsBucket::Auto, HOUR_MS).unwrap(), MINUTE_MS);
+        assert_eq!(bucket_size_ms(StatsBucket::Auto, DAY_MS).unwrap(), 15 * MINUTE_MS);
+        assert_eq!(bucket_size_ms(StatsBucket::Auto, 7 * DAY_MS).unwrap(), HOUR_MS);
+        assert_eq!(bucket_size_ms(StatsBucket::Auto, 90 * DAY_MS).unwrap(), DAY_MS);
+        assert_eq!(bucket_size_ms(StatsBucket::Auto, 10 * 365 * DAY_MS).unwrap(), 7 * DAY_MS);
+        assert_eq!(bucket_size_ms(StatsBucket::Hour, 30 * DAY_MS).unwrap(), HOUR_MS);
+        assert!(bucket_size_ms(StatsBucket::Minute, 30 * DAY_MS).is_err());
+    }
+
+    #[test]
+    fn aggregates_levels_providers_and_timeline() {
+        let mut stats = StatsAccumulator::new(START, START + 3 * HOUR_MS, HOUR_MS);
+        for line in [
+            format!("2\t{}\tdisk", START + 10 * MINUTE_MS),
+            format!("4\t{}\tService Control Manager", START + 20 * MINUTE_MS),
+            format!("0\t{}\tEventLog", START + 70 * MINUTE_MS),
+            format!("3\t{}\tService Control Manager", START + 130 * MINUTE_MS),
+            format!("1\t{}\tMicrosoft-Windows-Kernel-Power", START + 131 * MINUTE_MS),
+            format!("4\t{}\tService Control Manager\r", START + 3 * HOUR_MS),
+            "ligne invalide".to_string(),
+            format!("x\t{}\tdisk", START),
+        ] {
+            stats.add_line(&line);
+        }
+        let stats = stats.finish("System", START + 3 * HOUR_MS);
+
+        assert_eq!(stats.total, 6);
+        assert_eq!(stats.levels, LevelCounts { critical: 1, error: 1, warning: 1, information: 3, verbose: 0 });
+        assert_eq!(stats.distinct_providers, 4);
+        assert_eq!(stats.providers[0], ProviderCount { name: "Service Control Manager".to_string(), count: 3 });
+        assert_eq!(stats.start_time, "2026-10-18T00:00:00Z");
+
+        // 00h, 01h, 02h et l'intervalle contenant la borne de fin (03h)
+        assert_eq!(stats.timeline.len(), 4);
+        assert_eq!(stats.timeline[0], TimeBucket { start: "2026-10-18T00:00:00Z".to_string(), count: 2, errors: 1, warnings: 0 });
+        assert_eq!(stats.timeline[1].count, 1);
+        assert_eq!(stats.timeline[2], TimeBucket { start: "2026-10-18T02:00:00Z".to_string(), count: 2, errors: 1, warnings: 1 });
+        assert_eq!(stats.timeline[3].count, 1);
+    }
+
+    #[test]
+    fn clamps_out_of_range_events_and_limits_providers() {
+        let mut stats = StatsAccumulator::new(START, START + HOUR_MS, HOUR_MS);
+        stats.add_line(&format!("4\t{}\tavant", START - DAY_MS));
+        for i in 0..30 {
+            stats.add_line(&format!("4\t{}\tfournisseur{:02}", START + HOUR_MS + DAY_MS, i));
+        }
+        let stats = stats.finish("Application", START + HOUR_MS);
+        assert_eq!(stats.timeline[0].count, 1);
+        assert_eq!(stats.timeline[1].count, 30);
+        assert_eq!(stats.distinct_providers, 31);
+        assert_eq!(stats.providers.len(), TOP_PROVIDERS);
+    }
+
+    #[test]
+    fn resolves_default_and_explicit_ranges() {
+        let now = START + DAY_MS;
+        assert_eq!(resolve_range(&EventTimeRange::default(), now).unwrap(), (now - DEFAULT_RANGE_MS, now));
+        let explicit = EventTimeRange { start_time: Some("2026-10-18T02:00:00+02:00".to_string()), end_time: None };
+        assert_eq!(resolve_range(&explicit, now).unwrap(), (START, now));
+        let inverted = EventTimeRange { start_time: Some("2026-10-20T00:00:00Z".to_string()), end_time: Some("2026-10-19T00:00:00Z".to_string()) };
+        assert!(resolve_range(&inverted, now).is_err());
+    }
+}
@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::result::Result;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::command;
+use tauri::{AppHandle, Emitter, Webview};
+use tauri_plugin_shell::ShellExt;
+
+use crate::modules::common::powershell::ps_quote;
+
+use super::query::EventQuery;
+use super::{entry_from_ps, EventLogEntry, PsWinEvent, EVENT_SELECT_SCRIPT};
+
+// --- Suivi en direct d'un journal (live tail) ---
+// Chaque abonnement interroge le journal à intervalle régulier et ne lit que les événements dont
+// le RecordId dépasse le dernier reçu. Les nouveaux événements sont émis vers le frontend.
+// Un abonnement appartient à la page (webview) qui l'a créé et s'arrête quand elle est rechargée ou fermée.
+
+// Événement Tauri portant les nouveaux enregistrements d'un abonnement
+pub const EVENTS_EMIT_NAME: &str = "event-log-entries";
+// Événement Tauri signalant une erreur de lecture (l'abonnement continue)
+pub const EVENTS_ERROR_EMIT_NAME: &str = "event-log-subscription-error";
+// Événement Tauri signalant que le journal a été effacé: le signet repart de zéro
+pub const EVENTS_RESET_EMIT_NAME: &str = "event-log-subscription-reset";
+
+const DEFAULT_POLL_INTERVAL_MS: u64 = 2000;
+const MIN_POLL_INTERVAL_MS: u64 = 500;
+// Nombre maximal d'événements lus par interrogation; le reste est lu à l'interrogation suivante
+const MAX_EVENTS_PER_POLL: u32 = 500;
+
+static NEXT_SUBSCRIPTION_ID: AtomicU64 = AtomicU64::new(1);
+// Abonnements actifs et libellé de la webview qui les a créés
+static ACTIVE_SUBSCRIPTIONS: Mutex<BTreeMap<u64, String>> = Mutex::new(BTreeMap::new());
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EventSubscriptionBatch {
+    subscription_id: u64,
+    log_name: String,
+    events: Vec<EventLogEntry>, // Du plus ancien au plus récent
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EventSubscriptionError {
+    subscription_id: u64,
+    log_name: String,
+    error: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EventSubscriptionReset {
+    subscription_id: u64,
+    log_name: String,
+    previous_record_id: u64,
+    latest_record_id: u64,
+}
+
+// Résultat d'une interrogation: nouveaux événements et dernier RecordId du journal, lus par le même script
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsSubscriptionPoll {
+    latest_record_id: Option<u64>, // Absent si le journal est vide
+    #[serde(default)]
+    events: Vec<PsWinEvent>,
+}
+
+fn is_active(subscription_id: u64) -> bool {
+    ACTIVE_SUBSCRIPTIONS.lock().map(|s| s.contains_key(&subscription_id)).unwrap_or(false)
+}
+
+fn remove_webview_subscriptions(subscriptions: &mut BTreeMap<u64, String>, webview_label: &str) -> usize {
+    let before = subscriptions.len();
+    subscriptions.retain(|_, label| label != webview_label);
+    before - subscriptions.len()
+}
+
+// Appelé au rechargement ou à la fermeture d'une webview: ses abonnements n'ont plus de destinataire
+pub fn stop_webview_subscriptions(webview_label: &str) {
+    if let Ok(mut subscriptions) = ACTIVE_SUBSCRIPTIONS.lock() {
+        let stopped = remove_webview_subscriptions(&mut subscriptions, webview_label);
+        if stopped > 0 {
+            println!("{} abonnement(s) de la webview '{}' arrêté(s)", stopped, webview_label);
+        }
+    }
+}
+
+async fn run_powershell(app: &AppHandle, command: &str) -> Result<String, String> {
+    let output = app.shell()
+        .command("powershell")
+        .args(["-Command", command])
+        .output()
+        .await
+        .map_err(|e| format!("Erreur lors de l'exécution de Get-WinEvent: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// Sortie vide: journal vide (RecordId 0). Toute autre sortie non numérique est une erreur, pour ne pas
+// prendre un avertissement pour un journal effacé et relire tout le journal.
+fn parse_record_id(output: &str) -> Result<u64, String> {
+    let output = output.trim();
+    if output.is_empty() {
+        return Ok(0);
+    }
+    output.parse::<u64>().map_err(|_| format!("RecordId inattendu: '{}'", output))
+}
+
+// RecordId du dernier événement du journal (0 si le journal est vide)
+async fn latest_record_id(app: &AppHandle, log_name: &str) -> Result<u64, String> {
+    let command = format!(
+        "Get-WinEvent -ListLog {0} -ErrorAction Stop | Out-Null; (Get-WinEvent -LogName {0} -MaxEvents 1 -ErrorAction SilentlyContinue).RecordId",
+        ps_quote(log_name)
+    );
+    let output = run_powershell(app, &command).await?;
+    parse_record_id(&output)
+}
+
+// Après un effacement du journal, les RecordId repartent de 1: un dernier RecordId inférieur
+// au signet rendrait l'abonnement muet jusqu'à ce que le journal le rattrape
+fn is_log_reset(bookmark: u64, latest_record_id: u64) -> bool {
+    latest_record_id < bookmark
+}
+
+// Un seul processus PowerShell par interrogation: nouveaux événements après le signet, en ordre
+// chronologique (pas de perte au-delà de MAX_EVENTS_PER_POLL), et dernier RecordId du journal
+fn poll_script(log_name: &str, filter: &EventQuery, after_record_id: u64) -> Result<String, String> {
+    let mut query = filter.clone();
+    query.set_record_bounds(Some(after_record_id), None);
+    Ok(format!(
+        "$latest = (Get-WinEvent -LogName {0} -MaxEvents 1 -ErrorAction SilentlyContinue).RecordId; \
+         try {{ $events = @(Get-WinEvent -LogName {0} -FilterXPath {1} -MaxEvents {2} -Oldest -ErrorAction Stop | {3}) }} \
+         catch {{ if ($_.FullyQualifiedErrorId -like 'NoMatchingEventsFound*') {{ $events = @() }} else {{ Write-Error $_.Exception.Message; exit 1 }} }}; \
+         [PSCustomObject]@{{ LatestRecordId = $latest; Events = $events }} | ConvertTo-Json -Depth 4 -Compress",
+        ps_quote(log_name), ps_quote(&query.to_xpath()?), MAX_EVENTS_PER_POLL, EVENT_SELECT_SCRIPT
+    ))
+}
+
+async fn poll_new_events(app: &AppHandle, log_name: &str, filter: &EventQuery, after_record_id: u64) -> Result<PsSubscriptionPoll, String> {
+    let output = run_powershell(app, &poll_script(log_name, filter, after_record_id)?).await?;
+    serde_json::from_str(output.trim()).map_err(|e| format!("Erreur parsing JSON abonnement: {}", e))
+}
+
+#[command]
+pub async fn subscribe_events(
+    app: AppHandle,
+    webview: Webview,
+    log_name: String,
+    filter: Option<EventQuery>, // Les journaux du filtre sont ignorés au profit de log_name
+    interval_ms: Option<u64>,
+) -> Result<u64, String> {
+    println!("Real: subscribe_events(log: {}, filter: {:?}) called", log_name, filter);
+    if log_name.trim().is_empty() {
+        return Err("Nom de journal invalide".to_string());
+    }
+    let filter = filter.unwrap_or_default().with_log(&log_name);
+    // Valider le filtre avant de démarrer la boucle
+    filter.to_xpath()?;
+
+    // Les événements déjà présents ne sont pas renvoyés: le signet part du dernier enregistrement
+    let mut bookmark = latest_record_id(&app, &log_name).await?;
+    let interval = Duration::from_millis(interval_ms.unwrap_or(DEFAULT_POLL_INTERVAL_MS).max(MIN_POLL_INTERVAL_MS));
+
+    let subscription_id = NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::SeqCst);
+    ACTIVE_SUBSCRIPTIONS.lock().map_err(|e| e.to_string())?.insert(subscription_id, webview.label().to_string());
+
+    tauri::async_runtime::spawn(async move {
+        while is_active(subscription_id) {
+            tokio::time::sleep(interval).await;
+            if !is_active(subscription_id) {
+                break;
+            }
+            match poll_new_events(&app, &log_name, &filter, bookmark).await {
+                Ok(poll) if !poll.events.is_empty() => {
+                    let events: Vec<EventLogEntry> = poll.events.into_iter().map(entry_from_ps).collect();
+                    bookmark = events.iter().map(|e| e.record_id).max().unwrap_or(bookmark);
+                    let batch = EventSubscriptionBatch { subscription_id, log_name: log_name.clone(), events };
+                    if let Err(e) = app.emit(EVENTS_EMIT_NAME, batch) {
+                        println!("Erreur émission des événements (abonnement {}): {}", subscription_id, e);
+                    }
+                }
+                // Rien de nouveau après le signet: le journal a peut-être été effacé
+                Ok(poll) => {
+                    let latest = poll.latest_record_id.unwrap_or(0);
+                    if is_log_reset(bookmark, latest) {
+                        let reset = EventSubscriptionReset {
+                            subscription_id,
+                            log_name: log_name.clone(),
+                            previous_record_id: bookmark,
+                            latest_record_id: latest,
+                        };
+                        // Les événements écrits depuis l'effacement sont lus à l'interrogation suivante
+                        bookmark = 0;
+                        let _ = app.emit(EVENTS_RESET_EMIT_NAME, reset);
+                    }
+                }
+                Err(error) => {
+                    let payload = EventSubscriptionError { subscription_id, log_name: log_name.clone(), error };
+                    let _ = app.emit(EVENTS_ERROR_EMIT_NAME, payload);
+                }
+            }
+        }
+        println!("Abonnement {} au journal '{}' terminé", subscription_id, log_name);
+    });
+
+    Ok(subscription_id)
+}
+
+#[command]
+pub async fn unsubscribe_events(subscription_id: u64) -> Result<(), String> {
+    println!("Real: unsubscribe_events({}) called", subscription_id);
+    let removed = ACTIVE_SUBSCRIPTIONS.lock().map_err(|e| e.to_string())?.remove(&subscription_id);
+    if removed.is_none() {
+        return Err(format!("Abonnement {} introuvable", subscription_id));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_latest_record_id() {
+        assert_eq!(parse_record_id("48210\r\n"), Ok(48_210));
+        assert_eq!(parse_record_id("  \r\n"), Ok(0));
+        // Un avertissement sur la sortie standard ne doit pas passer pour un journal vide
+        assert!(parse_record_id("AVERTISSEMENT : module obsolète\r\n48210").is_err());
+    }
+
+    #[test]
+    fn detects_cleared_log() {
+        // Journal effacé: seuls quelques événements (dont 104/1102) ont été écrits depuis
+        assert!(is_log_reset(48_210, 3));
+        // Journal vidé sans nouvel événement
+        assert!(is_log_reset(48_210, 0));
+        // Aucun nouvel événement correspondant au filtre, journal intact
+        assert!(!is_log_reset(48_210, 48_210));
+        assert!(!is_log_reset(48_210, 48_500));
+        // Abonnement démarré sur un journal vide
+        assert!(!is_log_reset(0, 0));
+    }
+
+    #[test]
+    fn parses_poll_result() {
+        let poll: PsSubscriptionPoll = serde_json::from_str(r#"{"LatestRecordId":null,"Events":[]}"#).unwrap();
+        assert_eq!(poll.latest_record_id, None);
+        assert!(poll.events.is_empty());
+
+        let poll: PsSubscriptionPoll = serde_json::from_str(
+            r#"{"LatestRecordId":48212,"Events":[{"RecordId":48212,"Id":7036,"LevelDisplayName":"Information","ProviderName":"Service Control Manager","TimeCreated":"2024-06-01T10:00:00.0000000Z","Message":"Le service a démarré."}]}"#
+        ).unwrap();
+        assert_eq!(poll.latest_record_id, Some(48_212));
+        assert_eq!(poll.events.len(), 1);
+    }
+
+    #[test]
+    fn stops_only_the_reloaded_webview_subscriptions() {
+        let mut subscriptions = BTreeMap::from([
+            (1, "main".to_string()),
+            (2, "events".to_string()),
+            (3, "main".to_string()),
+        ]);
+        assert_eq!(remove_webview_subscriptions(&mut subscriptions, "main"), 2);
+        assert_eq!(subscriptions.keys().copied().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(remove_webview_subscriptions(&mut subscriptions, "main"), 0);
+    }
+}
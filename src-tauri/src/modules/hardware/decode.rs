@@ -0,0 +1,209 @@
+// --- Décodage des valeurs brutes WMI (SMBIOS, EDID, stockage) ---
+
+// Win32_SystemEnclosure.ChassisTypes (table SMBIOS 7.4.1)
+pub fn chassis_type_name(code: u16) -> String {
+    let name = match code {
+        1 => "Autre",
+        2 => "Inconnu",
+        3 => "Bureau",
+        4 => "Bureau compact",
+        5 => "Pizza Box",
+        6 => "Mini-tour",
+        7 => "Tour",
+        8 => "Portable",
+        9 => "Ordinateur portable",
+        10 => "Notebook",
+        11 => "Ordinateur de poche",
+        12 => "Station d'accueil",
+        13 => "Tout-en-un",
+        14 => "Sub-notebook",
+        15 => "Encombrement réduit",
+        16 => "Lunch Box",
+        17 => "Châssis serveur",
+        18 => "Châssis d'extension",
+        19 => "Sous-châssis",
+        20 => "Châssis d'extension de bus",
+        21 => "Châssis périphérique",
+        22 => "Châssis RAID",
+        23 => "Châssis rack",
+        24 => "PC scellé",
+        25 => "Châssis multi-système",
+        26 => "Compact PCI",
+        27 => "Advanced TCA",
+        28 => "Lame",
+        29 => "Boîtier de lames",
+        30 => "Tablette",
+        31 => "Convertible",
+        32 => "Détachable",
+        33 => "Passerelle IoT",
+        34 => "PC embarqué",
+        35 => "Mini PC",
+        36 => "Stick PC",
+        _ => return format!("Type {}", code),
+    };
+    name.to_string()
+}
+
+// Win32_PhysicalMemory.SMBIOSMemoryType (table SMBIOS 7.18.2)
+pub fn memory_type_name(code: u32) -> Option<&'static str> {
+    match code {
+        20 => Some("DDR"),
+        21 => Some("DDR2"),
+        24 => Some("DDR3"),
+        26 => Some("DDR4"),
+        27 => Some("LPDDR"),
+        28 => Some("LPDDR2"),
+        29 => Some("LPDDR3"),
+        30 => Some("LPDDR4"),
+        34 => Some("DDR5"),
+        35 => Some("LPDDR5"),
+        _ => None,
+    }
+}
+
+// MSFT_PhysicalDisk.MediaType
+pub fn disk_media_type_name(code: u16) -> &'static str {
+    match code {
+        3 => "HDD",
+        4 => "SSD",
+        5 => "SCM",
+        _ => "Non spécifié",
+    }
+}
+
+// MSFT_PhysicalDisk.BusType
+pub fn disk_bus_type_name(code: u16) -> &'static str {
+    match code {
+        1 => "SCSI",
+        2 => "ATAPI",
+        3 => "ATA",
+        4 => "IEEE 1394",
+        5 => "SSA",
+        6 => "Fibre Channel",
+        7 => "USB",
+        8 => "RAID",
+        9 => "iSCSI",
+        10 => "SAS",
+        11 => "SATA",
+        12 => "SD",
+        13 => "MMC",
+        14 => "Virtuel",
+        15 => "Virtuel (fichier)",
+        16 => "Espaces de stockage",
+        17 => "NVMe",
+        18 => "SCM",
+        19 => "UFS",
+        _ => "Inconnu",
+    }
+}
+
+// MSFT_PhysicalDisk.HealthStatus
+pub fn disk_health_status_name(code: u16) -> &'static str {
+    match code {
+        0 => "Sain",
+        1 => "Avertissement",
+        2 => "Défaillant",
+        _ => "Inconnu",
+    }
+}
+
+// Les chaînes WmiMonitorID sont des tableaux de caractères terminés par des zéros
+pub fn wmi_char_array(chars: &[u16]) -> Option<String> {
+    let end = chars.iter().position(|&c| c == 0).unwrap_or(chars.len());
+    let value = String::from_utf16_lossy(&chars[..end]).trim().to_string();
+    if value.is_empty() { None } else { Some(value) }
+}
+
+// Identifiant PNP du fabricant (3 lettres codées dans l'EDID) vers un nom lisible
+pub fn pnp_vendor_name(code: &str) -> Option<&'static str> {
+    match code.to_ascii_uppercase().as_str() {
+        "ACR" => Some("Acer"),
+        "AOC" => Some("AOC"),
+        "APP" => Some("Apple"),
+        "AUO" => Some("AU Optronics"),
+        "AUS" => Some("ASUS"),
+        "BNQ" => Some("BenQ"),
+        "BOE" => Some("BOE"),
+        "CMN" => Some("Innolux"),
+        "DEL" => Some("Dell"),
+        "ENC" => Some("EIZO"),
+        "FUS" => Some("Fujitsu"),
+        "GSM" => Some("LG Electronics"),
+        "HPN" | "HWP" => Some("HP"),
+        "IVM" => Some("iiyama"),
+        "LEN" => Some("Lenovo"),
+        "LGD" => Some("LG Display"),
+        "MSI" => Some("MSI"),
+        "NEC" => Some("NEC"),
+        "PHL" => Some("Philips"),
+        "SAM" => Some("Samsung"),
+        "SDC" => Some("Samsung Display"),
+        "SHP" => Some("Sharp"),
+        "SNY" => Some("Sony"),
+        "VSC" => Some("ViewSonic"),
+        _ => None,
+    }
+}
+
+// Date CIM/DMTF renvoyée par Get-WmiObject ("20230515000000.000000+000") vers AAAA-MM-JJ
+pub fn cim_date(value: &str) -> Option<String> {
+    let digits = value.trim().get(..8)?;
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("{}-{}-{}", &digits[..4], &digits[4..6], &digits[6..8]))
+}
+
+// Win32_Tpm.SpecVersion ("2.0, 0, 1.59"): le premier élément est la version de la spécification
+pub fn tpm_spec_version(value: &str) -> Option<String> {
+    value.split(',').next().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+// Usure de la batterie: capacité de pleine charge rapportée à la capacité nominale
+pub fn battery_health_percent(design_capacity: Option<u32>, full_charge_capacity: Option<u32>) -> Option<f64> {
+    match (design_capacity, full_charge_capacity) {
+        (Some(design), Some(full)) if design > 0 => Some((full as f64 * 1000.0 / design as f64).round() / 10.0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_smbios_codes() {
+        assert_eq!(chassis_type_name(10), "Notebook");
+        assert_eq!(chassis_type_name(3), "Bureau");
+        assert_eq!(chassis_type_name(99), "Type 99");
+        assert_eq!(memory_type_name(26), Some("DDR4"));
+        assert_eq!(memory_type_name(0), None);
+    }
+
+    #[test]
+    fn decodes_storage_codes() {
+        assert_eq!(disk_media_type_name(4), "SSD");
+        assert_eq!(disk_media_type_name(0), "Non spécifié");
+        assert_eq!(disk_bus_type_name(17), "NVMe");
+        assert_eq!(disk_bus_type_name(11), "SATA");
+        assert_eq!(disk_health_status_name(1), "Avertissement");
+    }
+
+    #[test]
+    fn decodes_monitor_strings() {
+        let name: Vec<u16> = "DELL U2720Q".encode_utf16().chain([0, 0, 0]).collect();
+        assert_eq!(wmi_char_array(&name).as_deref(), Some("DELL U2720Q"));
+        assert_eq!(wmi_char_array(&[0, 0]), None);
+        assert_eq!(pnp_vendor_name("del"), Some("Dell"));
+        assert_eq!(pnp_vendor_name("XYZ"), None);
+    }
+
+    #[test]
+    fn parses_firmware_values() {
+        assert_eq!(cim_date("20230515000000.000000+000").as_deref(), Some("2023-05-15"));
+        assert_eq!(cim_date("N/A"), None);
+        assert_eq!(tpm_spec_version("2.0, 0, 1.59").as_deref(), Some("2.0"));
+        assert_eq!(battery_health_percent(Some(57000), Some(48450)), Some(85.0));
+        assert_eq!(battery_health_percent(Some(0), Some(48450)), None);
+    }
+}
@@ -6,6 +6,13 @@ use tauri_plugin_shell::ShellExt;
 use serde_json::Value; // Besoin pour get_wmi_json
 use tokio; // Besoin pour join!
 
+pub mod decode;
+
+use decode::{
+    battery_health_percent, chassis_type_name, cim_date, disk_bus_type_name, disk_health_status_name,
+    disk_media_type_name, memory_type_name, pnp_vendor_name, tpm_spec_version, wmi_char_array,
+};
+
 // --- Structs de Parsing WMI --- 
 #[derive(Deserialize, Serialize, Debug, Clone)] // Ajouter Serialize
 #[serde(rename_all = "PascalCase")]
@@ -20,6 +27,15 @@ struct PsProcessor {
 #[serde(rename_all = "PascalCase")]
 struct PsPhysicalMemory {
     capacity: Option<u64>,
+    device_locator: Option<String>, // Emplacement (ex: "DIMM A1")
+    bank_label: Option<String>,
+    speed: Option<u32>,
+    configured_clock_speed: Option<u32>,
+    manufacturer: Option<String>,
+    part_number: Option<String>,
+    serial_number: Option<String>,
+    #[serde(rename = "SMBIOSMemoryType")]
+    smbios_memory_type: Option<u32>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)] // Ajouter Serialize
@@ -29,6 +45,81 @@ struct PsBaseBoard {
     product: Option<String>,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct PsBios {
+    manufacturer: Option<String>,
+    #[serde(rename = "SMBIOSBIOSVersion")]
+    smbios_bios_version: Option<String>,
+    release_date: Option<String>, // Date CIM (DMTF)
+    serial_number: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct PsSystemEnclosure {
+    chassis_types: Option<Vec<u16>>,
+}
+
+// root\Microsoft\Windows\Storage
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct PsPhysicalDisk {
+    friendly_name: Option<String>,
+    serial_number: Option<String>,
+    size: Option<u64>,
+    media_type: Option<u16>,
+    bus_type: Option<u16>,
+    health_status: Option<u16>,
+}
+
+// root\wmi: les trois classes batterie sont reliées par InstanceName
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct PsBatteryStaticData {
+    instance_name: String,
+    device_name: Option<String>,
+    manufacture_name: Option<String>,
+    designed_capacity: Option<u32>, // mWh
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct PsBatteryFullChargedCapacity {
+    instance_name: String,
+    full_charged_capacity: Option<u32>, // mWh
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct PsBatteryStatus {
+    instance_name: String,
+    remaining_capacity: Option<u32>, // mWh
+    power_online: Option<bool>,
+}
+
+// root\wmi WmiMonitorID: identification lue dans l'EDID
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct PsMonitorId {
+    manufacturer_name: Option<Vec<u16>>,
+    product_code_id: Option<Vec<u16>>,
+    serial_number_id: Option<Vec<u16>>,
+    user_friendly_name: Option<Vec<u16>>,
+    year_of_manufacture: Option<u16>,
+}
+
+// root\cimv2\Security\MicrosoftTpm
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct PsTpm {
+    spec_version: Option<String>,
+    manufacturer_id_txt: Option<String>,
+    manufacturer_version: Option<String>,
+    is_enabled_initial_value: Option<bool>,
+    is_activated_initial_value: Option<bool>,
+}
+
 // Ajouter struct pour WMI VideoController
 #[derive(Deserialize, Serialize, Debug, Clone)]
 struct PsVideoController {
@@ -48,6 +139,67 @@ pub struct GpuInfo {
     driver_version: String, // Driver version (sans Option)
 }
 
+#[derive(Serialize, Debug, Clone)]
+pub struct BiosInfo {
+    manufacturer: Option<String>,
+    version: Option<String>,
+    release_date: Option<String>, // AAAA-MM-JJ
+    serial_number: Option<String>, // Numéro de série de la machine
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct MemoryModuleInfo {
+    slot: Option<String>,
+    bank: Option<String>,
+    capacity_gb: Option<f64>,
+    memory_type: Option<String>,
+    speed_mhz: Option<u32>,            // Vitesse nominale du module
+    configured_speed_mhz: Option<u32>, // Vitesse effectivement configurée
+    manufacturer: Option<String>,
+    part_number: Option<String>,
+    serial_number: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PhysicalDiskInfo {
+    model: String,
+    serial_number: Option<String>,
+    size_gb: Option<f64>,
+    media_type: String, // HDD, SSD, SCM, Non spécifié
+    bus_type: String,   // NVMe, SATA, USB...
+    health_status: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BatteryInfo {
+    name: Option<String>,
+    manufacturer: Option<String>,
+    design_capacity_mwh: Option<u32>,
+    full_charge_capacity_mwh: Option<u32>,
+    health_percent: Option<f64>, // Pleine charge / capacité nominale
+    charge_percent: Option<f64>,
+    on_ac_power: Option<bool>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct MonitorInfo {
+    manufacturer_code: Option<String>, // Identifiant PNP (ex: DEL)
+    manufacturer: Option<String>,
+    model: Option<String>,
+    product_code: Option<String>,
+    serial_number: Option<String>,
+    year_of_manufacture: Option<u16>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TpmInfo {
+    spec_version: Option<String>, // 1.2, 2.0
+    manufacturer: Option<String>,
+    manufacturer_version: Option<String>,
+    enabled: Option<bool>,
+    activated: Option<bool>,
+}
+
 // --- Structure finale APLATIE --- 
 #[derive(Serialize, Debug, Clone, Default)]
 pub struct HardwareInfo {
@@ -60,6 +212,13 @@ pub struct HardwareInfo {
     motherboard_manufacturer: Option<String>,
     motherboard_product: Option<String>,
     gpus: Vec<GpuInfo>,    // Changé pour utiliser GpuInfo au lieu de PsVideoController
+    bios: Option<BiosInfo>,
+    chassis_types: Vec<String>,
+    memory_modules: Vec<MemoryModuleInfo>,
+    physical_disks: Vec<PhysicalDiskInfo>,
+    batteries: Vec<BatteryInfo>,
+    monitors: Vec<MonitorInfo>,
+    tpm: Option<TpmInfo>, // Absent, ou illisible sans privilèges admin
 }
 
 // --- Helpers ---
@@ -69,10 +228,20 @@ async fn get_wmi_json<T: serde::de::DeserializeOwned>(
     app: &AppHandle, 
     class: &str, 
     properties: &str
+) -> Result<Vec<T>, String> {
+    get_wmi_json_ns(app, "root\\cimv2", class, properties).await
+}
+
+// Variante pour les classes hors de root\cimv2 (root\wmi, stockage, TPM)
+async fn get_wmi_json_ns<T: serde::de::DeserializeOwned>(
+    app: &AppHandle,
+    namespace: &str,
+    class: &str,
+    properties: &str
 ) -> Result<Vec<T>, String> {
     let command = format!(
-        "Get-WmiObject -Class {} -ErrorAction SilentlyContinue | Select-Object {} | ConvertTo-Json -Compress",
-        class, properties
+        "Get-WmiObject -Namespace '{}' -Class {} -ErrorAction SilentlyContinue | Select-Object {} | ConvertTo-Json -Compress",
+        namespace, class, properties
     );
     let output = app.shell().command("powershell").args(&["-Command", &command]).output().await
         .map_err(|e| format!("Erreur lancement WMI pour {}: {}", class, e))?;
@@ -108,15 +277,34 @@ pub async fn get_hardware_info(app: AppHandle) -> Result<HardwareInfo, String> {
     let (cpu_res, board_res, mem_res, gpu_res) = tokio::join!(
         get_wmi_json::<PsProcessor>(&app, "Win32_Processor", "Name, NumberOfCores, NumberOfLogicalProcessors, MaxClockSpeed"),
         get_wmi_json::<PsBaseBoard>(&app, "Win32_BaseBoard", "Manufacturer, Product"),
-        get_wmi_json::<PsPhysicalMemory>(&app, "Win32_PhysicalMemory", "Capacity"),
+        get_wmi_json::<PsPhysicalMemory>(&app, "Win32_PhysicalMemory",
+            "Capacity, DeviceLocator, BankLabel, Speed, ConfiguredClockSpeed, Manufacturer, PartNumber, SerialNumber, SMBIOSMemoryType"),
         get_gpu_info(&app) // Nouvelle fonction pour GPU avec une commande PowerShell plus complète
     );
+    let (bios_res, enclosure_res, disk_res, battery_res, monitor_res, tpm_res) = tokio::join!(
+        get_wmi_json::<PsBios>(&app, "Win32_BIOS", "Manufacturer, SMBIOSBIOSVersion, ReleaseDate, SerialNumber"),
+        get_wmi_json::<PsSystemEnclosure>(&app, "Win32_SystemEnclosure", "ChassisTypes"),
+        get_wmi_json_ns::<PsPhysicalDisk>(&app, "root\\Microsoft\\Windows\\Storage", "MSFT_PhysicalDisk",
+            "FriendlyName, SerialNumber, Size, MediaType, BusType, HealthStatus"),
+        get_battery_info(&app),
+        get_wmi_json_ns::<PsMonitorId>(&app, "root\\wmi", "WmiMonitorID",
+            "ManufacturerName, ProductCodeID, SerialNumberID, UserFriendlyName, YearOfManufacture"),
+        // Win32_Tpm n'est lisible qu'avec des privilèges admin
+        get_wmi_json_ns::<PsTpm>(&app, "root\\cimv2\\Security\\MicrosoftTpm", "Win32_Tpm",
+            "SpecVersion, ManufacturerIdTxt, ManufacturerVersion, IsEnabled_InitialValue, IsActivated_InitialValue")
+    );
    
     // --- Logging --- 
     println!("WMI CPU Result: {:?}", cpu_res);
     println!("WMI BaseBoard Result: {:?}", board_res);
     println!("WMI RAM Result: {:?}", mem_res);
     println!("WMI GPU Result: {:?}", gpu_res);
+    println!("WMI BIOS Result: {:?}", bios_res);
+    println!("WMI Enclosure Result: {:?}", enclosure_res);
+    println!("WMI PhysicalDisk Result: {:?}", disk_res);
+    println!("WMI Battery Result: {:?}", battery_res);
+    println!("WMI Monitor Result: {:?}", monitor_res);
+    println!("WMI TPM Result: {:?}", tpm_res);
 
     let mut info = HardwareInfo::default();
 
@@ -143,6 +331,77 @@ pub async fn get_hardware_info(app: AppHandle) -> Result<HardwareInfo, String> {
         let total_ram_bytes: u64 = ram_modules.iter().filter_map(|m| m.capacity).sum();
         info.ram_total_gb = Some(total_ram_bytes as f64 / (1024.0 * 1024.0 * 1024.0));
         info.ram_modules_count = Some(ram_modules.len());
+        info.memory_modules = ram_modules.into_iter().map(|m| MemoryModuleInfo {
+            slot: non_empty(m.device_locator),
+            bank: non_empty(m.bank_label),
+            capacity_gb: m.capacity.map(|c| c as f64 / (1024.0 * 1024.0 * 1024.0)),
+            memory_type: m.smbios_memory_type.and_then(memory_type_name).map(str::to_string),
+            speed_mhz: m.speed,
+            configured_speed_mhz: m.configured_clock_speed,
+            manufacturer: non_empty(m.manufacturer),
+            part_number: non_empty(m.part_number),
+            serial_number: non_empty(m.serial_number),
+        }).collect();
+    }
+
+    // Mapper BIOS et châssis depuis WMI
+    if let Ok(bioses) = bios_res {
+        if let Some(bios) = bioses.into_iter().next() {
+            info.bios = Some(BiosInfo {
+                manufacturer: non_empty(bios.manufacturer),
+                version: non_empty(bios.smbios_bios_version),
+                release_date: bios.release_date.as_deref().and_then(cim_date),
+                serial_number: non_empty(bios.serial_number),
+            });
+        }
+    }
+    if let Ok(enclosures) = enclosure_res {
+        info.chassis_types = enclosures.into_iter()
+            .flat_map(|e| e.chassis_types.unwrap_or_default())
+            .map(chassis_type_name)
+            .collect();
+    }
+
+    // Mapper disques physiques (API de stockage Windows 8+)
+    if let Ok(disks) = disk_res {
+        info.physical_disks = disks.into_iter().map(|d| PhysicalDiskInfo {
+            model: non_empty(d.friendly_name).unwrap_or_else(|| "Disque inconnu".to_string()),
+            serial_number: non_empty(d.serial_number),
+            size_gb: d.size.map(|s| s as f64 / (1024.0 * 1024.0 * 1024.0)),
+            media_type: disk_media_type_name(d.media_type.unwrap_or(0)).to_string(),
+            bus_type: disk_bus_type_name(d.bus_type.unwrap_or(0)).to_string(),
+            health_status: disk_health_status_name(d.health_status.unwrap_or(5)).to_string(),
+        }).collect();
+    }
+
+    if let Ok(batteries) = battery_res {
+        info.batteries = batteries;
+    }
+
+    // Mapper écrans: le fabricant EDID est un identifiant PNP de 3 lettres
+    if let Ok(monitors) = monitor_res {
+        info.monitors = monitors.into_iter().map(|m| {
+            let manufacturer_code = m.manufacturer_name.as_deref().and_then(wmi_char_array);
+            MonitorInfo {
+                manufacturer: manufacturer_code.as_deref().and_then(pnp_vendor_name).map(str::to_string)
+                    .or_else(|| manufacturer_code.clone()),
+                manufacturer_code,
+                model: m.user_friendly_name.as_deref().and_then(wmi_char_array),
+                product_code: m.product_code_id.as_deref().and_then(wmi_char_array),
+                serial_number: m.serial_number_id.as_deref().and_then(wmi_char_array).filter(|s| s != "0"),
+                year_of_manufacture: m.year_of_manufacture.filter(|&y| y > 0),
+            }
+        }).collect();
+    }
+
+    if let Ok(tpms) = tpm_res {
+        info.tpm = tpms.into_iter().next().map(|t| TpmInfo {
+            spec_version: t.spec_version.as_deref().and_then(tpm_spec_version),
+            manufacturer: non_empty(t.manufacturer_id_txt),
+            manufacturer_version: non_empty(t.manufacturer_version),
+            enabled: t.is_enabled_initial_value,
+            activated: t.is_activated_initial_value,
+        });
     }
     
     // Mapper GPU depuis la nouvelle fonction et convertir en GpuInfo
@@ -179,6 +438,49 @@ pub async fn get_hardware_info(app: AppHandle) -> Result<HardwareInfo, String> {
     Ok(info)
 }
 
+// Les champs SMBIOS non renseignés valent souvent des blancs ou "To Be Filled By O.E.M."
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty() && !v.eq_ignore_ascii_case("To Be Filled By O.E.M."))
+}
+
+// Regroupe les trois classes batterie de root\wmi par InstanceName
+fn build_batteries(
+    static_data: Vec<PsBatteryStaticData>,
+    full_charged: &[PsBatteryFullChargedCapacity],
+    statuses: &[PsBatteryStatus],
+) -> Vec<BatteryInfo> {
+    static_data.into_iter().map(|battery| {
+        let full_charge = full_charged.iter()
+            .find(|f| f.instance_name == battery.instance_name)
+            .and_then(|f| f.full_charged_capacity);
+        let status = statuses.iter().find(|s| s.instance_name == battery.instance_name);
+        let charge_percent = match (status.and_then(|s| s.remaining_capacity), full_charge) {
+            (Some(remaining), Some(full)) if full > 0 => Some((remaining as f64 * 100.0 / full as f64).min(100.0).round()),
+            _ => None,
+        };
+        BatteryInfo {
+            name: non_empty(battery.device_name),
+            manufacturer: non_empty(battery.manufacture_name),
+            health_percent: battery_health_percent(battery.designed_capacity, full_charge),
+            design_capacity_mwh: battery.designed_capacity,
+            full_charge_capacity_mwh: full_charge,
+            charge_percent,
+            on_ac_power: status.and_then(|s| s.power_online),
+        }
+    }).collect()
+}
+
+async fn get_battery_info(app: &AppHandle) -> Result<Vec<BatteryInfo>, String> {
+    // Pas de batterie (poste fixe): les classes sont vides
+    let (static_res, full_res, status_res) = tokio::join!(
+        get_wmi_json_ns::<PsBatteryStaticData>(app, "root\\wmi", "BatteryStaticData", "InstanceName, DeviceName, ManufactureName, DesignedCapacity"),
+        get_wmi_json_ns::<PsBatteryFullChargedCapacity>(app, "root\\wmi", "BatteryFullChargedCapacity", "InstanceName, FullChargedCapacity"),
+        get_wmi_json_ns::<PsBatteryStatus>(app, "root\\wmi", "BatteryStatus", "InstanceName, RemainingCapacity, PowerOnline")
+    );
+    Ok(build_batteries(static_res?, &full_res.unwrap_or_default(), &status_res.unwrap_or_default()))
+}
+
 // Nouvelle fonction pour obtenir des informations GPU plus détaillées
 async fn get_gpu_info(app: &AppHandle) -> Result<Vec<PsVideoController>, String> {
     // Script simple avec valeurs fixes pour diagnostic
@@ -226,4 +528,34 @@ async fn get_gpu_info(app: &AppHandle) -> Result<Vec<PsVideoController>, String>
     }
     
     result.map_err(|e| format!("Erreur parsing JSON GPU: {}\nJSON: {}", e, json_str))
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_battery_classes_by_instance() {
+        let static_data = vec![PsBatteryStaticData {
+            instance_name: "ACPI\\PNP0C0A\\1_0".to_string(),
+            device_name: Some("5B10W13930 ".to_string()),
+            manufacture_name: Some("SMP".to_string()),
+            designed_capacity: Some(57000),
+        }];
+        let full = vec![PsBatteryFullChargedCapacity { instance_name: "ACPI\\PNP0C0A\\1_0".to_string(), full_charged_capacity: Some(48450) }];
+        let status = vec![PsBatteryStatus { instance_name: "ACPI\\PNP0C0A\\1_0".to_string(), remaining_capacity: Some(24225), power_online: Some(false) }];
+
+        let batteries = build_batteries(static_data, &full, &status);
+        assert_eq!(batteries.len(), 1);
+        assert_eq!(batteries[0].name.as_deref(), Some("5B10W13930"));
+        assert_eq!(batteries[0].health_percent, Some(85.0));
+        assert_eq!(batteries[0].charge_percent, Some(50.0));
+        assert_eq!(batteries[0].on_ac_power, Some(false));
+    }
+
+    #[test]
+    fn ignores_placeholder_smbios_values() {
+        assert_eq!(non_empty(Some("To Be Filled By O.E.M.".to_string())), None);
+        assert_eq!(non_empty(Some("  ".to_string())), None);
+        assert_eq!(non_empty(Some(" CZC1234XYZ ".to_string())).as_deref(), Some("CZC1234XYZ"));
+    }
+}
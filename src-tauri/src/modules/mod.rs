@@ -2,11 +2,13 @@
 pub mod active_directory;
 pub mod admin;
 pub mod backup;
+pub mod common;
 pub mod disks;
 pub mod event_viewer;
 pub mod hardware;
 pub mod network;
 pub mod security;
+pub mod sessions;
 pub mod shares;
 pub mod system;
 pub mod tasks;
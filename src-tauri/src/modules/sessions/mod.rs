@@ -0,0 +1,298 @@
+use serde::Serialize;
+use std::result::Result;
+use tauri::command;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+use crate::modules::common::oem::decode_console_output;
+
+// Structure finale pour une session ouverte sur la machine (sortie de quser)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SessionInfo {
+    user_name: String,
+    session_name: String, // Vide pour une session déconnectée
+    session_id: u32,
+    state: String,        // Active, Disc (libellé localisé par Windows)
+    idle_time: String,    // Valeur brute: "none", ".", "5", "1:05", "2+03:12"
+    idle_minutes: Option<u64>,
+    logon_time: String,   // Format de date local de la machine
+    is_current: bool,     // Session de l'application (marquée par '>')
+}
+
+// --- Parsing de la sortie quser ---
+
+// Durée d'inactivité quser -> minutes. "none" / "." = aucune inactivité,
+// "5" = minutes, "1:05" = heures:minutes, "2+03:12" = jours+heures:minutes
+fn parse_idle_minutes(idle: &str) -> Option<u64> {
+    let idle = idle.trim();
+    if idle == "." || idle.eq_ignore_ascii_case("none") || idle.eq_ignore_ascii_case("aucun") {
+        return Some(0);
+    }
+    let (days, rest) = match idle.split_once('+') {
+        Some((days, rest)) => (days.parse::<u64>().ok()?, rest),
+        None => (0, idle),
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((hours, minutes)) => (hours.parse::<u64>().ok()?, minutes.parse::<u64>().ok()?),
+        None => (0, rest.parse::<u64>().ok()?),
+    };
+    Some(days * 24 * 60 + hours * 60 + minutes)
+}
+
+fn looks_like_date(token: &str) -> bool {
+    let parts: Vec<&str> = token.split(['/', '.', '-']).collect();
+    parts.len() == 3 && parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+}
+
+// Découpe une ligne en mots avec leur position (en caractères) de début
+fn tokens_with_offsets(chars: &[char]) -> Vec<(usize, String)> {
+    let mut tokens = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_whitespace() {
+            if let Some(token) = current.take() {
+                tokens.push(token);
+            }
+        } else {
+            current.get_or_insert_with(|| (i, String::new())).1.push(*c);
+        }
+    }
+    tokens.extend(current);
+    tokens
+}
+
+// Position de la colonne SESSIONNAME: deuxième groupe de l'en-tête, les groupes étant séparés
+// par au moins deux espaces (les titres eux-mêmes peuvent contenir un espace: "IDLE TIME")
+fn session_column_offset(header: &[char]) -> Option<usize> {
+    let mut spaces = 0;
+    let mut seen_first_group = false;
+    for (i, c) in header.iter().enumerate() {
+        if *c == ' ' {
+            spaces += 1;
+        } else {
+            if seen_first_group && spaces >= 2 {
+                return Some(i);
+            }
+            if i > 0 {
+                seen_first_group = true;
+            }
+            spaces = 0;
+        }
+    }
+    None
+}
+
+// quser produit un tableau à largeur fixe dont l'en-tête est localisé. Les colonnes de droite
+// (ID, STATE, IDLE TIME, LOGON TIME) sont lues à rebours à partir de la date d'ouverture,
+// le début de ligne est coupé à la position de la colonne SESSIONNAME lue dans l'en-tête.
+fn parse_quser_output(output: &str) -> Vec<SessionInfo> {
+    let mut lines = output.lines().map(|l| l.trim_end()).filter(|l| !l.trim().is_empty());
+    let header: Vec<char> = match lines.next() {
+        Some(header) => header.chars().collect(),
+        None => return vec![],
+    };
+    let session_offset = session_column_offset(&header);
+
+    lines.filter_map(|line| {
+        let chars: Vec<char> = line.chars().collect();
+        let is_current = chars.first() == Some(&'>');
+        let tokens = tokens_with_offsets(&chars[1..]);
+
+        let date_index = tokens.iter().rposition(|(_, t)| looks_like_date(t))?;
+        if date_index < 3 {
+            return None;
+        }
+        let logon_time = tokens[date_index..].iter().map(|(_, t)| t.as_str()).collect::<Vec<_>>().join(" ");
+        let idle_time = tokens[date_index - 1].1.clone();
+        let state = tokens[date_index - 2].1.clone();
+        let (id_offset, id) = &tokens[date_index - 3];
+        let session_id = id.parse::<u32>().ok()?;
+
+        // Début de ligne (sans le marqueur) = USERNAME + SESSIONNAME éventuel
+        let prefix: Vec<char> = chars[1..1 + id_offset].to_vec();
+        let (user_name, session_name) = match session_offset {
+            Some(offset) if offset > 1 && offset - 1 < prefix.len() => (
+                prefix[..offset - 1].iter().collect::<String>().trim().to_string(),
+                prefix[offset - 1..].iter().collect::<String>().trim().to_string(),
+            ),
+            _ => {
+                let prefix: String = prefix.iter().collect();
+                let mut words = prefix.split_whitespace();
+                (
+                    words.next().unwrap_or_default().to_string(),
+                    words.collect::<Vec<_>>().join(" "),
+                )
+            }
+        };
+
+        Some(SessionInfo {
+            user_name,
+            session_name,
+            session_id,
+            state,
+            idle_minutes: parse_idle_minutes(&idle_time),
+            idle_time,
+            logon_time,
+            is_current,
+        })
+    }).collect()
+}
+
+// --- Commandes ---
+
+#[command]
+pub async fn list_sessions(app: AppHandle) -> Result<Vec<SessionInfo>, String> {
+    println!("Real: list_sessions() called");
+
+    let output = app.shell()
+        .command("quser")
+        .output()
+        .await
+        .map_err(|e| format!("Erreur lors de l'exécution de quser: {}", e))?;
+
+    // quser écrit dans la page de code OEM (CP850 sur un Windows français), pas en UTF-8
+    let stdout = decode_console_output(&output.stdout);
+    if !output.status.success() {
+        // quser retourne 1 avec "No User exists for *" quand aucune session n'est ouverte
+        if stdout.trim().is_empty() {
+            return Ok(vec![]);
+        }
+        return Err(format!("quser a échoué: {:?} \nErreur: {}",
+            output.status, decode_console_output(&output.stderr)));
+    }
+
+    Ok(parse_quser_output(&stdout))
+}
+
+async fn run_session_tool(app: &AppHandle, program: &str, args: &[String]) -> Result<(), String> {
+    let output = app.shell()
+        .command(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Erreur lors de l'exécution de {}: {}", program, e))?;
+
+    if !output.status.success() {
+        return Err(format!("{} a échoué: {:?} \nErreur: {}\nSortie: {}",
+            program,
+            output.status,
+            decode_console_output(&output.stderr),
+            decode_console_output(&output.stdout)
+        ));
+    }
+    Ok(())
+}
+
+#[command]
+pub async fn logoff_session(app: AppHandle, session_id: u32) -> Result<(), String> {
+    println!("Real: logoff_session({}) called", session_id);
+    // Important: Nécessite des privilèges admin pour les sessions d'autres utilisateurs
+    run_session_tool(&app, "logoff", &[session_id.to_string()]).await
+}
+
+#[command]
+pub async fn disconnect_session(app: AppHandle, session_id: u32) -> Result<(), String> {
+    println!("Real: disconnect_session({}) called", session_id);
+    // Important: Nécessite des privilèges admin pour les sessions d'autres utilisateurs
+    run_session_tool(&app, "tsdiscon", &[session_id.to_string()]).await
+}
+
+#[command]
+pub async fn send_session_message(
+    app: AppHandle,
+    session_id: u32,
+    message: String,
+    timeout_seconds: Option<u32>, // Fermeture automatique du message (60 s par défaut côté msg.exe)
+) -> Result<(), String> {
+    println!("Real: send_session_message({}) called", session_id);
+
+    if message.trim().is_empty() {
+        return Err("Le message ne peut pas être vide.".to_string());
+    }
+
+    let mut args = vec![session_id.to_string()];
+    if let Some(timeout) = timeout_seconds {
+        args.push(format!("/TIME:{}", timeout));
+    }
+    args.push(message);
+    run_session_tool(&app, "msg", &args).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_english_quser_output() {
+        let sessions = parse_quser_output(include_str!("fixtures/quser_en.txt"));
+        assert_eq!(sessions.len(), 4);
+
+        assert_eq!(sessions[0], SessionInfo {
+            user_name: "administrator".to_string(),
+            session_name: "console".to_string(),
+            session_id: 1,
+            state: "Active".to_string(),
+            idle_time: "none".to_string(),
+            idle_minutes: Some(0),
+            logon_time: "10/18/2026 9:02 AM".to_string(),
+            is_current: true,
+        });
+
+        assert_eq!(sessions[1].user_name, "jdoe");
+        assert_eq!(sessions[1].session_name, "");
+        assert_eq!(sessions[1].session_id, 2);
+        assert_eq!(sessions[1].state, "Disc");
+        assert_eq!(sessions[1].idle_minutes, Some(65));
+        assert!(!sessions[1].is_current);
+
+        assert_eq!(sessions[2].session_name, "rdp-tcp#3");
+        assert_eq!(sessions[2].idle_minutes, Some(5));
+
+        assert_eq!(sessions[3].user_name, "svc.backup");
+        assert_eq!(sessions[3].idle_minutes, Some(2 * 24 * 60 + 3 * 60 + 12));
+        assert_eq!(sessions[3].logon_time, "10/15/2026 11:30 PM");
+    }
+
+    #[test]
+    fn parses_localized_quser_output() {
+        let sessions = parse_quser_output(include_str!("fixtures/quser_fr.txt"));
+        assert_eq!(sessions.len(), 2);
+
+        // Nom d'utilisateur contenant un espace: découpé à la position de la colonne
+        assert_eq!(sessions[0].user_name, "admin local");
+        assert_eq!(sessions[0].session_name, "console");
+        assert_eq!(sessions[0].state, "Actif");
+        assert_eq!(sessions[0].idle_minutes, Some(0));
+        assert_eq!(sessions[0].logon_time, "18/10/2026 09:02");
+
+        assert_eq!(sessions[1].user_name, "mdupont");
+        assert_eq!(sessions[1].session_name, "");
+        assert_eq!(sessions[1].state, "Déco");
+    }
+
+    #[test]
+    fn parses_oem_encoded_quser_output() {
+        // Sortie brute d'un Windows français: en-tête "ÉTAT" et état "Déco" en CP850
+        let sessions = parse_quser_output(&decode_console_output(include_bytes!("fixtures/quser_fr_cp850.bin")));
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].user_name, "admin local");
+        assert_eq!(sessions[1].state, "Déco");
+        assert_eq!(sessions[1].idle_minutes, Some(65));
+    }
+
+    #[test]
+    fn empty_output_yields_no_sessions() {
+        assert!(parse_quser_output("").is_empty());
+        assert!(parse_quser_output(" USERNAME              SESSIONNAME        ID  STATE   IDLE TIME  LOGON TIME\r\n").is_empty());
+    }
+
+    #[test]
+    fn idle_time_formats() {
+        assert_eq!(parse_idle_minutes("."), Some(0));
+        assert_eq!(parse_idle_minutes("none"), Some(0));
+        assert_eq!(parse_idle_minutes("42"), Some(42));
+        assert_eq!(parse_idle_minutes("3:07"), Some(187));
+        assert_eq!(parse_idle_minutes("1+00:01"), Some(1441));
+        assert_eq!(parse_idle_minutes("n/a"), None);
+    }
+}
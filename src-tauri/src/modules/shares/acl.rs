@@ -0,0 +1,479 @@
+use serde::{Deserialize, Serialize};
+use std::result::Result;
+use tauri::command;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+use crate::modules::common::powershell::ps_quote;
+
+// --- Structs ACL NTFS ---
+
+// ACE telle que produite par les scripts PowerShell ci-dessous
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsAce {
+    principal: String,
+    sid: Option<String>,
+    rights: i64, // FileSystemRights en entier signé (GENERIC_READ = 0x80000000 est négatif)
+    rights_display: Option<String>,
+    access_type: String,
+    is_inherited: bool,
+    inheritance_flags: Option<String>,
+    propagation_flags: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsPathAcl {
+    path: String,
+    owner: Option<String>,
+    are_access_rules_protected: Option<bool>,
+    access: Option<Vec<PsAce>>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct AclEntry {
+    principal: String,
+    sid: String,
+    rights: String,    // Libellé (FullControl, Modify, ReadAndExecute...)
+    rights_mask: u32,
+    access_type: String, // Allow, Deny
+    is_inherited: bool,
+    inheritance_flags: String, // None, ContainerInherit, ObjectInherit
+    propagation_flags: String, // None, NoPropagateInherit, InheritOnly
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PathAcl {
+    path: String,
+    owner: String,
+    inheritance_protected: bool, // Héritage désactivé sur le dossier
+    entries: Vec<AclEntry>,
+}
+
+// Modification demandée sur l'ACL: "add" ajoute une ACE, "remove" retire les droits indiqués
+// ou, sans `rights`, toutes les ACE explicites du principal
+#[derive(Deserialize, Debug, Clone)]
+pub struct AclChange {
+    action: String,
+    principal: String,
+    rights: Option<String>,            // "Modify", "ReadAndExecute, Synchronize"...
+    access_type: Option<String>,       // Allow (défaut), Deny
+    inheritance_flags: Option<String>, // "ContainerInherit, ObjectInherit" par défaut
+    propagation_flags: Option<String>, // "None" par défaut
+}
+
+// Accès effectif d'un principal sur un partage (droits de partage ∩ droits NTFS)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct EffectiveAccess {
+    principal: String,
+    share_rights: String,
+    ntfs_rights: String,
+    effective_rights: String,
+    effective_mask: u32,
+    can_read: bool,
+    can_write: bool,
+    can_delete: bool,
+    full_control: bool,
+    limited_by: String, // share, ntfs, both, none
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsShareAce {
+    sid: Option<String>,
+    access_right: String,
+    access_type: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsEffectiveAccessInput {
+    share_aces: Option<Vec<PsShareAce>>,
+    ntfs_aces: Option<Vec<PsAce>>,
+    principal_sids: Option<Vec<String>>,
+}
+
+// --- Calcul des droits ---
+
+// Masques FileSystemRights usuels
+const FULL_CONTROL: u32 = 0x001F_01FF;
+const MODIFY: u32 = 0x0013_01BF;
+const READ_AND_EXECUTE: u32 = 0x0012_00A9;
+const READ: u32 = 0x0012_0089;
+const WRITE: u32 = 0x0011_0116;
+const DELETE: u32 = 0x0001_0000;
+const READ_DATA: u32 = 0x0000_0001;
+const WRITE_DATA: u32 = 0x0000_0002;
+
+// Une ACE évaluée pour le calcul d'accès effectif
+#[derive(Debug, Clone)]
+struct AccessRule {
+    sid: String,
+    mask: u32,
+    deny: bool,
+    inherited: bool,
+    inherit_only: bool, // Ne s'applique qu'aux enfants, pas au dossier lui-même
+}
+
+// Les droits génériques (souvent présents sur CREATOR OWNER ou SYSTEM) sont convertis en droits spécifiques
+fn map_generic_rights(mask: u32) -> u32 {
+    let mut mapped = mask & 0x0FFF_FFFF;
+    if mask & 0x1000_0000 != 0 { mapped |= FULL_CONTROL; }  // GENERIC_ALL
+    if mask & 0x2000_0000 != 0 { mapped |= 0x0012_00A0; }   // GENERIC_EXECUTE
+    if mask & 0x4000_0000 != 0 { mapped |= WRITE; }         // GENERIC_WRITE
+    if mask & 0x8000_0000 != 0 { mapped |= READ; }          // GENERIC_READ
+    mapped
+}
+
+// Droits de partage SMB exprimés en équivalent NTFS
+fn share_right_mask(access_right: &str) -> u32 {
+    match access_right.to_lowercase().as_str() {
+        "full" => FULL_CONTROL,
+        "change" => MODIFY,
+        "read" => READ_AND_EXECUTE,
+        _ => 0,
+    }
+}
+
+// Évaluation dans l'ordre canonique Windows: refus explicites, autorisations explicites,
+// puis ACE héritées (refus avant autorisations). Pour chaque bit, la première ACE qui le
+// mentionne l'emporte.
+fn evaluate_rules(rules: &[AccessRule], identities: &[String]) -> u32 {
+    let mut applicable: Vec<&AccessRule> = rules.iter()
+        .filter(|r| !r.inherit_only && identities.iter().any(|id| id.eq_ignore_ascii_case(&r.sid)))
+        .collect();
+    applicable.sort_by_key(|r| (r.inherited, !r.deny));
+
+    let mut granted = 0u32;
+    let mut denied = 0u32;
+    for rule in applicable {
+        let bits = map_generic_rights(rule.mask) & !(granted | denied);
+        if rule.deny {
+            denied |= bits;
+        } else {
+            granted |= bits;
+        }
+    }
+    granted
+}
+
+fn rights_label(mask: u32) -> String {
+    let label = if mask & FULL_CONTROL == FULL_CONTROL {
+        "FullControl"
+    } else if mask & MODIFY == MODIFY {
+        "Modify"
+    } else if mask & READ_AND_EXECUTE == READ_AND_EXECUTE {
+        if mask & WRITE == WRITE { "ReadAndExecute, Write" } else { "ReadAndExecute" }
+    } else if mask & READ == READ {
+        if mask & WRITE == WRITE { "Read, Write" } else { "Read" }
+    } else if mask & WRITE == WRITE {
+        "Write"
+    } else if mask == 0 {
+        "None"
+    } else {
+        "Special"
+    };
+    label.to_string()
+}
+
+fn combine_effective_access(principal: &str, share_mask: u32, ntfs_mask: u32) -> EffectiveAccess {
+    let effective_mask = share_mask & ntfs_mask;
+    let limited_by = match (effective_mask != ntfs_mask, effective_mask != share_mask) {
+        (true, true) => "both",
+        (true, false) => "share",
+        (false, true) => "ntfs",
+        (false, false) => "none",
+    };
+    EffectiveAccess {
+        principal: principal.to_string(),
+        share_rights: rights_label(share_mask),
+        ntfs_rights: rights_label(ntfs_mask),
+        effective_rights: rights_label(effective_mask),
+        effective_mask,
+        can_read: effective_mask & READ_DATA != 0,
+        can_write: effective_mask & WRITE_DATA != 0,
+        can_delete: effective_mask & DELETE != 0,
+        full_control: effective_mask & FULL_CONTROL == FULL_CONTROL,
+        limited_by: limited_by.to_string(),
+    }
+}
+
+fn ntfs_rule(ace: &PsAce) -> AccessRule {
+    AccessRule {
+        sid: ace.sid.clone().unwrap_or_else(|| ace.principal.clone()),
+        mask: ace.rights as u32,
+        deny: ace.access_type.eq_ignore_ascii_case("Deny"),
+        inherited: ace.is_inherited,
+        inherit_only: ace.propagation_flags.as_deref().is_some_and(|f| f.contains("InheritOnly")),
+    }
+}
+
+fn share_rule(ace: &PsShareAce) -> AccessRule {
+    AccessRule {
+        sid: ace.sid.clone().unwrap_or_default(),
+        mask: share_right_mask(&ace.access_right),
+        deny: ace.access_type.eq_ignore_ascii_case("Deny"),
+        inherited: false,
+        inherit_only: false,
+    }
+}
+
+// --- Scripts PowerShell ---
+
+// Fonctions communes: résolution de SID et conversion des ACE NTFS
+const PS_ACL_HELPERS: &str = r#"
+    function Resolve-Sid($account) { try { (New-Object System.Security.Principal.NTAccount($account)).Translate([System.Security.Principal.SecurityIdentifier]).Value } catch { $null } }
+    function Convert-Ace($ace) {
+        [PSCustomObject]@{
+            Principal = $ace.IdentityReference.Value;
+            Sid = (Resolve-Sid $ace.IdentityReference.Value);
+            Rights = [int64]$ace.FileSystemRights;
+            RightsDisplay = $ace.FileSystemRights.ToString();
+            AccessType = $ace.AccessControlType.ToString();
+            IsInherited = $ace.IsInherited;
+            InheritanceFlags = $ace.InheritanceFlags.ToString();
+            PropagationFlags = $ace.PropagationFlags.ToString()
+        }
+    }
+"#;
+
+// Libellés de droits/indicateurs: uniquement des identifiants .NET séparés par des virgules
+fn is_valid_flag_list(value: &str) -> bool {
+    regex::Regex::new(r"^[A-Za-z]+(\s*,\s*[A-Za-z]+)*$").map(|re| re.is_match(value.trim())).unwrap_or(false)
+}
+
+async fn run_acl_script(app: &AppHandle, command: &str) -> Result<String, String> {
+    let output = app.shell().command("powershell").args(["-Command", command]).output().await
+        .map_err(|e| format!("Erreur lors de l'exécution de PowerShell (ACL): {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Opération ACL a échoué: {:?} \nErreur: {}", output.status, String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// --- Commandes ---
+
+#[command]
+pub async fn get_path_acl(app: AppHandle, path: String) -> Result<PathAcl, String> {
+    println!("Real: get_path_acl('{}') called", path);
+
+    let command = format!(
+        "$ErrorActionPreference = 'Stop'; {} $acl = Get-Acl -LiteralPath {}; [PSCustomObject]@{{ Path = {}; Owner = $acl.Owner; AreAccessRulesProtected = $acl.AreAccessRulesProtected; Access = @($acl.Access | ForEach-Object {{ Convert-Ace $_ }}) }} | ConvertTo-Json -Depth 4 -Compress",
+        PS_ACL_HELPERS, ps_quote(&path), ps_quote(&path)
+    );
+    let json_str = run_acl_script(&app, &command).await?;
+    let parsed: PsPathAcl = serde_json::from_str(&json_str)
+        .map_err(|e| format!("Erreur parsing JSON ACL: {}\nJSON: {}", e, json_str))?;
+
+    let entries = parsed.access.unwrap_or_default().into_iter().map(|ace| {
+        let rights_mask = ace.rights as u32;
+        AclEntry {
+            sid: ace.sid.unwrap_or_else(|| "N/A".to_string()),
+            principal: ace.principal,
+            rights: ace.rights_display.unwrap_or_else(|| rights_label(map_generic_rights(rights_mask))),
+            rights_mask,
+            access_type: ace.access_type,
+            is_inherited: ace.is_inherited,
+            inheritance_flags: ace.inheritance_flags.unwrap_or_else(|| "None".to_string()),
+            propagation_flags: ace.propagation_flags.unwrap_or_else(|| "None".to_string()),
+        }
+    }).collect();
+
+    Ok(PathAcl {
+        path: parsed.path,
+        owner: parsed.owner.unwrap_or_default(),
+        inheritance_protected: parsed.are_access_rules_protected.unwrap_or(false),
+        entries,
+    })
+}
+
+#[command]
+pub async fn set_path_acl(app: AppHandle, path: String, changes: Vec<AclChange>) -> Result<(), String> {
+    println!("Real: set_path_acl('{}', {} modification(s)) called", path, changes.len());
+
+    if changes.is_empty() {
+        return Err("Aucune modification d'ACL demandée.".to_string());
+    }
+
+    // Important: Nécessite d'être propriétaire ou de disposer du droit ChangePermissions
+    let mut statements = Vec::new();
+    for change in &changes {
+        if change.principal.trim().is_empty() {
+            return Err("Principal vide dans les modifications d'ACL".to_string());
+        }
+        let principal = ps_quote(change.principal.trim());
+        let access_type = change.access_type.clone().unwrap_or_else(|| "Allow".to_string());
+        let inheritance = change.inheritance_flags.clone().unwrap_or_else(|| "ContainerInherit, ObjectInherit".to_string());
+        let propagation = change.propagation_flags.clone().unwrap_or_else(|| "None".to_string());
+        for value in [&access_type, &inheritance, &propagation] {
+            if !is_valid_flag_list(value) {
+                return Err(format!("Valeur d'ACL invalide: '{}'", value));
+            }
+        }
+
+        let statement = match (change.action.to_lowercase().as_str(), &change.rights) {
+            ("add", Some(rights)) | ("remove", Some(rights)) => {
+                if !is_valid_flag_list(rights) {
+                    return Err(format!("Droits NTFS invalides: '{}'", rights));
+                }
+                let method = if change.action.eq_ignore_ascii_case("add") { "AddAccessRule" } else { "RemoveAccessRule" };
+                format!(
+                    "[void]$acl.{}((New-Object System.Security.AccessControl.FileSystemAccessRule({}, {}, {}, {}, {})));",
+                    method, principal, ps_quote(rights), ps_quote(&inheritance), ps_quote(&propagation), ps_quote(&access_type)
+                )
+            }
+            ("add", None) => return Err(format!("Droits manquants pour l'ajout de {}", change.principal)),
+            ("remove", None) => format!("$acl.PurgeAccessRules((New-Object System.Security.Principal.NTAccount({})));", principal),
+            (other, _) => return Err(format!("Action d'ACL inconnue '{}' (add, remove)", other)),
+        };
+        statements.push(statement);
+    }
+
+    // Set-Acl tente de réécrire le propriétaire: passer par SetAccessControl pour ne modifier que la DACL
+    let command = format!(
+        "$ErrorActionPreference = 'Stop'; $item = Get-Item -LiteralPath {}; $acl = $item.GetAccessControl('Access'); {} $item.SetAccessControl($acl)",
+        ps_quote(&path), statements.join(" ")
+    );
+    run_acl_script(&app, &command).await?;
+    Ok(())
+}
+
+#[command]
+pub async fn get_effective_share_access(app: AppHandle, share_name: String, principal: String) -> Result<EffectiveAccess, String> {
+    println!("Real: get_effective_share_access(share: '{}', principal: '{}') called", share_name, principal);
+
+    // Identités du principal: son SID, ses groupes (jeton S4U si disponible, sinon groupes locaux
+    // via ADSI) et les groupes implicites d'un accès réseau (Everyone, Authenticated Users, Network)
+    let command = format!(
+        r#"$ErrorActionPreference = 'Stop'; {helpers}
+        $share = Get-SmbShare -Name {share};
+        $principalSid = Resolve-Sid {principal};
+        if (-not $principalSid) {{ throw 'Principal introuvable: ' + {principal} }}
+        $sids = @($principalSid, 'S-1-1-0', 'S-1-5-11', 'S-1-5-2');
+        try {{ $sids += (New-Object System.Security.Principal.WindowsIdentity({principal})).Groups | ForEach-Object {{ $_.Value }} }} catch {{ }}
+        foreach ($group in Get-LocalGroup) {{
+            $members = @(([ADSI]('WinNT://' + $env:COMPUTERNAME + '/' + $group.Name + ',group')).psbase.Invoke('Members') | ForEach-Object {{
+                try {{ (New-Object System.Security.Principal.SecurityIdentifier($_.GetType().InvokeMember('objectSid', 'GetProperty', $null, $_, $null), 0)).Value }} catch {{ }}
+            }});
+            if ($members | Where-Object {{ $sids -contains $_ }}) {{ $sids += $group.SID.Value }}
+        }}
+        $shareAces = @(Get-SmbShareAccess -Name {share} | ForEach-Object {{ [PSCustomObject]@{{ Sid = (Resolve-Sid $_.AccountName); AccessRight = $_.AccessRight.ToString(); AccessType = $_.AccessControlType.ToString() }} }});
+        $ntfsAces = @((Get-Acl -LiteralPath $share.Path).Access | ForEach-Object {{ Convert-Ace $_ }});
+        [PSCustomObject]@{{ ShareAces = $shareAces; NtfsAces = $ntfsAces; PrincipalSids = @($sids | Select-Object -Unique) }} | ConvertTo-Json -Depth 4 -Compress"#,
+        helpers = PS_ACL_HELPERS,
+        share = ps_quote(&share_name),
+        principal = ps_quote(&principal),
+    );
+    let json_str = run_acl_script(&app, &command).await?;
+    let parsed: PsEffectiveAccessInput = serde_json::from_str(&json_str)
+        .map_err(|e| format!("Erreur parsing JSON accès effectif: {}\nJSON: {}", e, json_str))?;
+
+    let identities = parsed.principal_sids.unwrap_or_default();
+    let share_rules: Vec<AccessRule> = parsed.share_aces.unwrap_or_default().iter().map(share_rule).collect();
+    let ntfs_rules: Vec<AccessRule> = parsed.ntfs_aces.unwrap_or_default().iter().map(ntfs_rule).collect();
+
+    Ok(combine_effective_access(
+        &principal,
+        evaluate_rules(&share_rules, &identities),
+        evaluate_rules(&ntfs_rules, &identities),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER: &str = "S-1-5-21-1-2-3-1001";
+    const USERS_GROUP: &str = "S-1-5-32-545";
+    const EVERYONE: &str = "S-1-1-0";
+
+    fn rule(sid: &str, mask: u32, deny: bool, inherited: bool) -> AccessRule {
+        AccessRule { sid: sid.to_string(), mask, deny, inherited, inherit_only: false }
+    }
+
+    fn identities() -> Vec<String> {
+        vec![USER.to_string(), USERS_GROUP.to_string(), EVERYONE.to_string()]
+    }
+
+    #[test]
+    fn allow_rules_accumulate_across_groups() {
+        let rules = vec![rule(USERS_GROUP, READ_AND_EXECUTE, false, true), rule(USER, WRITE, false, false)];
+        let mask = evaluate_rules(&rules, &identities());
+        assert_eq!(mask, READ_AND_EXECUTE | WRITE);
+    }
+
+    #[test]
+    fn rules_for_other_principals_are_ignored() {
+        let rules = vec![rule("S-1-5-32-544", FULL_CONTROL, false, false)];
+        assert_eq!(evaluate_rules(&rules, &identities()), 0);
+    }
+
+    #[test]
+    fn explicit_deny_wins_over_allow() {
+        let rules = vec![rule(USERS_GROUP, MODIFY, false, false), rule(USER, DELETE, true, false)];
+        let mask = evaluate_rules(&rules, &identities());
+        assert_eq!(mask & DELETE, 0);
+        assert_ne!(mask & WRITE_DATA, 0);
+    }
+
+    #[test]
+    fn explicit_allow_wins_over_inherited_deny() {
+        let rules = vec![rule(USER, WRITE, true, true), rule(USER, WRITE, false, false)];
+        assert_eq!(evaluate_rules(&rules, &identities()) & WRITE, WRITE);
+    }
+
+    #[test]
+    fn inherit_only_rules_do_not_apply_to_the_folder() {
+        let mut creator_owner = rule(USER, FULL_CONTROL, false, false);
+        creator_owner.inherit_only = true;
+        assert_eq!(evaluate_rules(&[creator_owner], &identities()), 0);
+    }
+
+    #[test]
+    fn generic_rights_are_mapped() {
+        assert_eq!(map_generic_rights(0x1000_0000), FULL_CONTROL);
+        assert_eq!(map_generic_rights(0x8000_0000) & READ, READ);
+        // -1610612736 = GENERIC_READ | GENERIC_EXECUTE tel que sérialisé par PowerShell
+        let ace_rights: i64 = -1_610_612_736;
+        assert_eq!(rights_label(map_generic_rights(ace_rights as u32)), "ReadAndExecute");
+    }
+
+    #[test]
+    fn share_read_limits_ntfs_modify() {
+        let access = combine_effective_access("CORP\\jdoe", share_right_mask("Read"), MODIFY);
+        assert_eq!(access.effective_rights, "ReadAndExecute");
+        assert!(access.can_read);
+        assert!(!access.can_write);
+        assert!(!access.can_delete);
+        assert_eq!(access.limited_by, "share");
+    }
+
+    #[test]
+    fn ntfs_read_limits_share_full() {
+        let access = combine_effective_access("CORP\\jdoe", share_right_mask("Full"), READ_AND_EXECUTE);
+        assert_eq!(access.effective_rights, "ReadAndExecute");
+        assert_eq!(access.limited_by, "ntfs");
+    }
+
+    #[test]
+    fn full_on_both_sides_gives_full_control() {
+        let access = combine_effective_access("CORP\\jdoe", share_right_mask("full"), FULL_CONTROL);
+        assert!(access.full_control);
+        assert_eq!(access.effective_rights, "FullControl");
+        assert_eq!(access.limited_by, "none");
+    }
+
+    #[test]
+    fn share_deny_removes_all_access() {
+        let share_rules = vec![
+            share_rule(&PsShareAce { sid: Some(EVERYONE.to_string()), access_right: "Full".to_string(), access_type: "Allow".to_string() }),
+            share_rule(&PsShareAce { sid: Some(USER.to_string()), access_right: "Full".to_string(), access_type: "Deny".to_string() }),
+        ];
+        let share_mask = evaluate_rules(&share_rules, &identities());
+        let access = combine_effective_access("CORP\\jdoe", share_mask, FULL_CONTROL);
+        assert_eq!(access.effective_mask, 0);
+        assert_eq!(access.effective_rights, "None");
+        assert!(!access.can_read);
+    }
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use std::result::Result;
+use tauri::command;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+use crate::modules::common::powershell::{ps_quote, secret_env_ref, SECRET_ENV_VAR};
+
+// --- Côté client SMB: lecteurs réseau mappés et configuration de sécurité SMB ---
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsSmbMapping {
+    local_path: Option<String>,
+    remote_path: String,
+    status: Option<String>,
+    persistent: Option<bool>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct MappedDriveInfo {
+    local_path: String, // "Z:" ou vide pour une connexion sans lettre
+    remote_path: String,
+    status: String,     // OK, Disconnected, Unavailable...
+    persistent: bool,   // Reconnecté à l'ouverture de session
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsSmbSecurityConfig {
+    server_smb1_enabled: Option<bool>,
+    server_signing_required: Option<bool>,
+    server_signing_enabled: Option<bool>,
+    server_encrypt_data: Option<bool>,
+    client_smb1_installed: Option<bool>,
+    client_signing_required: Option<bool>,
+    client_signing_enabled: Option<bool>,
+    client_insecure_guest_logons: Option<bool>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SmbConfigIssue {
+    setting: String,
+    severity: String, // High, Medium, Low
+    message: String,
+    recommendation: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SmbSecurityConfig {
+    server_smb1_enabled: bool,
+    server_signing_required: bool,
+    server_signing_enabled: bool,
+    server_encrypt_data: bool,
+    client_smb1_installed: bool,
+    client_signing_required: bool,
+    client_signing_enabled: bool,
+    client_insecure_guest_logons: bool,
+    issues: Vec<SmbConfigIssue>,
+}
+
+fn smb_issue(setting: &str, severity: &str, message: &str, recommendation: &str) -> SmbConfigIssue {
+    SmbConfigIssue {
+        setting: setting.to_string(),
+        severity: severity.to_string(),
+        message: message.to_string(),
+        recommendation: recommendation.to_string(),
+    }
+}
+
+fn evaluate_smb_config(config: &SmbSecurityConfig) -> Vec<SmbConfigIssue> {
+    let mut issues = Vec::new();
+    if config.server_smb1_enabled {
+        issues.push(smb_issue("Server.EnableSMB1Protocol", "High",
+            "SMBv1 est activé sur le serveur (vulnérable à EternalBlue / WannaCry).",
+            "Set-SmbServerConfiguration -EnableSMB1Protocol $false"));
+    }
+    if config.client_smb1_installed {
+        issues.push(smb_issue("Client.SMB1Protocol", "High",
+            "Le pilote client SMBv1 (mrxsmb10) est installé.",
+            "Disable-WindowsOptionalFeature -Online -FeatureName SMB1Protocol"));
+    }
+    if !config.server_signing_required {
+        issues.push(smb_issue("Server.RequireSecuritySignature", "Medium",
+            "La signature SMB n'est pas exigée par le serveur (relais NTLM possible).",
+            "Set-SmbServerConfiguration -RequireSecuritySignature $true"));
+    }
+    if !config.client_signing_required {
+        issues.push(smb_issue("Client.RequireSecuritySignature", "Medium",
+            "La signature SMB n'est pas exigée par le client.",
+            "Set-SmbClientConfiguration -RequireSecuritySignature $true"));
+    }
+    if config.client_insecure_guest_logons {
+        issues.push(smb_issue("Client.EnableInsecureGuestLogons", "Medium",
+            "Les connexions invité non authentifiées sont autorisées côté client.",
+            "Set-SmbClientConfiguration -EnableInsecureGuestLogons $false"));
+    }
+    if !config.server_encrypt_data {
+        issues.push(smb_issue("Server.EncryptData", "Low",
+            "Le chiffrement SMB 3 n'est pas imposé au niveau du serveur.",
+            "Set-SmbServerConfiguration -EncryptData $true (ou par partage avec Set-SmbShare -EncryptData)"));
+    }
+    issues
+}
+
+async fn run_smb_client_command(app: &AppHandle, command: &str, password: Option<&str>) -> Result<String, String> {
+    let mut shell_command = app.shell().command("powershell").args(["-Command", command]);
+    if let Some(secret) = password {
+        shell_command = shell_command.env(SECRET_ENV_VAR, secret);
+    }
+    let output = shell_command.output().await.map_err(|e| e.to_string())?;
+    if !output.status.success() { return Err(String::from_utf8_lossy(&output.stderr).to_string()); }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// Normalise une lettre de lecteur ("z", "Z:", "Z:\") en "Z:"
+fn normalize_drive_letter(local_path: &str) -> Result<String, String> {
+    let letter = local_path.trim().chars().next().filter(|c| c.is_ascii_alphabetic())
+        .ok_or_else(|| format!("Lettre de lecteur invalide: '{}'", local_path))?;
+    Ok(format!("{}:", letter.to_ascii_uppercase()))
+}
+
+// --- Commandes ---
+
+// Note: une application élevée ne voit pas les lecteurs mappés par la session non élevée
+// (et inversement) sauf si EnableLinkedConnections est configuré.
+#[command]
+pub async fn list_mapped_drives(app: AppHandle) -> Result<Vec<MappedDriveInfo>, String> {
+    println!("Real: list_mapped_drives() called");
+    // Les mappages persistants sont enregistrés sous HKCU:\Network\<lettre>
+    let command = "Get-SmbMapping | Select-Object LocalPath, RemotePath, @{N='Status';E={$_.Status.ToString()}}, @{N='Persistent';E={ $_.LocalPath -and (Test-Path ('HKCU:\\Network\\' + $_.LocalPath.TrimEnd(':'))) }} | ConvertTo-Json -Compress";
+    let json_str = run_smb_client_command(&app, command, None).await?;
+    if json_str.trim().is_empty() { return Ok(vec![]); }
+    let parsed: Vec<PsSmbMapping> = if json_str.trim().starts_with('[') {
+        serde_json::from_str(&json_str).map_err(|e| e.to_string())?
+    } else {
+        serde_json::from_str::<PsSmbMapping>(&json_str).map(|m| vec![m]).map_err(|e| e.to_string())?
+    };
+    let final_drives = parsed.into_iter().map(|ps| MappedDriveInfo {
+        local_path: ps.local_path.unwrap_or_default(),
+        remote_path: ps.remote_path,
+        status: ps.status.unwrap_or_default(),
+        persistent: ps.persistent.unwrap_or(false),
+    }).collect();
+    Ok(final_drives)
+}
+
+#[command]
+pub async fn map_network_drive(
+    app: AppHandle,
+    local_path: String,
+    remote_path: String,
+    user_name: Option<String>,
+    password: Option<String>,
+    persistent: Option<bool>,
+) -> Result<(), String> {
+    // Ne jamais journaliser le mot de passe
+    println!("Real: map_network_drive('{}' -> '{}', user: {:?}) called", local_path, remote_path, user_name);
+
+    let drive = normalize_drive_letter(&local_path)?;
+    if !remote_path.trim().starts_with("\\\\") {
+        return Err(format!("Chemin UNC invalide: '{}' (attendu: \\\\serveur\\partage)", remote_path));
+    }
+
+    let mut command = format!(
+        "New-SmbMapping -LocalPath {} -RemotePath {} -Persistent ${}",
+        ps_quote(&drive), ps_quote(remote_path.trim()), persistent.unwrap_or(true)
+    );
+    if let Some(user) = user_name.as_deref().filter(|u| !u.trim().is_empty()) {
+        command.push_str(&format!(" -UserName {}", ps_quote(user.trim())));
+    }
+    let password = password.filter(|p| !p.is_empty());
+    if password.is_some() {
+        // Le mot de passe transite par l'environnement du processus, jamais par la ligne de commande
+        command.push_str(&format!(" -Password {}", secret_env_ref()));
+    }
+    run_smb_client_command(&app, &command, password.as_deref()).await?;
+    Ok(())
+}
+
+#[command]
+pub async fn unmap_network_drive(app: AppHandle, local_path: String) -> Result<(), String> {
+    println!("Real: unmap_network_drive('{}') called", local_path);
+    let drive = normalize_drive_letter(&local_path)?;
+    // -UpdateProfile supprime aussi la reconnexion persistante
+    let command = format!("Remove-SmbMapping -LocalPath {} -Force -UpdateProfile", ps_quote(&drive));
+    run_smb_client_command(&app, &command, None).await?;
+    Ok(())
+}
+
+#[command]
+pub async fn get_smb_security_config(app: AppHandle) -> Result<SmbSecurityConfig, String> {
+    println!("Real: get_smb_security_config() called");
+    // Nécessite admin pour Get-SmbServerConfiguration
+    let command = "$server = Get-SmbServerConfiguration; $client = Get-SmbClientConfiguration; \
+        [PSCustomObject]@{ \
+            ServerSmb1Enabled = $server.EnableSMB1Protocol; \
+            ServerSigningRequired = $server.RequireSecuritySignature; \
+            ServerSigningEnabled = $server.EnableSecuritySignature; \
+            ServerEncryptData = $server.EncryptData; \
+            ClientSmb1Installed = [bool](Get-Service -Name mrxsmb10 -ErrorAction SilentlyContinue); \
+            ClientSigningRequired = $client.RequireSecuritySignature; \
+            ClientSigningEnabled = $client.EnableSecuritySignature; \
+            ClientInsecureGuestLogons = $client.EnableInsecureGuestLogons \
+        } | ConvertTo-Json -Compress";
+    let json_str = run_smb_client_command(&app, command, None).await?;
+    let parsed: PsSmbSecurityConfig = serde_json::from_str(&json_str)
+        .map_err(|e| format!("Erreur parsing JSON configuration SMB: {}\nJSON: {}", e, json_str))?;
+
+    let mut config = SmbSecurityConfig {
+        server_smb1_enabled: parsed.server_smb1_enabled.unwrap_or(false),
+        server_signing_required: parsed.server_signing_required.unwrap_or(false),
+        server_signing_enabled: parsed.server_signing_enabled.unwrap_or(false),
+        server_encrypt_data: parsed.server_encrypt_data.unwrap_or(false),
+        client_smb1_installed: parsed.client_smb1_installed.unwrap_or(false),
+        client_signing_required: parsed.client_signing_required.unwrap_or(false),
+        client_signing_enabled: parsed.client_signing_enabled.unwrap_or(false),
+        client_insecure_guest_logons: parsed.client_insecure_guest_logons.unwrap_or(false),
+        issues: vec![],
+    };
+    config.issues = evaluate_smb_config(&config);
+    Ok(config)
+}
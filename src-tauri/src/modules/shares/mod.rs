@@ -4,12 +4,20 @@ use tauri::command;
 use tauri::AppHandle;
 use tauri_plugin_shell::ShellExt;
 
+use crate::modules::common::powershell::ps_quote;
+
+pub mod acl;
+pub mod client;
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct PsSmbShare {
     name: String,
     path: String,
     description: Option<String>,
+    current_users: Option<u32>,
+    caching_mode: Option<String>,
+    folder_enumeration_mode: Option<String>,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -17,12 +25,103 @@ pub struct ShareInfo {
     name: String,
     path: String,
     description: String,
+    current_users: u32,
+    caching_mode: String,            // Manual, Documents, Programs, BranchCache, None
+    folder_enumeration_mode: String, // Unrestricted, AccessBased (ABE)
+}
+
+// Entrée de contrôle d'accès au partage (Get-SmbShareAccess)
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsSmbShareAccess {
+    account_name: String,
+    access_control_type: Option<String>,
+    access_right: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ShareAccessInfo {
+    account_name: String,
+    access_control_type: String, // Allow, Deny
+    access_right: String,        // Full, Change, Read, Custom
+}
+
+// Droit demandé pour un principal lors de la création du partage
+#[derive(Deserialize, Debug, Clone)]
+pub struct ShareAccessEntry {
+    account_name: String,
+    access_right: String, // full, change, read
+}
+
+// Session SMB ouverte sur ce serveur (Get-SmbSession)
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsSmbSession {
+    session_id: u64,
+    client_computer_name: Option<String>,
+    client_user_name: Option<String>,
+    num_opens: Option<u32>,
+    seconds_exists: Option<u64>,
+    seconds_idle: Option<u64>,
+    dialect: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SmbSessionInfo {
+    session_id: u64,
+    client_computer_name: String,
+    client_user_name: String,
+    num_opens: u32,
+    seconds_exists: u64,
+    seconds_idle: u64,
+    dialect: String,
+}
+
+// Fichier ouvert à distance via un partage (Get-SmbOpenFile)
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsSmbOpenFile {
+    file_id: u64,
+    session_id: u64,
+    path: Option<String>,
+    share_relative_path: Option<String>,
+    client_computer_name: Option<String>,
+    client_user_name: Option<String>,
+    locks: Option<u32>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SmbOpenFileInfo {
+    file_id: u64,
+    session_id: u64,
+    path: String,
+    share_relative_path: String,
+    client_computer_name: String,
+    client_user_name: String,
+    locks: u32,
+}
+
+// Normalise un droit de partage vers la valeur attendue par les cmdlets SMB
+fn normalize_access_right(access_right: &str) -> Result<&'static str, String> {
+    match access_right.trim().to_lowercase().as_str() {
+        "full" => Ok("Full"),
+        "change" => Ok("Change"),
+        "read" => Ok("Read"),
+        other => Err(format!("Droit de partage invalide '{}' (full, change, read)", other)),
+    }
+}
+
+async fn run_smb_command(app: &AppHandle, command: &str) -> Result<String, String> {
+    let output = app.shell().command("powershell").args(["-Command", command]).output().await.map_err(|e| e.to_string())?;
+    if !output.status.success() { return Err(String::from_utf8_lossy(&output.stderr).to_string()); }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
 #[command]
 pub async fn list_shares(app: AppHandle) -> Result<Vec<ShareInfo>, String> {
     println!("Real: list_shares() called");
-    let command = "Get-SmbShare | Select-Object Name, Path, Description | ConvertTo-Json -Compress";
+    // Les énumérations sont converties en texte (sinon sérialisées en entiers)
+    let command = "Get-SmbShare | Select-Object Name, Path, Description, CurrentUsers, @{N='CachingMode';E={$_.CachingMode.ToString()}}, @{N='FolderEnumerationMode';E={$_.FolderEnumerationMode.ToString()}} | ConvertTo-Json -Compress";
     let output = app.shell().command("powershell").args(&["-Command", command]).output().await.map_err(|e| e.to_string())?;
     if !output.status.success() { return Err(String::from_utf8_lossy(&output.stderr).to_string()); }
     let json_str = String::from_utf8_lossy(&output.stdout);
@@ -36,19 +135,38 @@ pub async fn list_shares(app: AppHandle) -> Result<Vec<ShareInfo>, String> {
         name: ps.name,
         path: ps.path,
         description: ps.description.unwrap_or_default(),
+        current_users: ps.current_users.unwrap_or(0),
+        caching_mode: ps.caching_mode.unwrap_or_default(),
+        folder_enumeration_mode: ps.folder_enumeration_mode.unwrap_or_default(),
     }).collect();
     Ok(final_shares)
 }
 
 #[command]
-pub async fn create_share(app: AppHandle, name: String, path: String, description: Option<String>) -> Result<(), String> {
+pub async fn create_share(app: AppHandle, name: String, path: String, description: Option<String>, access: Option<Vec<ShareAccessEntry>>) -> Result<(), String> {
     println!("Real: create_share() called");
     // Nécessite admin
-    let desc = description.unwrap_or_default().replace('"', "''");
-    let command = format!("New-SmbShare -Name \"{}\" -Path \"{}\" -Description \"{}\" -FullAccess Everyone", 
-        name.replace('"', "''"), path.replace('"', "''"), desc);
-    let output = app.shell().command("powershell").args(&["-Command", &command]).output().await.map_err(|e| e.to_string())?;
-    if !output.status.success() { return Err(String::from_utf8_lossy(&output.stderr).to_string()); }
+    // Regrouper les principaux par niveau d'accès: -FullAccess 'a','b' -ChangeAccess ... -ReadAccess ...
+    let mut full = Vec::new();
+    let mut change = Vec::new();
+    let mut read = Vec::new();
+    for entry in access.unwrap_or_default() {
+        if entry.account_name.trim().is_empty() { return Err("Nom de compte vide dans la liste d'accès".to_string()); }
+        let account = ps_quote(entry.account_name.trim());
+        match normalize_access_right(&entry.access_right)? {
+            "Full" => full.push(account),
+            "Change" => change.push(account),
+            _ => read.push(account),
+        }
+    }
+    // Sans liste d'accès, New-SmbShare applique son défaut (Everyone en lecture seule)
+    let mut access_parameters = String::new();
+    if !full.is_empty() { access_parameters.push_str(&format!(" -FullAccess {}", full.join(","))); }
+    if !change.is_empty() { access_parameters.push_str(&format!(" -ChangeAccess {}", change.join(","))); }
+    if !read.is_empty() { access_parameters.push_str(&format!(" -ReadAccess {}", read.join(","))); }
+    let command = format!("New-SmbShare -Name {} -Path {} -Description {}{}",
+        ps_quote(&name), ps_quote(&path), ps_quote(&description.unwrap_or_default()), access_parameters);
+    run_smb_command(&app, &command).await?;
     Ok(())
 }
 
@@ -56,8 +174,119 @@ pub async fn create_share(app: AppHandle, name: String, path: String, descriptio
 pub async fn delete_share(app: AppHandle, name: String) -> Result<(), String> {
      println!("Real: delete_share() called");
     // Nécessite admin
-    let command = format!("Remove-SmbShare -Name \"{}\" -Force", name.replace('"', "''"));
-    let output = app.shell().command("powershell").args(&["-Command", &command]).output().await.map_err(|e| e.to_string())?;
+    let command = format!("Remove-SmbShare -Name {} -Force", ps_quote(&name));
+    let output = app.shell().command("powershell").args(["-Command", &command]).output().await.map_err(|e| e.to_string())?;
     if !output.status.success() { return Err(String::from_utf8_lossy(&output.stderr).to_string()); }
     Ok(())
-} 
\ No newline at end of file
+}
+
+#[command]
+pub async fn get_share_access(app: AppHandle, name: String) -> Result<Vec<ShareAccessInfo>, String> {
+    println!("Real: get_share_access('{}') called", name);
+    let command = format!("Get-SmbShareAccess -Name {} | Select-Object AccountName, @{{N='AccessControlType';E={{$_.AccessControlType.ToString()}}}}, @{{N='AccessRight';E={{$_.AccessRight.ToString()}}}} | ConvertTo-Json -Compress", ps_quote(&name));
+    let json_str = run_smb_command(&app, &command).await?;
+    if json_str.trim().is_empty() { return Ok(vec![]); }
+    let parsed: Vec<PsSmbShareAccess> = if json_str.trim().starts_with('[') {
+        serde_json::from_str(&json_str).map_err(|e| e.to_string())?
+    } else {
+        serde_json::from_str::<PsSmbShareAccess>(&json_str).map(|a| vec![a]).map_err(|e| e.to_string())?
+    };
+    let final_access = parsed.into_iter().map(|ps| ShareAccessInfo {
+        account_name: ps.account_name,
+        access_control_type: ps.access_control_type.unwrap_or_default(),
+        access_right: ps.access_right.unwrap_or_default(),
+    }).collect();
+    Ok(final_access)
+}
+
+#[command]
+pub async fn grant_share_access(app: AppHandle, name: String, account_name: String, access_right: String) -> Result<(), String> {
+    println!("Real: grant_share_access('{}', '{}', '{}') called", name, account_name, access_right);
+    // Nécessite admin
+    let right = normalize_access_right(&access_right)?;
+    let command = format!("Grant-SmbShareAccess -Name {} -AccountName {} -AccessRight {} -Force", ps_quote(&name), ps_quote(&account_name), right);
+    run_smb_command(&app, &command).await?;
+    Ok(())
+}
+
+#[command]
+pub async fn revoke_share_access(app: AppHandle, name: String, account_name: String) -> Result<(), String> {
+    println!("Real: revoke_share_access('{}', '{}') called", name, account_name);
+    // Nécessite admin
+    let command = format!("Revoke-SmbShareAccess -Name {} -AccountName {} -Force", ps_quote(&name), ps_quote(&account_name));
+    run_smb_command(&app, &command).await?;
+    Ok(())
+}
+
+// --- Sessions et fichiers ouverts ---
+
+#[command]
+pub async fn list_smb_sessions(app: AppHandle) -> Result<Vec<SmbSessionInfo>, String> {
+    println!("Real: list_smb_sessions() called");
+    // Nécessite admin
+    let command = "Get-SmbSession | Select-Object SessionId, ClientComputerName, ClientUserName, NumOpens, SecondsExists, SecondsIdle, Dialect | ConvertTo-Json -Compress";
+    let json_str = run_smb_command(&app, command).await?;
+    if json_str.trim().is_empty() { return Ok(vec![]); }
+    let parsed: Vec<PsSmbSession> = if json_str.trim().starts_with('[') {
+        serde_json::from_str(&json_str).map_err(|e| e.to_string())?
+    } else {
+        serde_json::from_str::<PsSmbSession>(&json_str).map(|s| vec![s]).map_err(|e| e.to_string())?
+    };
+    let final_sessions = parsed.into_iter().map(|ps| SmbSessionInfo {
+        session_id: ps.session_id,
+        client_computer_name: ps.client_computer_name.unwrap_or_default(),
+        client_user_name: ps.client_user_name.unwrap_or_default(),
+        num_opens: ps.num_opens.unwrap_or(0),
+        seconds_exists: ps.seconds_exists.unwrap_or(0),
+        seconds_idle: ps.seconds_idle.unwrap_or(0),
+        dialect: ps.dialect.unwrap_or_default(),
+    }).collect();
+    Ok(final_sessions)
+}
+
+// `path_filter` restreint la liste aux fichiers dont le chemin contient le texte (ex: "compta.xlsx")
+#[command]
+pub async fn list_open_files(app: AppHandle, path_filter: Option<String>) -> Result<Vec<SmbOpenFileInfo>, String> {
+    println!("Real: list_open_files(filter: {:?}) called", path_filter);
+    // Nécessite admin
+    let filter = match path_filter.as_deref().map(str::trim) {
+        Some(f) if !f.is_empty() => format!(" | Where-Object {{ $_.Path -like ('*' + [WildcardPattern]::Escape({}) + '*') }}", ps_quote(f)),
+        _ => String::new(),
+    };
+    let command = format!("Get-SmbOpenFile{} | Select-Object FileId, SessionId, Path, ShareRelativePath, ClientComputerName, ClientUserName, Locks | ConvertTo-Json -Compress", filter);
+    let json_str = run_smb_command(&app, &command).await?;
+    if json_str.trim().is_empty() { return Ok(vec![]); }
+    let parsed: Vec<PsSmbOpenFile> = if json_str.trim().starts_with('[') {
+        serde_json::from_str(&json_str).map_err(|e| e.to_string())?
+    } else {
+        serde_json::from_str::<PsSmbOpenFile>(&json_str).map(|f| vec![f]).map_err(|e| e.to_string())?
+    };
+    let final_files = parsed.into_iter().map(|ps| SmbOpenFileInfo {
+        file_id: ps.file_id,
+        session_id: ps.session_id,
+        path: ps.path.unwrap_or_default(),
+        share_relative_path: ps.share_relative_path.unwrap_or_default(),
+        client_computer_name: ps.client_computer_name.unwrap_or_default(),
+        client_user_name: ps.client_user_name.unwrap_or_default(),
+        locks: ps.locks.unwrap_or(0),
+    }).collect();
+    Ok(final_files)
+}
+
+#[command]
+pub async fn close_smb_session(app: AppHandle, session_id: u64) -> Result<(), String> {
+    println!("Real: close_smb_session({}) called", session_id);
+    // Nécessite admin. Les fichiers ouverts de la session sont fermés sans sauvegarde côté client.
+    let command = format!("Close-SmbSession -SessionId {} -Force", session_id);
+    run_smb_command(&app, &command).await?;
+    Ok(())
+}
+
+#[command]
+pub async fn close_open_file(app: AppHandle, file_id: u64) -> Result<(), String> {
+    println!("Real: close_open_file({}) called", file_id);
+    // Nécessite admin
+    let command = format!("Close-SmbOpenFile -FileId {} -Force", file_id);
+    run_smb_command(&app, &command).await?;
+    Ok(())
+}
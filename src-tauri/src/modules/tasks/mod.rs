@@ -4,6 +4,14 @@ use tauri::command;
 use tauri::AppHandle;
 use tauri_plugin_shell::ShellExt;
 use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::modules::common::powershell::{ps_quote, secret_env_ref, SECRET_ENV_VAR};
+
+pub mod audit;
+pub mod results;
+
+use results::{decode_task_result, TaskResultInfo};
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
@@ -24,6 +32,699 @@ pub struct TaskInfo {
     last_run_time: String,
     next_run_time: String,
     last_result: String,
+    last_result_info: Option<TaskResultInfo>, // Nom et message du code de résultat
+}
+
+// Comptes de service intégrés: pas de mot de passe, type d'ouverture de session ServiceAccount
+const SERVICE_ACCOUNTS: [&str; 3] = ["SYSTEM", "LOCAL SERVICE", "NETWORK SERVICE"];
+
+const WEEK_DAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+
+// --- Spécification d'une tâche planifiée (envoyée par le frontend) ---
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TaskTriggerSpec {
+    Daily {
+        start: String, // "2026-10-18T03:00:00"
+        days_interval: Option<u32>,
+    },
+    Weekly {
+        start: String,
+        days_of_week: Vec<String>, // "Monday", "Tuesday"...
+        weeks_interval: Option<u32>,
+    },
+    AtLogon {
+        user_id: Option<String>, // Aucun = n'importe quel utilisateur
+    },
+    AtStartup,
+    OnEvent {
+        log: String,              // "System", "Application"...
+        source: Option<String>,   // Nom du fournisseur
+        event_id: Option<u32>,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TaskActionSpec {
+    execute: String,
+    arguments: Option<String>,
+    working_directory: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TaskPrincipalSpec {
+    user_id: String,            // "SYSTEM", "DOMAINE\utilisateur"...
+    logon_type: Option<String>, // Interactive (défaut), S4U; ignoré si un mot de passe est fourni
+    run_level: Option<String>,  // Limited (défaut), Highest
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TaskSpec {
+    name: String,
+    path: Option<String>, // Dossier, "\" par défaut
+    description: Option<String>,
+    triggers: Vec<TaskTriggerSpec>,
+    actions: Vec<TaskActionSpec>,
+    principal: Option<TaskPrincipalSpec>,
+    hidden: Option<bool>,
+}
+
+fn xml_escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('\'', "&apos;").replace('"', "&quot;")
+}
+
+// Dossier de tâche au format attendu par les cmdlets: "\" ou "\Dossier\Sous-dossier\"
+fn normalize_task_path(path: Option<&str>) -> String {
+    let trimmed = path.unwrap_or("").trim().trim_matches('\\');
+    if trimmed.is_empty() {
+        "\\".to_string()
+    } else {
+        format!("\\{}\\", trimmed)
+    }
+}
+
+fn ps_datetime(value: &str) -> String {
+    format!("([datetime]::Parse({}, [Globalization.CultureInfo]::InvariantCulture))", ps_quote(value))
+}
+
+// Requête d'abonnement d'un déclencheur sur événement (format QueryList du journal d'événements)
+fn event_subscription(log: &str, source: Option<&str>, event_id: Option<u32>) -> String {
+    let mut conditions = Vec::new();
+    if let Some(source) = source.filter(|s| !s.trim().is_empty()) {
+        conditions.push(format!("Provider[@Name='{}']", xml_escape(source.trim())));
+    }
+    if let Some(id) = event_id {
+        conditions.push(format!("EventID={}", id));
+    }
+    let select = if conditions.is_empty() {
+        "*".to_string()
+    } else {
+        format!("*[System[{}]]", conditions.join(" and "))
+    };
+    let log = xml_escape(log.trim());
+    // Attributs entre apostrophes: évite les guillemets dans la ligne de commande de PowerShell
+    format!("<QueryList><Query Id='0' Path='{0}'><Select Path='{0}'>{1}</Select></Query></QueryList>", log, select)
+}
+
+// Expression PowerShell produisant un déclencheur
+fn trigger_expression(trigger: &TaskTriggerSpec) -> Result<String, String> {
+    match trigger {
+        TaskTriggerSpec::Daily { start, days_interval } => Ok(format!(
+            "(New-ScheduledTaskTrigger -Daily -At {} -DaysInterval {})",
+            ps_datetime(start), days_interval.unwrap_or(1).max(1)
+        )),
+        TaskTriggerSpec::Weekly { start, days_of_week, weeks_interval } => {
+            if days_of_week.is_empty() {
+                return Err("Un déclencheur hebdomadaire doit spécifier au moins un jour.".to_string());
+            }
+            let mut days = Vec::new();
+            for day in days_of_week {
+                let known = WEEK_DAYS.iter().find(|d| d.eq_ignore_ascii_case(day.trim()))
+                    .ok_or_else(|| format!("Jour de la semaine invalide: '{}'", day))?;
+                days.push(*known);
+            }
+            Ok(format!(
+                "(New-ScheduledTaskTrigger -Weekly -At {} -DaysOfWeek {} -WeeksInterval {})",
+                ps_datetime(start), days.join(","), weeks_interval.unwrap_or(1).max(1)
+            ))
+        }
+        TaskTriggerSpec::AtLogon { user_id } => match user_id.as_deref().filter(|u| !u.trim().is_empty()) {
+            Some(user) => Ok(format!("(New-ScheduledTaskTrigger -AtLogOn -User {})", ps_quote(user.trim()))),
+            None => Ok("(New-ScheduledTaskTrigger -AtLogOn)".to_string()),
+        },
+        TaskTriggerSpec::AtStartup => Ok("(New-ScheduledTaskTrigger -AtStartup)".to_string()),
+        TaskTriggerSpec::OnEvent { log, source, event_id } => {
+            if log.trim().is_empty() {
+                return Err("Un déclencheur sur événement doit spécifier un journal.".to_string());
+            }
+            // New-ScheduledTaskTrigger ne gère pas les événements: instance CIM MSFT_TaskEventTrigger
+            Ok(format!(
+                "$($t = New-CimInstance -CimClass (Get-CimClass -ClassName MSFT_TaskEventTrigger -Namespace Root/Microsoft/Windows/TaskScheduler) -ClientOnly; $t.Enabled = $true; $t.Subscription = {}; $t)",
+                ps_quote(&event_subscription(log, source.as_deref(), *event_id))
+            ))
+        }
+    }
+}
+
+fn action_expression(action: &TaskActionSpec) -> Result<String, String> {
+    if action.execute.trim().is_empty() {
+        return Err("Une action doit spécifier un programme à exécuter.".to_string());
+    }
+    let mut expression = format!("(New-ScheduledTaskAction -Execute {}", ps_quote(action.execute.trim()));
+    if let Some(arguments) = action.arguments.as_deref().filter(|a| !a.trim().is_empty()) {
+        expression.push_str(&format!(" -Argument {}", ps_quote(arguments)));
+    }
+    if let Some(directory) = action.working_directory.as_deref().filter(|d| !d.trim().is_empty()) {
+        expression.push_str(&format!(" -WorkingDirectory {}", ps_quote(directory.trim())));
+    }
+    expression.push(')');
+    Ok(expression)
+}
+
+fn normalize_run_level(run_level: Option<&str>) -> Result<&'static str, String> {
+    match run_level.map(|r| r.trim().to_lowercase()).as_deref() {
+        None | Some("") | Some("limited") => Ok("Limited"),
+        Some("highest") => Ok("Highest"),
+        Some(other) => Err(format!("Niveau d'exécution invalide: '{}' (attendu: Limited, Highest)", other)),
+    }
+}
+
+// Script complet d'enregistrement d'une tâche. Le mot de passe éventuel est lu dans l'environnement.
+fn build_register_task_script(spec: &TaskSpec, has_password: bool, overwrite: bool) -> Result<String, String> {
+    if spec.name.trim().is_empty() || spec.name.contains('\\') {
+        return Err(format!("Nom de tâche invalide: '{}'", spec.name));
+    }
+    if spec.actions.is_empty() {
+        return Err("La tâche doit comporter au moins une action.".to_string());
+    }
+
+    let triggers = spec.triggers.iter().map(trigger_expression).collect::<Result<Vec<_>, _>>()?;
+    let actions = spec.actions.iter().map(action_expression).collect::<Result<Vec<_>, _>>()?;
+
+    let mut script = format!("$actions = @({}); ", actions.join(", "));
+    if triggers.is_empty() {
+        script.push_str("$triggers = @(); ");
+    } else {
+        script.push_str(&format!("$triggers = @({}); ", triggers.join(", ")));
+    }
+    script.push_str(&format!(
+        "$settings = New-ScheduledTaskSettingsSet -Hidden:${}; ",
+        spec.hidden.unwrap_or(false)
+    ));
+
+    let mut register = format!(
+        "Register-ScheduledTask -TaskName {} -TaskPath {} -Action $actions -Settings $settings",
+        ps_quote(spec.name.trim()), ps_quote(&normalize_task_path(spec.path.as_deref()))
+    );
+    if !triggers.is_empty() {
+        register.push_str(" -Trigger $triggers");
+    }
+    if let Some(description) = spec.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        register.push_str(&format!(" -Description {}", ps_quote(description)));
+    }
+
+    match &spec.principal {
+        Some(principal) if !principal.user_id.trim().is_empty() => {
+            let user = principal.user_id.trim();
+            let run_level = normalize_run_level(principal.run_level.as_deref())?;
+            let is_service = SERVICE_ACCOUNTS.iter().any(|a| {
+                let bare = user.rsplit('\\').next().unwrap_or(user);
+                a.eq_ignore_ascii_case(bare)
+            });
+            if is_service {
+                script.push_str(&format!(
+                    "$principal = New-ScheduledTaskPrincipal -UserId {} -LogonType ServiceAccount -RunLevel {}; ",
+                    ps_quote(user), run_level
+                ));
+                register.push_str(" -Principal $principal");
+            } else if has_password {
+                // Exécution que l'utilisateur soit connecté ou non (mot de passe stocké par le planificateur)
+                register.push_str(&format!(
+                    " -User {} -Password {} -RunLevel {}",
+                    ps_quote(user), secret_env_ref(), run_level
+                ));
+            } else {
+                let logon_type = match principal.logon_type.as_deref().map(|l| l.trim().to_lowercase()).as_deref() {
+                    Some("s4u") => "S4U",
+                    _ => "Interactive",
+                };
+                script.push_str(&format!(
+                    "$principal = New-ScheduledTaskPrincipal -UserId {} -LogonType {} -RunLevel {}; ",
+                    ps_quote(user), logon_type, run_level
+                ));
+                register.push_str(" -Principal $principal");
+            }
+        }
+        _ => {}
+    }
+
+    if overwrite {
+        register.push_str(" -Force");
+    }
+    register.push_str(" -ErrorAction Stop");
+    script.push_str(&register);
+    script.push_str(" | Out-Null");
+    Ok(script)
+}
+
+// Exécution de PowerShell, abstraite pour pouvoir vérifier les arguments exacts dans les tests
+trait PowerShellRunner {
+    async fn powershell(&self, args: &[String], password: Option<&str>) -> Result<String, String>;
+}
+
+struct ShellRunner<'a>(&'a AppHandle);
+
+impl PowerShellRunner for ShellRunner<'_> {
+    async fn powershell(&self, args: &[String], password: Option<&str>) -> Result<String, String> {
+        let mut shell_command = self.0.shell().command("powershell").args(args);
+        if let Some(secret) = password {
+            shell_command = shell_command.env(SECRET_ENV_VAR, secret);
+        }
+        let output = shell_command.output().await.map_err(|e| e.to_string())?;
+        if !output.status.success() { return Err(String::from_utf8_lossy(&output.stderr).to_string()); }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+async fn run_task_command(app: &AppHandle, command: &str, password: Option<&str>) -> Result<String, String> {
+    let args = vec!["-Command".to_string(), command.to_string()];
+    ShellRunner(app).powershell(&args, password).await
+}
+
+// --- Opérations sur une tâche (activer, désactiver, exécuter, arrêter) ---
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TaskOperation {
+    Enable,
+    Disable,
+    Run,
+    Stop,
+}
+
+impl TaskOperation {
+    fn parse(operation: &str) -> Result<Self, String> {
+        match operation.trim().to_lowercase().as_str() {
+            "enable" => Ok(TaskOperation::Enable),
+            "disable" => Ok(TaskOperation::Disable),
+            "run" => Ok(TaskOperation::Run),
+            "stop" => Ok(TaskOperation::Stop),
+            other => Err(format!("Opération invalide: '{}' (attendu: enable, disable, run, stop)", other)),
+        }
+    }
+
+    fn cmdlet(self) -> &'static str {
+        match self {
+            TaskOperation::Enable => "Enable-ScheduledTask",
+            TaskOperation::Disable => "Disable-ScheduledTask",
+            TaskOperation::Run => "Start-ScheduledTask",
+            TaskOperation::Stop => "Stop-ScheduledTask",
+        }
+    }
+}
+
+// Tâche désignée par son dossier et son nom: -TaskPath seul agirait sur tout le dossier
+#[derive(Deserialize, Debug, Clone)]
+pub struct TaskRef {
+    path: String,
+    name: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TaskOperationResult {
+    path: String,
+    name: String,
+    success: bool,
+    error: Option<String>,
+}
+
+fn task_operation_args(operation: TaskOperation, task_path: &str, task_name: &str) -> Result<Vec<String>, String> {
+    if task_name.trim().is_empty() {
+        return Err("Le nom de la tâche est requis.".to_string());
+    }
+    Ok(vec![
+        "-Command".to_string(),
+        format!(
+            "{} -TaskPath {} -TaskName {} -ErrorAction Stop | Out-Null",
+            operation.cmdlet(), ps_quote(&normalize_task_path(Some(task_path))), ps_quote(task_name)
+        ),
+    ])
+}
+
+async fn apply_task_operation<R: PowerShellRunner>(runner: &R, operation: TaskOperation, task_path: &str, task_name: &str) -> Result<(), String> {
+    let args = task_operation_args(operation, task_path, task_name)?;
+    runner.powershell(&args, None).await?;
+    Ok(())
+}
+
+// Applique l'opération tâche par tâche: un échec n'interrompt pas le lot
+async fn apply_batch_operation<R: PowerShellRunner>(runner: &R, operation: TaskOperation, tasks: &[TaskRef]) -> Vec<TaskOperationResult> {
+    let mut results = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        let outcome = apply_task_operation(runner, operation, &task.path, &task.name).await;
+        results.push(TaskOperationResult {
+            path: task.path.clone(),
+            name: task.name.clone(),
+            success: outcome.is_ok(),
+            error: outcome.err().map(|e| e.trim().to_string()),
+        });
+    }
+    results
+}
+
+// --- Détails d'une tâche (déclencheurs, actions, compte, paramètres, historique) ---
+
+// Journal d'historique du planificateur (désactivé par défaut sur les postes clients)
+const TASK_HISTORY_LOG: &str = "Microsoft-Windows-TaskScheduler/Operational";
+
+// Événements de l'historique utilisés pour reconstituer les exécutions
+const TASK_HISTORY_EVENT_IDS: [u32; 8] = [100, 101, 102, 103, 111, 200, 201, 203];
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsTaskTrigger {
+    kind: Option<String>, // Nom de classe CIM: MSFT_TaskDailyTrigger...
+    enabled: Option<bool>,
+    start_boundary: Option<String>,
+    end_boundary: Option<String>,
+    interval: Option<String>,
+    duration: Option<String>,
+    delay: Option<String>,
+    days_interval: Option<u32>,
+    weeks_interval: Option<u32>,
+    days_of_week: Option<u32>, // Masque: 1 = dimanche, 2 = lundi, ... 64 = samedi
+    user_id: Option<String>,
+    subscription: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsTaskAction {
+    kind: Option<String>,
+    execute: Option<String>,
+    arguments: Option<String>,
+    working_directory: Option<String>,
+    class_id: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsTaskPrincipal {
+    user_id: Option<String>,
+    group_id: Option<String>,
+    logon_type: Option<String>,
+    run_level: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsTaskSettings {
+    enabled: Option<bool>,
+    hidden: Option<bool>,
+    allow_demand_start: Option<bool>,
+    start_when_available: Option<bool>,
+    run_only_if_network_available: Option<bool>,
+    disallow_start_if_on_batteries: Option<bool>,
+    stop_if_going_on_batteries: Option<bool>,
+    wake_to_run: Option<bool>,
+    execution_time_limit: Option<String>,
+    multiple_instances: Option<String>,
+    restart_count: Option<u32>,
+    restart_interval: Option<String>,
+    priority: Option<u32>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+struct PsTaskHistoryEvent {
+    id: u32,
+    time_created: Option<String>,
+    instance_id: Option<String>,
+    result_code: Option<String>, // Décimal dans l'XML de l'événement
+    action_name: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsTaskDetails {
+    task_name: String,
+    task_path: String,
+    description: Option<String>,
+    author: Option<String>,
+    registration_date: Option<String>,
+    state: Option<String>,
+    #[serde(default)]
+    triggers: Vec<PsTaskTrigger>,
+    #[serde(default)]
+    actions: Vec<PsTaskAction>,
+    principal: Option<PsTaskPrincipal>,
+    settings: Option<PsTaskSettings>,
+    last_run_time: Option<String>,
+    next_run_time: Option<String>,
+    last_task_result: Option<u32>,
+    number_of_missed_runs: Option<u32>,
+    history_enabled: Option<bool>,
+    #[serde(default)]
+    history: Vec<PsTaskHistoryEvent>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TaskTriggerInfo {
+    kind: String, // Daily, Weekly, Logon, Boot, Event, Time, Idle, Registration, SessionStateChange
+    enabled: bool,
+    start_boundary: Option<String>,
+    end_boundary: Option<String>,
+    repetition_interval: Option<String>, // Durée ISO 8601: "PT15M"
+    repetition_duration: Option<String>,
+    delay: Option<String>,
+    days_interval: Option<u32>,
+    weeks_interval: Option<u32>,
+    days_of_week: Vec<String>,
+    user_id: Option<String>,
+    subscription: Option<String>, // Requête des déclencheurs sur événement
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TaskActionInfo {
+    kind: String, // Exec, ComHandler, SendEmail, ShowMessage
+    execute: Option<String>,
+    arguments: Option<String>,
+    working_directory: Option<String>,
+    class_id: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TaskPrincipalInfo {
+    user_id: Option<String>,
+    group_id: Option<String>,
+    logon_type: String, // Interactive, Password, S4U, ServiceAccount...
+    run_level: String,  // Limited, Highest
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TaskSettingsInfo {
+    enabled: bool,
+    hidden: bool,
+    allow_demand_start: bool,
+    start_when_available: bool,
+    run_only_if_network_available: bool,
+    disallow_start_if_on_batteries: bool,
+    stop_if_going_on_batteries: bool,
+    wake_to_run: bool,
+    execution_time_limit: Option<String>,
+    multiple_instances: Option<String>,
+    restart_count: u32,
+    restart_interval: Option<String>,
+    priority: Option<u32>,
+}
+
+// Une exécution reconstituée à partir des événements partageant le même InstanceId
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TaskRunInfo {
+    instance_id: String,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    status: String, // Running, Succeeded, Failed, Terminated
+    action_name: Option<String>,
+    result: Option<TaskResultInfo>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TaskDetails {
+    name: String,
+    path: String,
+    description: Option<String>,
+    author: Option<String>,
+    registration_date: Option<String>,
+    state: String,
+    triggers: Vec<TaskTriggerInfo>,
+    actions: Vec<TaskActionInfo>,
+    principal: Option<TaskPrincipalInfo>,
+    settings: Option<TaskSettingsInfo>,
+    last_run_time: Option<String>,
+    next_run_time: Option<String>,
+    last_result: Option<TaskResultInfo>,
+    number_of_missed_runs: u32,
+    history_enabled: bool, // Historique désactivé = aucune exécution listée
+    runs: Vec<TaskRunInfo>,
+}
+
+// "MSFT_TaskDailyTrigger" -> "Daily", "MSFT_TaskExecAction" -> "Exec"
+fn cim_kind(class_name: Option<&str>, suffix: &str) -> String {
+    let name = class_name.unwrap_or_default();
+    let name = name.strip_prefix("MSFT_Task").unwrap_or(name);
+    name.strip_suffix(suffix).unwrap_or(name).to_string()
+}
+
+fn days_of_week_names(mask: Option<u32>) -> Vec<String> {
+    const DAYS: [&str; 7] = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+    let mask = mask.unwrap_or(0);
+    DAYS.iter().enumerate()
+        .filter(|(i, _)| mask & (1 << i) != 0)
+        .map(|(_, d)| d.to_string())
+        .collect()
+}
+
+fn parse_result_code(value: &str) -> Option<u32> {
+    let value = value.trim();
+    match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => value.parse::<u32>().ok().or_else(|| value.parse::<i32>().ok().map(|v| v as u32)),
+    }
+}
+
+// Regroupe les événements d'historique (du plus récent au plus ancien) par exécution
+fn group_task_runs(events: &[PsTaskHistoryEvent], limit: usize) -> Vec<TaskRunInfo> {
+    let mut order: Vec<String> = Vec::new();
+    let mut runs: HashMap<String, TaskRunInfo> = HashMap::new();
+
+    for event in events {
+        let Some(instance_id) = event.instance_id.clone().filter(|i| !i.is_empty()) else { continue };
+        let run = runs.entry(instance_id.clone()).or_insert_with(|| {
+            order.push(instance_id.clone());
+            TaskRunInfo {
+                instance_id: instance_id.clone(),
+                start_time: None,
+                end_time: None,
+                status: String::new(),
+                action_name: None,
+                result: None,
+            }
+        });
+        let result = event.result_code.as_deref().and_then(parse_result_code).map(decode_task_result);
+        match event.id {
+            100 => run.start_time = event.time_created.clone(),
+            101 | 103 | 203 => {
+                run.status = "Failed".to_string();
+                if result.is_some() { run.result = result; }
+                if run.end_time.is_none() { run.end_time = event.time_created.clone(); }
+            }
+            102 => run.end_time = event.time_created.clone(),
+            111 => {
+                run.status = "Terminated".to_string();
+                run.end_time = event.time_created.clone();
+            }
+            200 if run.action_name.is_none() => run.action_name = event.action_name.clone(),
+            201 => {
+                // Plusieurs actions: on garde le résultat de la dernière (premier événement lu)
+                if run.result.is_none() { run.result = result; }
+                if run.action_name.is_none() { run.action_name = event.action_name.clone(); }
+                if run.end_time.is_none() { run.end_time = event.time_created.clone(); }
+            }
+            _ => {}
+        }
+    }
+
+    order.into_iter().take(limit).filter_map(|id| runs.remove(&id)).map(|mut run| {
+        if run.status.is_empty() {
+            run.status = match (&run.end_time, &run.result) {
+                (None, _) => "Running",
+                (Some(_), Some(result)) if result.is_error() => "Failed",
+                (Some(_), _) => "Succeeded",
+            }.to_string();
+        }
+        run
+    }).collect()
+}
+
+fn task_details_from_ps(parsed: PsTaskDetails, history_count: usize) -> TaskDetails {
+    let runs = group_task_runs(&parsed.history, history_count);
+    TaskDetails {
+        name: parsed.task_name,
+        path: parsed.task_path,
+        description: parsed.description,
+        author: parsed.author,
+        registration_date: parsed.registration_date,
+        state: parsed.state.unwrap_or_default(),
+        triggers: parsed.triggers.into_iter().map(|t| TaskTriggerInfo {
+            kind: cim_kind(t.kind.as_deref(), "Trigger"),
+            enabled: t.enabled.unwrap_or(true),
+            start_boundary: t.start_boundary,
+            end_boundary: t.end_boundary,
+            repetition_interval: t.interval,
+            repetition_duration: t.duration,
+            delay: t.delay,
+            days_interval: t.days_interval,
+            weeks_interval: t.weeks_interval,
+            days_of_week: days_of_week_names(t.days_of_week),
+            user_id: t.user_id,
+            subscription: t.subscription,
+        }).collect(),
+        actions: parsed.actions.into_iter().map(|a| TaskActionInfo {
+            kind: cim_kind(a.kind.as_deref(), "Action"),
+            execute: a.execute,
+            arguments: a.arguments,
+            working_directory: a.working_directory,
+            class_id: a.class_id,
+        }).collect(),
+        principal: parsed.principal.map(|p| TaskPrincipalInfo {
+            user_id: p.user_id,
+            group_id: p.group_id,
+            logon_type: p.logon_type.unwrap_or_default(),
+            run_level: p.run_level.unwrap_or_default(),
+        }),
+        settings: parsed.settings.map(|s| TaskSettingsInfo {
+            enabled: s.enabled.unwrap_or(true),
+            hidden: s.hidden.unwrap_or(false),
+            allow_demand_start: s.allow_demand_start.unwrap_or(true),
+            start_when_available: s.start_when_available.unwrap_or(false),
+            run_only_if_network_available: s.run_only_if_network_available.unwrap_or(false),
+            disallow_start_if_on_batteries: s.disallow_start_if_on_batteries.unwrap_or(false),
+            stop_if_going_on_batteries: s.stop_if_going_on_batteries.unwrap_or(false),
+            wake_to_run: s.wake_to_run.unwrap_or(false),
+            execution_time_limit: s.execution_time_limit,
+            multiple_instances: s.multiple_instances,
+            restart_count: s.restart_count.unwrap_or(0),
+            restart_interval: s.restart_interval,
+            priority: s.priority,
+        }),
+        last_run_time: parsed.last_run_time,
+        next_run_time: parsed.next_run_time,
+        last_result: parsed.last_task_result.map(decode_task_result),
+        number_of_missed_runs: parsed.number_of_missed_runs.unwrap_or(0),
+        history_enabled: parsed.history_enabled.unwrap_or(false),
+        runs,
+    }
+}
+
+// Conversion d'une date PowerShell en ISO 8601 UTC ($null pour les dates « jamais exécutée »)
+const ISO_DATE_SCRIPT: &str = "param($d) if ($d -and $d.Year -gt 2000) { $d.ToUniversalTime().ToString('s') + 'Z' }";
+
+// Objet décrivant la tâche $t (informations d'exécution $i, historique $history), à partir
+// duquel est désérialisé PsTaskDetails. Nécessite aussi $iso et $historyEnabled.
+const TASK_OBJECT_SCRIPT: &str = "[PSCustomObject]@{ \
+            TaskName = $t.TaskName; TaskPath = $t.TaskPath; Description = $t.Description; Author = $t.Author; RegistrationDate = $t.Date; State = \"$($t.State)\"; \
+            Triggers = @($t.Triggers | ForEach-Object { [PSCustomObject]@{ Kind = $_.CimClass.CimClassName; Enabled = $_.Enabled; StartBoundary = $_.StartBoundary; EndBoundary = $_.EndBoundary; \
+                Interval = $_.Repetition.Interval; Duration = $_.Repetition.Duration; Delay = $_.Delay; DaysInterval = $_.DaysInterval; WeeksInterval = $_.WeeksInterval; \
+                DaysOfWeek = $_.DaysOfWeek; UserId = $_.UserId; Subscription = $_.Subscription } }); \
+            Actions = @($t.Actions | ForEach-Object { [PSCustomObject]@{ Kind = $_.CimClass.CimClassName; Execute = $_.Execute; Arguments = $_.Arguments; WorkingDirectory = $_.WorkingDirectory; ClassId = $_.ClassId } }); \
+            Principal = [PSCustomObject]@{ UserId = $t.Principal.UserId; GroupId = $t.Principal.GroupId; LogonType = \"$($t.Principal.LogonType)\"; RunLevel = \"$($t.Principal.RunLevel)\" }; \
+            Settings = [PSCustomObject]@{ Enabled = $t.Settings.Enabled; Hidden = $t.Settings.Hidden; AllowDemandStart = $t.Settings.AllowDemandStart; StartWhenAvailable = $t.Settings.StartWhenAvailable; \
+                RunOnlyIfNetworkAvailable = $t.Settings.RunOnlyIfNetworkAvailable; DisallowStartIfOnBatteries = $t.Settings.DisallowStartIfOnBatteries; StopIfGoingOnBatteries = $t.Settings.StopIfGoingOnBatteries; \
+                WakeToRun = $t.Settings.WakeToRun; ExecutionTimeLimit = $t.Settings.ExecutionTimeLimit; MultipleInstances = \"$($t.Settings.MultipleInstances)\"; \
+                RestartCount = $t.Settings.RestartCount; RestartInterval = $t.Settings.RestartInterval; Priority = $t.Settings.Priority }; \
+            LastRunTime = (& $iso $i.LastRunTime); NextRunTime = (& $iso $i.NextRunTime); LastTaskResult = $i.LastTaskResult; NumberOfMissedRuns = $i.NumberOfMissedRuns; \
+            HistoryEnabled = $historyEnabled; \
+            History = $history \
+        }";
+
+// Filtre XPath sur le nom complet de la tâche. XPath 1.0 n'a pas d'échappement d'apostrophe:
+// pour un nom qui en contient, le filtrage se fait dans PowerShell (plus lent).
+fn task_history_query(full_name: &str, max_events: u32) -> String {
+    let ids = TASK_HISTORY_EVENT_IDS.iter().map(|id| format!("EventID={}", id)).collect::<Vec<_>>().join(" or ");
+    if full_name.contains('\'') {
+        let xpath = format!("*[System[{}]]", ids);
+        format!(
+            "Get-WinEvent -LogName '{}' -FilterXPath {} -MaxEvents 5000 -ErrorAction SilentlyContinue | Where-Object {{ $_.Properties[0].Value -eq {} }} | Select-Object -First {}",
+            TASK_HISTORY_LOG, ps_quote(&xpath), ps_quote(full_name), max_events
+        )
+    } else {
+        let xpath = format!("*[System[{}] and EventData[Data[@Name='TaskName']='{}']]", ids, xml_escape(full_name));
+        format!(
+            "Get-WinEvent -LogName '{}' -FilterXPath {} -MaxEvents {} -ErrorAction SilentlyContinue",
+            TASK_HISTORY_LOG, ps_quote(&xpath), max_events
+        )
+    }
 }
 
 // Helper pour parser date WMI/PowerShell
@@ -38,7 +739,7 @@ fn parse_ps_date(date_value: Option<&Value>) -> String {
 #[command]
 pub async fn list_scheduled_tasks(app: AppHandle) -> Result<Vec<TaskInfo>, String> {
     let command = "Get-ScheduledTask | Select-Object TaskName, TaskPath, State, @{N='LastRunTime';E={$_.LastRunTime}}, @{N='NextRunTime';E={$_.NextRunTime}}, LastTaskResult | ConvertTo-Json -Depth 3 -Compress";
-    let output = app.shell().command("powershell").args(&["-Command", command]).output().await.map_err(|e|e.to_string())?;
+    let output = app.shell().command("powershell").args(["-Command", command]).output().await.map_err(|e|e.to_string())?;
     if !output.status.success() { return Err(String::from_utf8_lossy(&output.stderr).to_string()); }
     let json_str = String::from_utf8_lossy(&output.stdout);
     if json_str.trim().is_empty() { return Ok(vec![]); }
@@ -50,30 +751,331 @@ pub async fn list_scheduled_tasks(app: AppHandle) -> Result<Vec<TaskInfo>, Strin
         last_run_time: parse_ps_date(t.last_run_time.as_ref()),
         next_run_time: parse_ps_date(t.next_run_time.as_ref()),
         last_result: t.last_task_result.map_or("N/A".to_string(), |r| r.to_string()),
+        last_result_info: t.last_task_result.map(decode_task_result),
     }).collect();
     Ok(final_tasks)
 }
 
 #[command]
-pub async fn enable_task(app: AppHandle, task_path: String) -> Result<(), String> {
-    let command = format!("Enable-ScheduledTask -TaskPath \"{}\"", task_path.replace('"',"''"));
-    let output = app.shell().command("powershell").args(&["-Command", &command]).output().await.map_err(|e|e.to_string())?;
-    if !output.status.success() { return Err(String::from_utf8_lossy(&output.stderr).to_string()); }
+pub async fn enable_task(app: AppHandle, task_path: String, task_name: String) -> Result<(), String> {
+    println!("Real: enable_task('{}{}') called", task_path, task_name);
+    apply_task_operation(&ShellRunner(&app), TaskOperation::Enable, &task_path, &task_name).await
+}
+
+#[command]
+pub async fn disable_task(app: AppHandle, task_path: String, task_name: String) -> Result<(), String> {
+    println!("Real: disable_task('{}{}') called", task_path, task_name);
+    apply_task_operation(&ShellRunner(&app), TaskOperation::Disable, &task_path, &task_name).await
+}
+
+#[command]
+pub async fn run_task(app: AppHandle, task_path: String, task_name: String) -> Result<(), String> {
+    println!("Real: run_task('{}{}') called", task_path, task_name);
+    apply_task_operation(&ShellRunner(&app), TaskOperation::Run, &task_path, &task_name).await
+}
+
+#[command]
+pub async fn stop_task(app: AppHandle, task_path: String, task_name: String) -> Result<(), String> {
+    println!("Real: stop_task('{}{}') called", task_path, task_name);
+    apply_task_operation(&ShellRunner(&app), TaskOperation::Stop, &task_path, &task_name).await
+}
+
+// operation: enable, disable, run, stop
+#[command]
+pub async fn batch_task_operation(app: AppHandle, operation: String, tasks: Vec<TaskRef>) -> Result<Vec<TaskOperationResult>, String> {
+    println!("Real: batch_task_operation('{}', {} tâches) called", operation, tasks.len());
+    let operation = TaskOperation::parse(&operation)?;
+    Ok(apply_batch_operation(&ShellRunner(&app), operation, &tasks).await)
+}
+
+#[command]
+pub async fn create_scheduled_task(
+    app: AppHandle,
+    spec: TaskSpec,
+    password: Option<String>,  // Mot de passe du compte d'exécution (jamais journalisé)
+    overwrite: Option<bool>,   // Remplace une tâche existante du même nom (modification)
+) -> Result<(), String> {
+    println!("Real: create_scheduled_task('{}') called", spec.name);
+    // Nécessite admin pour les tâches d'autres comptes ou avec RunLevel Highest
+    let password = password.filter(|p| !p.is_empty());
+    let script = build_register_task_script(&spec, password.is_some(), overwrite.unwrap_or(false))?;
+    run_task_command(&app, &script, password.as_deref()).await?;
     Ok(())
 }
 
 #[command]
-pub async fn disable_task(app: AppHandle, task_path: String) -> Result<(), String> {
-    let command = format!("Disable-ScheduledTask -TaskPath \"{}\"", task_path.replace('"',"''"));
-    let output = app.shell().command("powershell").args(&["-Command", &command]).output().await.map_err(|e|e.to_string())?;
-    if !output.status.success() { return Err(String::from_utf8_lossy(&output.stderr).to_string()); }
+pub async fn delete_scheduled_task(app: AppHandle, task_path: String, task_name: String) -> Result<(), String> {
+    println!("Real: delete_scheduled_task('{}{}') called", task_path, task_name);
+    let command = format!(
+        "Unregister-ScheduledTask -TaskPath {} -TaskName {} -Confirm:$false",
+        ps_quote(&normalize_task_path(Some(&task_path))), ps_quote(&task_name)
+    );
+    run_task_command(&app, &command, None).await?;
     Ok(())
 }
 
+// Retourne la définition XML de la tâche; l'écrit aussi dans file_path (UTF-16, comme schtasks) si fourni
+#[command]
+pub async fn export_task_xml(app: AppHandle, task_path: String, task_name: String, file_path: Option<String>) -> Result<String, String> {
+    println!("Real: export_task_xml('{}{}') called", task_path, task_name);
+    let mut command = format!(
+        "$xml = Export-ScheduledTask -TaskPath {} -TaskName {} -ErrorAction Stop; ",
+        ps_quote(&normalize_task_path(Some(&task_path))), ps_quote(&task_name)
+    );
+    if let Some(file) = file_path.as_deref().filter(|f| !f.trim().is_empty()) {
+        command.push_str(&format!("[IO.File]::WriteAllText({}, $xml, [Text.Encoding]::Unicode); ", ps_quote(file.trim())));
+    }
+    command.push_str("$xml");
+    let xml = run_task_command(&app, &command, None).await?;
+    Ok(xml.trim().to_string())
+}
+
 #[command]
-pub async fn run_task(app: AppHandle, task_path: String) -> Result<(), String> {
-     let command = format!("Start-ScheduledTask -TaskPath \"{}\"", task_path.replace('"',"''"));
-     let output = app.shell().command("powershell").args(&["-Command", &command]).output().await.map_err(|e|e.to_string())?;
-     if !output.status.success() { return Err(String::from_utf8_lossy(&output.stderr).to_string()); }
-     Ok(())
-} 
\ No newline at end of file
+pub async fn import_task_xml(
+    app: AppHandle,
+    file_path: String,
+    task_path: Option<String>,
+    task_name: String,
+    user_id: Option<String>,   // Compte d'exécution à substituer (tâches copiées depuis une autre machine)
+    password: Option<String>,
+    overwrite: Option<bool>,
+) -> Result<(), String> {
+    println!("Real: import_task_xml('{}' -> '{}') called", file_path, task_name);
+    if task_name.trim().is_empty() || task_name.contains('\\') {
+        return Err(format!("Nom de tâche invalide: '{}'", task_name));
+    }
+    let mut command = format!(
+        "Register-ScheduledTask -Xml (Get-Content -LiteralPath {} -Raw) -TaskPath {} -TaskName {}",
+        ps_quote(file_path.trim()), ps_quote(&normalize_task_path(task_path.as_deref())), ps_quote(task_name.trim())
+    );
+    if let Some(user) = user_id.as_deref().filter(|u| !u.trim().is_empty()) {
+        command.push_str(&format!(" -User {}", ps_quote(user.trim())));
+    }
+    let password = password.filter(|p| !p.is_empty());
+    if password.is_some() {
+        command.push_str(&format!(" -Password {}", secret_env_ref()));
+    }
+    if overwrite.unwrap_or(false) {
+        command.push_str(" -Force");
+    }
+    command.push_str(" -ErrorAction Stop | Out-Null");
+    run_task_command(&app, &command, password.as_deref()).await?;
+    Ok(())
+}
+
+#[command]
+pub async fn get_task_details(
+    app: AppHandle,
+    task_path: String,
+    task_name: String,
+    history_count: Option<u32>, // Nombre d'exécutions à retourner (10 par défaut)
+) -> Result<TaskDetails, String> {
+    println!("Real: get_task_details('{}{}') called", task_path, task_name);
+    let path = normalize_task_path(Some(&task_path));
+    let history_count = history_count.unwrap_or(10).clamp(1, 200);
+    // Une exécution produit jusqu'à 6 événements parmi ceux retenus
+    let history_query = task_history_query(&format!("{}{}", path, task_name), history_count * 6);
+
+    let command = format!(
+        "$t = Get-ScheduledTask -TaskPath {path} -TaskName {name} -ErrorAction Stop; \
+        $i = $t | Get-ScheduledTaskInfo; \
+        $iso = {{ {iso} }}; \
+        $historyEnabled = [bool](Get-WinEvent -ListLog '{log}' -ErrorAction SilentlyContinue).IsEnabled; \
+        $history = @({history_query} | ForEach-Object {{ $e = $_; $d = @{{}}; ([xml]$e.ToXml()).Event.EventData.Data | ForEach-Object {{ $d[$_.Name] = $_.'#text' }}; \
+            $instance = if ($d['InstanceId']) {{ $d['InstanceId'] }} else {{ $d['TaskInstanceId'] }}; \
+            [PSCustomObject]@{{ Id = $e.Id; TimeCreated = (& $iso $e.TimeCreated); InstanceId = $instance; ResultCode = $d['ResultCode']; ActionName = $d['ActionName'] }} }}); \
+        {task_object} | ConvertTo-Json -Depth 5 -Compress",
+        path = ps_quote(&path), name = ps_quote(&task_name), iso = ISO_DATE_SCRIPT, history_query = history_query,
+        log = TASK_HISTORY_LOG, task_object = TASK_OBJECT_SCRIPT
+    );
+
+    let json_str = run_task_command(&app, &command, None).await?;
+    let parsed: PsTaskDetails = serde_json::from_str(&json_str)
+        .map_err(|e| format!("Erreur parsing JSON détails de tâche: {}\nJSON: {}", e, json_str))?;
+
+    Ok(task_details_from_ps(parsed, history_count as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::event_viewer::event_xml::parse_event_xml;
+    use std::sync::Mutex;
+
+    // Exécuteur de substitution: enregistre les arguments et échoue pour les tâches demandées
+    struct RecordingRunner {
+        calls: Mutex<Vec<Vec<String>>>,
+        failing_task: Option<&'static str>,
+    }
+
+    impl RecordingRunner {
+        fn new(failing_task: Option<&'static str>) -> Self {
+            RecordingRunner { calls: Mutex::new(Vec::new()), failing_task }
+        }
+    }
+
+    impl PowerShellRunner for RecordingRunner {
+        async fn powershell(&self, args: &[String], _password: Option<&str>) -> Result<String, String> {
+            self.calls.lock().unwrap().push(args.to_vec());
+            match self.failing_task {
+                Some(name) if args[1].contains(&format!("-TaskName '{}'", name)) => Err("Tâche introuvable.\r\n".to_string()),
+                _ => Ok(String::new()),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn single_operation_targets_path_and_name() {
+        let runner = RecordingRunner::new(None);
+        apply_task_operation(&runner, TaskOperation::Disable, "\\Microsoft\\Windows\\Defrag", "ScheduledDefrag").await.unwrap();
+        apply_task_operation(&runner, TaskOperation::Stop, "\\", "L'import").await.unwrap();
+
+        let calls = runner.calls.lock().unwrap();
+        assert_eq!(calls[0], vec![
+            "-Command".to_string(),
+            "Disable-ScheduledTask -TaskPath '\\Microsoft\\Windows\\Defrag\\' -TaskName 'ScheduledDefrag' -ErrorAction Stop | Out-Null".to_string(),
+        ]);
+        assert_eq!(calls[1], vec![
+            "-Command".to_string(),
+            "Stop-ScheduledTask -TaskPath '\\' -TaskName 'L''import' -ErrorAction Stop | Out-Null".to_string(),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn empty_task_name_is_rejected_before_running() {
+        let runner = RecordingRunner::new(None);
+        assert!(apply_task_operation(&runner, TaskOperation::Run, "\\Ops", " ").await.is_err());
+        assert!(runner.calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn batch_reports_each_task_and_continues_after_failure() {
+        let runner = RecordingRunner::new(Some("Missing"));
+        let tasks = vec![
+            TaskRef { path: "\\Ops\\".to_string(), name: "Backup".to_string() },
+            TaskRef { path: "\\Ops\\".to_string(), name: "Missing".to_string() },
+            TaskRef { path: "Ops".to_string(), name: "Cleanup".to_string() },
+        ];
+        let results = apply_batch_operation(&runner, TaskOperation::Run, &tasks).await;
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].success);
+        assert_eq!(results[1], TaskOperationResult {
+            path: "\\Ops\\".to_string(),
+            name: "Missing".to_string(),
+            success: false,
+            error: Some("Tâche introuvable.".to_string()),
+        });
+        assert!(results[2].success);
+
+        let calls = runner.calls.lock().unwrap();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[2][1], "Start-ScheduledTask -TaskPath '\\Ops\\' -TaskName 'Cleanup' -ErrorAction Stop | Out-Null");
+    }
+
+    #[test]
+    fn parses_operation_names() {
+        assert_eq!(TaskOperation::parse("Enable"), Ok(TaskOperation::Enable));
+        assert_eq!(TaskOperation::parse(" stop "), Ok(TaskOperation::Stop));
+        assert!(TaskOperation::parse("delete").is_err());
+    }
+
+    fn history_event(id: u32, time: &str, instance: &str, result_code: Option<&str>) -> PsTaskHistoryEvent {
+        PsTaskHistoryEvent {
+            id,
+            time_created: Some(time.to_string()),
+            instance_id: Some(instance.to_string()),
+            result_code: result_code.map(|r| r.to_string()),
+            action_name: Some("C:\\Tools\\backup.exe".to_string()),
+        }
+    }
+
+    #[test]
+    fn groups_history_events_into_runs() {
+        // Du plus récent au plus ancien, comme Get-WinEvent
+        let events = vec![
+            history_event(100, "2026-10-18T03:00:00Z", "c", None),
+            history_event(102, "2026-10-17T03:05:00Z", "b", None),
+            history_event(201, "2026-10-17T03:05:00Z", "b", Some("2147942402")),
+            history_event(100, "2026-10-17T03:00:00Z", "b", None),
+            history_event(102, "2026-10-16T03:02:00Z", "a", None),
+            history_event(201, "2026-10-16T03:02:00Z", "a", Some("0")),
+            history_event(100, "2026-10-16T03:00:00Z", "a", None),
+        ];
+        let runs = group_task_runs(&events, 10);
+        assert_eq!(runs.len(), 3);
+
+        assert_eq!(runs[0].instance_id, "c");
+        assert_eq!(runs[0].status, "Running");
+
+        assert_eq!(runs[1].status, "Failed");
+        assert_eq!(runs[1].start_time.as_deref(), Some("2026-10-17T03:00:00Z"));
+        assert_eq!(runs[1].end_time.as_deref(), Some("2026-10-17T03:05:00Z"));
+        assert_eq!(runs[1].result, Some(decode_task_result(0x8007_0002)));
+
+        assert_eq!(runs[2].status, "Succeeded");
+        assert_eq!(group_task_runs(&events, 2).len(), 2);
+    }
+
+    // Même correspondance que le script de get_task_details: InstanceId, sinon TaskInstanceId (événements 200/201/203)
+    fn history_event_from_xml(xml: &str) -> PsTaskHistoryEvent {
+        let event = parse_event_xml(xml).unwrap();
+        PsTaskHistoryEvent {
+            id: event.event_id,
+            time_created: event.time_created.clone(),
+            instance_id: event.data("InstanceId").or_else(|| event.data("TaskInstanceId")).map(str::to_string),
+            result_code: event.data("ResultCode").map(str::to_string),
+            action_name: event.data("ActionName").map(str::to_string),
+        }
+    }
+
+    fn task_scheduler_xml(id: u32, time: &str, data: &str) -> String {
+        format!(
+            "<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'><System>\
+             <Provider Name='Microsoft-Windows-TaskScheduler' Guid='{{de7b24ea-73c8-4a09-985d-5bdadcfa9017}}'/>\
+             <EventID>{}</EventID><Level>4</Level><TimeCreated SystemTime='{}'/>\
+             <Channel>Microsoft-Windows-TaskScheduler/Operational</Channel></System><EventData>{}</EventData></Event>",
+            id, time, data
+        )
+    }
+
+    #[test]
+    fn attaches_action_events_to_their_run() {
+        const INSTANCE: &str = "{6f0c2e4a-1d3b-4a6e-9c41-2b7f5d8e9a10}";
+        let events: Vec<PsTaskHistoryEvent> = [
+            task_scheduler_xml(102, "2026-10-17T03:05:01.0000000Z", &format!(
+                "<Data Name='TaskName'>\\Ops\\Backup</Data><Data Name='UserContext'>CORP\\svc-backup</Data>\
+                 <Data Name='InstanceId'>{}</Data>", INSTANCE)),
+            task_scheduler_xml(201, "2026-10-17T03:05:00.0000000Z", &format!(
+                "<Data Name='TaskName'>\\Ops\\Backup</Data><Data Name='TaskInstanceId'>{}</Data>\
+                 <Data Name='ActionName'>C:\\Tools\\backup.exe</Data><Data Name='ResultCode'>2147942402</Data>\
+                 <Data Name='EnginePID'>4120</Data>", INSTANCE)),
+            task_scheduler_xml(200, "2026-10-17T03:00:01.0000000Z", &format!(
+                "<Data Name='TaskName'>\\Ops\\Backup</Data><Data Name='ActionName'>C:\\Tools\\backup.exe</Data>\
+                 <Data Name='TaskInstanceId'>{}</Data><Data Name='EnginePID'>4120</Data>", INSTANCE)),
+            task_scheduler_xml(100, "2026-10-17T03:00:00.0000000Z", &format!(
+                "<Data Name='TaskName'>\\Ops\\Backup</Data><Data Name='UserContext'>CORP\\svc-backup</Data>\
+                 <Data Name='InstanceId'>{}</Data>", INSTANCE)),
+        ].iter().map(|xml| history_event_from_xml(xml)).collect();
+
+        let runs = group_task_runs(&events, 10);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].instance_id, INSTANCE);
+        assert_eq!(runs[0].action_name.as_deref(), Some("C:\\Tools\\backup.exe"));
+        assert_eq!(runs[0].result, Some(decode_task_result(0x8007_0002)));
+        assert_eq!(runs[0].status, "Failed");
+        assert_eq!(runs[0].start_time.as_deref(), Some("2026-10-17T03:00:00.0000000Z"));
+        assert_eq!(runs[0].end_time.as_deref(), Some("2026-10-17T03:05:01.0000000Z"));
+    }
+
+    #[test]
+    fn terminated_run_keeps_its_status() {
+        let events = vec![
+            history_event(111, "2026-10-18T04:00:00Z", "x", None),
+            history_event(100, "2026-10-18T03:00:00Z", "x", None),
+        ];
+        let runs = group_task_runs(&events, 10);
+        assert_eq!(runs[0].status, "Terminated");
+        assert_eq!(runs[0].end_time.as_deref(), Some("2026-10-18T04:00:00Z"));
+    }
+}
@@ -0,0 +1,201 @@
+use serde::Serialize;
+
+// --- Décodage des codes de résultat des tâches planifiées (LastTaskResult, ResultCode) ---
+
+// Un code de résultat peut être: un code de sortie du programme (0, 1, 2...), un HRESULT du
+// planificateur (SCHED_S_* / SCHED_E_*), un HRESULT enveloppant une erreur Win32 (0x8007xxxx)
+// ou un NTSTATUS remonté par le processus (0xC0000xxx).
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TaskResultInfo {
+    code: u32,
+    hex: String,      // "0x80070002"
+    name: String,     // "ERROR_FILE_NOT_FOUND", "SCHED_S_TASK_RUNNING"...
+    message: String,
+    severity: String, // Success, Info, Error
+}
+
+// Codes propres au planificateur de tâches (winerror.h)
+const SCHEDULER_CODES: [(u32, &str, &str); 38] = [
+    (0x0004_1300, "SCHED_S_TASK_READY", "La tâche est prête à être exécutée à sa prochaine heure planifiée."),
+    (0x0004_1301, "SCHED_S_TASK_RUNNING", "La tâche est en cours d'exécution."),
+    (0x0004_1302, "SCHED_S_TASK_DISABLED", "La tâche ne sera pas exécutée car elle est désactivée."),
+    (0x0004_1303, "SCHED_S_TASK_HAS_NOT_RUN", "La tâche n'a pas encore été exécutée."),
+    (0x0004_1304, "SCHED_S_TASK_NO_MORE_RUNS", "Aucune autre exécution n'est planifiée pour cette tâche."),
+    (0x0004_1305, "SCHED_S_TASK_NOT_SCHEDULED", "Une ou plusieurs propriétés nécessaires à la planification n'ont pas été définies."),
+    (0x0004_1306, "SCHED_S_TASK_TERMINATED", "La dernière exécution de la tâche a été interrompue par l'utilisateur."),
+    (0x0004_1307, "SCHED_S_TASK_NO_VALID_TRIGGERS", "La tâche n'a aucun déclencheur ou ses déclencheurs sont désactivés ou non définis."),
+    (0x0004_1308, "SCHED_S_EVENT_TRIGGER", "Les déclencheurs sur événement n'ont pas d'heure d'exécution définie."),
+    (0x0004_131B, "SCHED_S_SOME_TRIGGERS_FAILED", "La tâche est enregistrée mais certains déclencheurs n'ont pas pu être activés."),
+    (0x0004_131C, "SCHED_S_BATCH_LOGON_PROBLEM", "La tâche est enregistrée mais le compte n'a peut-être pas le droit « Ouvrir une session en tant que tâche »."),
+    (0x0004_1325, "SCHED_S_TASK_QUEUED", "La tâche est en file d'attente d'exécution."),
+    (0x8004_1309, "SCHED_E_TRIGGER_NOT_FOUND", "Déclencheur introuvable."),
+    (0x8004_130A, "SCHED_E_TASK_NOT_READY", "Une ou plusieurs propriétés nécessaires à l'exécution de la tâche n'ont pas été définies."),
+    (0x8004_130B, "SCHED_E_TASK_NOT_RUNNING", "Aucune instance de cette tâche n'est en cours d'exécution."),
+    (0x8004_130C, "SCHED_E_SERVICE_NOT_INSTALLED", "Le service Planificateur de tâches n'est pas installé."),
+    (0x8004_130D, "SCHED_E_CANNOT_OPEN_TASK", "L'objet tâche n'a pas pu être ouvert."),
+    (0x8004_130E, "SCHED_E_INVALID_TASK", "L'objet est un objet tâche invalide."),
+    (0x8004_130F, "SCHED_E_ACCOUNT_INFORMATION_NOT_SET", "Les informations de compte sont absentes du planificateur."),
+    (0x8004_1310, "SCHED_E_ACCOUNT_NAME_NOT_FOUND", "Impossible de trouver le nom de compte spécifié."),
+    (0x8004_1311, "SCHED_E_ACCOUNT_DBASE_CORRUPT", "La base des comptes du planificateur est corrompue."),
+    (0x8004_1312, "SCHED_E_NO_SECURITY_SERVICES", "Les services de sécurité ne sont disponibles que sur Windows NT."),
+    (0x8004_1313, "SCHED_E_UNKNOWN_OBJECT_VERSION", "Version d'objet tâche non prise en charge ou invalide."),
+    (0x8004_1314, "SCHED_E_UNSUPPORTED_ACCOUNT_OPTION", "La combinaison de compte et d'options n'est pas prise en charge."),
+    (0x8004_1315, "SCHED_E_SERVICE_NOT_RUNNING", "Le service Planificateur de tâches n'est pas démarré."),
+    (0x8004_1316, "SCHED_E_UNEXPECTEDNODE", "La définition XML de la tâche contient un élément inattendu."),
+    (0x8004_1317, "SCHED_E_NAMESPACE", "La définition XML de la tâche contient un espace de noms invalide."),
+    (0x8004_1318, "SCHED_E_INVALIDVALUE", "La définition XML de la tâche contient une valeur invalide."),
+    (0x8004_1319, "SCHED_E_MISSINGNODE", "La définition XML de la tâche ne contient pas un élément requis."),
+    (0x8004_131A, "SCHED_E_MALFORMEDXML", "La définition XML de la tâche est mal formée."),
+    (0x8004_131D, "SCHED_E_TOO_MANY_NODES", "La définition XML de la tâche contient trop d'éléments du même type."),
+    (0x8004_131E, "SCHED_E_PAST_END_BOUNDARY", "La tâche ne peut pas démarrer après sa date de fin."),
+    (0x8004_131F, "SCHED_E_ALREADY_RUNNING", "Une instance de la tâche est déjà en cours d'exécution."),
+    (0x8004_1320, "SCHED_E_USER_NOT_LOGGED_ON", "La tâche n'a pas démarré car l'utilisateur n'est pas connecté."),
+    (0x8004_1321, "SCHED_E_INVALID_TASK_HASH", "L'image de la tâche est corrompue ou a été modifiée."),
+    (0x8004_1322, "SCHED_E_SERVICE_NOT_AVAILABLE", "Le service Planificateur de tâches n'est pas disponible."),
+    (0x8004_1323, "SCHED_E_SERVICE_TOO_BUSY", "Le service Planificateur de tâches est trop occupé pour traiter la demande."),
+    (0x8004_1326, "SCHED_E_TASK_DISABLED", "La tâche est désactivée."),
+];
+
+// Erreurs Win32 les plus fréquentes pour une tâche (code de sortie direct ou enveloppé en HRESULT)
+const WIN32_CODES: [(u32, &str, &str); 20] = [
+    (0, "ERROR_SUCCESS", "L'opération s'est terminée avec succès."),
+    (1, "ERROR_INVALID_FUNCTION", "Fonction incorrecte (ou le programme s'est terminé avec le code 1)."),
+    (2, "ERROR_FILE_NOT_FOUND", "Le fichier spécifié est introuvable (programme ou script de l'action)."),
+    (3, "ERROR_PATH_NOT_FOUND", "Le chemin d'accès spécifié est introuvable."),
+    (5, "ERROR_ACCESS_DENIED", "Accès refusé."),
+    (193, "ERROR_BAD_EXE_FORMAT", "Le programme n'est pas une application Win32 valide."),
+    (267, "ERROR_DIRECTORY", "Nom de répertoire non valide (vérifier le dossier de démarrage de l'action)."),
+    (1058, "ERROR_SERVICE_DISABLED", "Le service ne peut pas être démarré car il est désactivé."),
+    (1208, "ERROR_EXTENDED_ERROR", "Une erreur étendue s'est produite."),
+    (1245, "ERROR_NOT_LOGGED_ON", "L'opération demandée nécessite une session ouverte."),
+    (1260, "ERROR_ACCESS_DISABLED_BY_POLICY", "Ce programme est bloqué par une stratégie de groupe."),
+    (1326, "ERROR_LOGON_FAILURE", "Nom d'utilisateur ou mot de passe incorrect."),
+    (1327, "ERROR_ACCOUNT_RESTRICTION", "Restriction de compte (mot de passe vide, horaires ou postes autorisés)."),
+    (1330, "ERROR_PASSWORD_EXPIRED", "Le mot de passe du compte d'exécution a expiré."),
+    (1331, "ERROR_ACCOUNT_DISABLED", "Le compte d'exécution est désactivé."),
+    (1385, "ERROR_LOGON_TYPE_NOT_GRANTED", "Le type d'ouverture de session demandé n'est pas accordé (droit « Ouvrir une session en tant que tâche »)."),
+    (1392, "ERROR_FILE_CORRUPT", "Le fichier ou le répertoire est endommagé et illisible."),
+    (1909, "ERROR_ACCOUNT_LOCKED_OUT", "Le compte d'exécution est verrouillé."),
+    (1907, "ERROR_PASSWORD_MUST_CHANGE", "Le mot de passe du compte d'exécution doit être changé."),
+    (4320, "ERROR_REQUEST_REFUSED", "L'opérateur ou l'administrateur a refusé la demande (tâche arrêtée ou déjà en cours)."),
+];
+
+// NTSTATUS remontés comme code de sortie d'un processus terminé anormalement
+const NTSTATUS_CODES: [(u32, &str, &str); 6] = [
+    (0xC000_0005, "STATUS_ACCESS_VIOLATION", "Le programme s'est arrêté sur une violation d'accès mémoire."),
+    (0xC000_0135, "STATUS_DLL_NOT_FOUND", "Une DLL requise par le programme est introuvable."),
+    (0xC000_0142, "STATUS_DLL_INIT_FAILED", "L'initialisation d'une DLL a échoué (souvent: session de bureau indisponible)."),
+    (0xC000_013A, "STATUS_CONTROL_C_EXIT", "Le programme a été interrompu (Ctrl+C ou arrêt de la tâche)."),
+    (0xC000_00FD, "STATUS_STACK_OVERFLOW", "Le programme s'est arrêté sur un dépassement de pile."),
+    (0xE043_4352, "CLR_EXCEPTION", "Exception .NET non gérée dans le programme."),
+];
+
+const FACILITY_WIN32_MASK: u32 = 0xFFFF_0000;
+const FACILITY_WIN32_PREFIX: u32 = 0x8007_0000;
+
+impl TaskResultInfo {
+    pub fn is_error(&self) -> bool {
+        self.severity == "Error"
+    }
+}
+
+fn lookup(table: &[(u32, &'static str, &'static str)], code: u32) -> Option<(&'static str, &'static str)> {
+    table.iter().find(|(c, _, _)| *c == code).map(|(_, name, message)| (*name, *message))
+}
+
+fn result_info(code: u32, name: &str, message: &str, severity: &str) -> TaskResultInfo {
+    TaskResultInfo {
+        code,
+        hex: format!("0x{:08X}", code),
+        name: name.to_string(),
+        message: message.to_string(),
+        severity: severity.to_string(),
+    }
+}
+
+pub fn decode_task_result(code: u32) -> TaskResultInfo {
+    if code == 0 {
+        return result_info(code, "S_OK", "L'opération s'est terminée avec succès.", "Success");
+    }
+    if let Some((name, message)) = lookup(&SCHEDULER_CODES, code) {
+        // Bit de sévérité à 0: code de statut informatif (SCHED_S_*)
+        let severity = if code & 0x8000_0000 == 0 { "Info" } else { "Error" };
+        return result_info(code, name, message, severity);
+    }
+    if code & FACILITY_WIN32_MASK == FACILITY_WIN32_PREFIX {
+        let win32 = code & 0xFFFF;
+        return match lookup(&WIN32_CODES, win32) {
+            Some((name, message)) => result_info(code, &format!("HRESULT_FROM_WIN32({})", name), message, "Error"),
+            None => result_info(code, &format!("HRESULT_FROM_WIN32({})", win32),
+                &format!("Erreur Win32 {} (non répertoriée).", win32), "Error"),
+        };
+    }
+    if let Some((name, message)) = lookup(&NTSTATUS_CODES, code) {
+        return result_info(code, name, message, "Error");
+    }
+    if let Some((name, message)) = lookup(&WIN32_CODES, code) {
+        return result_info(code, name, message, "Error");
+    }
+    if code < 0x1_0000 {
+        return result_info(code, "EXIT_CODE", &format!("Le programme s'est terminé avec le code de sortie {}.", code), "Error");
+    }
+    result_info(code, "UNKNOWN", "Code de résultat inconnu.", "Error")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_success_and_scheduler_status_codes() {
+        let ok = decode_task_result(0);
+        assert_eq!(ok.name, "S_OK");
+        assert_eq!(ok.severity, "Success");
+
+        let running = decode_task_result(267009); // 0x41301
+        assert_eq!(running.name, "SCHED_S_TASK_RUNNING");
+        assert_eq!(running.hex, "0x00041301");
+        assert_eq!(running.severity, "Info");
+
+        let never_run = decode_task_result(0x41303);
+        assert_eq!(never_run.name, "SCHED_S_TASK_HAS_NOT_RUN");
+        assert_eq!(never_run.severity, "Info");
+    }
+
+    #[test]
+    fn decodes_scheduler_error_codes() {
+        let already = decode_task_result(0x8004_131F);
+        assert_eq!(already.name, "SCHED_E_ALREADY_RUNNING");
+        assert_eq!(already.severity, "Error");
+        assert_eq!(decode_task_result(2147750687).name, "SCHED_E_ALREADY_RUNNING");
+    }
+
+    #[test]
+    fn decodes_win32_wrapped_in_hresult() {
+        // Valeur affichée telle quelle par Get-ScheduledTaskInfo
+        let not_found = decode_task_result(2147942402);
+        assert_eq!(not_found.hex, "0x80070002");
+        assert_eq!(not_found.name, "HRESULT_FROM_WIN32(ERROR_FILE_NOT_FOUND)");
+        assert_eq!(not_found.severity, "Error");
+
+        assert_eq!(decode_task_result(0x8007_010B).name, "HRESULT_FROM_WIN32(ERROR_DIRECTORY)");
+        assert_eq!(decode_task_result(0x8007_10E0).name, "HRESULT_FROM_WIN32(ERROR_REQUEST_REFUSED)");
+
+        let unlisted = decode_task_result(0x8007_0057);
+        assert_eq!(unlisted.name, "HRESULT_FROM_WIN32(87)");
+    }
+
+    #[test]
+    fn decodes_ntstatus_and_plain_exit_codes() {
+        assert_eq!(decode_task_result(0xC000_013A).name, "STATUS_CONTROL_C_EXIT");
+        assert_eq!(decode_task_result(3221225786).name, "STATUS_CONTROL_C_EXIT");
+        assert_eq!(decode_task_result(1).name, "ERROR_INVALID_FUNCTION");
+
+        let exit = decode_task_result(42);
+        assert_eq!(exit.name, "EXIT_CODE");
+        assert!(exit.message.contains("42"));
+
+        assert_eq!(decode_task_result(0x1234_5678).name, "UNKNOWN");
+    }
+}
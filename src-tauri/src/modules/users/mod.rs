@@ -4,6 +4,10 @@ use tauri::command;
 use tauri::AppHandle;
 use tauri_plugin_shell::ShellExt;
 use serde_json::Value;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::modules::common::csv::csv_field;
+use crate::modules::common::powershell::{ps_quote, secure_password_preamble, SECRET_ENV_VAR};
 
 // Structure pour parser le JSON de Get-LocalUser
 #[derive(Deserialize, Debug)]
@@ -13,9 +17,14 @@ struct PsLocalUser {
     full_name: Option<String>,
     description: Option<String>,
     enabled: bool,
-    // Les dates JSON de PowerShell peuvent être complexes, on les prend comme String pour l'instant
-    // PasswordLastSet: Option<String>,
     sid: Option<Value>, // SID est un objet complexe, on le prend comme Value
+    // Les dates sont décodées par parse_ps_json_date (format variable selon la version de PowerShell)
+    last_logon: Option<Value>,
+    password_last_set: Option<Value>,
+    password_expires: Option<Value>,
+    account_expires: Option<Value>,
+    password_required: Option<bool>,
+    principal_source: Option<String>,
 }
 
 // Structure finale retournée au frontend
@@ -25,8 +34,14 @@ pub struct LocalUserInfo {
     full_name: String,
     description: String,
     enabled: bool,
-    // password_last_set: String,
     sid: String, // On va juste afficher le SID comme string
+    // Dates ISO 8601 UTC, None si jamais / sans objet
+    last_logon: Option<String>,
+    password_last_set: Option<String>,
+    password_expires: Option<String>, // None: le mot de passe n'expire jamais
+    account_expires: Option<String>,  // None: le compte n'expire jamais
+    password_required: bool,
+    principal_source: String, // Local, MicrosoftAccount, AzureAD...
 }
 
 // --- Structs pour Groupes Locaux ---
@@ -38,6 +53,7 @@ struct PsLocalGroup {
     name: String,
     description: Option<String>,
     sid: Option<Value>,
+    member_count: Option<u32>,
 }
 
 // Structure finale pour le groupe local
@@ -46,6 +62,161 @@ pub struct LocalGroupInfo {
     name: String,
     description: String,
     sid: String,
+    member_count: u32,
+}
+
+// Structure pour parser le JSON de Get-LocalGroupMember
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsLocalGroupMember {
+    name: String,
+    object_class: Option<String>,
+    principal_source: Option<String>,
+    sid: Option<Value>,
+}
+
+// Structure finale pour un membre de groupe local
+#[derive(Serialize, Debug, Clone)]
+pub struct LocalGroupMemberInfo {
+    name: String,
+    object_class: String,     // User, Group
+    principal_source: String, // Local, ActiveDirectory, AzureAD, MicrosoftAccount
+    sid: String,
+}
+
+// --- Helpers ---
+
+// Exécute une commande *-LocalUser / *-LocalGroup, avec un mot de passe optionnel passé hors ligne de commande
+async fn run_local_account_command(app: &AppHandle, cmdlet: &str, command: &str, password: Option<&str>) -> Result<(), String> {
+    let mut shell_command = app.shell()
+        .command("powershell")
+        .args(["-Command", command]);
+    if let Some(secret) = password {
+        shell_command = shell_command.env(SECRET_ENV_VAR, secret);
+    }
+
+    let output = shell_command
+        .output()
+        .await
+        .map_err(|e| format!("Erreur lors de l'exécution de {}: {}", cmdlet, e))?;
+
+    if !output.status.success() {
+        return Err(format!("{} a échoué: {:?} \nErreur: {}",
+            cmdlet, output.status, String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}
+
+// --- Dates PowerShell ---
+
+// Décode une date produite par ConvertTo-Json en millisecondes Unix (UTC). Formats rencontrés:
+// "\/Date(1599214542137)\/" (Windows PowerShell 5.1), objet {"value": ..., "DateTime": ...}
+// quand la date porte des propriétés étendues, et "2020-09-04T10:15:42.137+02:00" (PowerShell 7).
+fn parse_ps_json_date(value: Option<&Value>) -> Option<i64> {
+    let value = value?;
+    if let Some(inner) = value.get("value") {
+        return parse_ps_json_date(Some(inner));
+    }
+    let text = value.as_str()?.trim();
+    if let Some(rest) = text.strip_prefix("/Date(") {
+        // Un éventuel décalage ("+0200") suit les millisecondes, qui sont déjà en UTC
+        let end = rest.char_indices()
+            .find(|&(i, c)| !(c.is_ascii_digit() || (i == 0 && c == '-')))
+            .map_or(rest.len(), |(i, _)| i);
+        return rest[..end].parse().ok();
+    }
+    parse_iso8601_ms(text)
+}
+
+// AAAA-MM-JJ[THH:MM:SS[.fff...]][Z|±HH:MM]; une heure sans fuseau est considérée comme UTC
+pub(crate) fn parse_iso8601_ms(text: &str) -> Option<i64> {
+    let bytes = text.as_bytes();
+    if bytes.len() < 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+    let year: i64 = text.get(0..4)?.parse().ok()?;
+    let month: u32 = text.get(5..7)?.parse().ok()?;
+    let day: u32 = text.get(8..10)?.parse().ok()?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    let mut ms = days_from_civil(year, month, day) * 86_400_000;
+
+    let rest = &text[10..];
+    if rest.is_empty() {
+        return Some(ms);
+    }
+    let rest = rest.strip_prefix('T').or_else(|| rest.strip_prefix(' '))?;
+    let hour: i64 = rest.get(0..2)?.parse().ok()?;
+    let minute: i64 = rest.get(3..5)?.parse().ok()?;
+    let second: i64 = rest.get(6..8)?.parse().ok()?;
+    if hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    ms += (hour * 3600 + minute * 60 + second) * 1000;
+
+    let mut rest = &rest[8..];
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits_len = fraction.chars().take_while(|c| c.is_ascii_digit()).count();
+        let millis = format!("{:0<3}", &fraction[..digits_len.min(3)]);
+        ms += millis.parse::<i64>().ok()?;
+        rest = &fraction[digits_len..];
+    }
+
+    match rest {
+        "" | "Z" => Some(ms),
+        _ => {
+            let sign = match rest.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let offset_hours: i64 = rest.get(1..3)?.parse().ok()?;
+            let offset_minutes: i64 = rest.get(4..6)?.parse().ok()?;
+            Some(ms - sign * (offset_hours * 60 + offset_minutes) * 60_000)
+        }
+    }
+}
+
+pub(crate) fn format_iso8601_ms(ms: i64) -> String {
+    let days = ms.div_euclid(86_400_000);
+    let seconds = ms.rem_euclid(86_400_000) / 1000;
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day, seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Conversions calendrier grégorien <-> jours depuis 1970-01-01 (algorithmes de H. Hinnant)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = (if year >= 0 { year } else { year - 399 }) / 400;
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = (if days >= 0 { days } else { days - 146_096 }) / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
 }
 
 #[command]
@@ -53,8 +224,8 @@ pub async fn list_local_users(app: AppHandle) -> Result<Vec<LocalUserInfo>, Stri
     println!("Real: list_local_users() called");
 
     // Sélectionner les propriétés voulues et convertir en JSON
-    // Note: PasswordLastSet peut être problématique à parser directement
-    let command = "Get-LocalUser | Select-Object Name, FullName, Description, Enabled, SID | ConvertTo-Json -Depth 3 -Compress";
+    // PrincipalSource est une énumération: la convertir en texte (sinon sérialisée en entier)
+    let command = "Get-LocalUser | Select-Object Name, FullName, Description, Enabled, SID, LastLogon, PasswordLastSet, PasswordExpires, AccountExpires, PasswordRequired, @{N='PrincipalSource';E={$_.PrincipalSource.ToString()}} | ConvertTo-Json -Depth 3 -Compress";
 
     let output = app.shell()
         .command("powershell")
@@ -99,8 +270,13 @@ pub async fn list_local_users(app: AppHandle) -> Result<Vec<LocalUserInfo>, Stri
             full_name: ps_user.full_name.unwrap_or_default(),
             description: ps_user.description.unwrap_or_default(),
             enabled: ps_user.enabled,
-            // password_last_set: ps_user.PasswordLastSet.unwrap_or_else(|| "N/A".to_string()),
             sid: sid_string,
+            last_logon: parse_ps_json_date(ps_user.last_logon.as_ref()).map(format_iso8601_ms),
+            password_last_set: parse_ps_json_date(ps_user.password_last_set.as_ref()).map(format_iso8601_ms),
+            password_expires: parse_ps_json_date(ps_user.password_expires.as_ref()).map(format_iso8601_ms),
+            account_expires: parse_ps_json_date(ps_user.account_expires.as_ref()).map(format_iso8601_ms),
+            password_required: ps_user.password_required.unwrap_or(true),
+            principal_source: ps_user.principal_source.unwrap_or_else(|| "Unknown".to_string()),
         }
     }).collect();
 
@@ -113,7 +289,8 @@ pub async fn list_local_groups(app: AppHandle) -> Result<Vec<LocalGroupInfo>, St
     println!("Real: list_local_groups() called");
 
     // Sélectionner les propriétés voulues et convertir en JSON
-    let command = "Get-LocalGroup | Select-Object Name, Description, SID | ConvertTo-Json -Depth 3 -Compress";
+    // Le nombre de membres passe par ADSI: Get-LocalGroupMember échoue dès qu'un membre est un SID orphelin
+    let command = "Get-LocalGroup | Select-Object Name, Description, SID, @{N='MemberCount';E={ try { @(([ADSI]('WinNT://' + $env:COMPUTERNAME + '/' + $_.Name + ',group')).psbase.Invoke('Members')).Count } catch { 0 } }} | ConvertTo-Json -Depth 3 -Compress";
 
     let output = app.shell()
         .command("powershell")
@@ -157,6 +334,7 @@ pub async fn list_local_groups(app: AppHandle) -> Result<Vec<LocalGroupInfo>, St
             name: ps_group.name,
             description: ps_group.description.unwrap_or_default(),
             sid: sid_string,
+            member_count: ps_group.member_count.unwrap_or(0),
         }
     }).collect();
 
@@ -184,27 +362,34 @@ pub async fn add_local_user(
     }
 
     // Important: Nécessite des privilèges admin
-    // Construire les parties de la commande
-    let safe_user_name = user_name.replace('"', "''");
-    let safe_full_name = full_name.unwrap_or_default().replace('"', "''");
-    let safe_description = description.unwrap_or_default().replace('"', "''");
-    // Échapper le mot de passe pour l'interpolation dans la chaîne PowerShell
-    let safe_password = password.replace("\"", "\"\"").replace("'", "''");
-
-    let part1 = format!("$Password = ConvertTo-SecureString -String \"{safe_password}\" -AsPlainText -Force;");
-    let part2 = format!("New-LocalUser -Name \"{safe_user_name}\" -Password $Password -FullName \"{safe_full_name}\" -Description \"{safe_description}\"");
-    // Joindre les parties avec un point-virgule (ou autre séparateur si nécessaire)
-    let command = vec![part1, part2].join(" "); 
+    // Le mot de passe transite par l'environnement du processus, jamais par la ligne de commande
+    let command = format!(
+        "{} New-LocalUser -Name {} -Password $Password -FullName {} -Description {}",
+        secure_password_preamble(),
+        ps_quote(&user_name),
+        ps_quote(&full_name.unwrap_or_default()),
+        ps_quote(&description.unwrap_or_default())
+    );
+
+    run_local_account_command(&app, "New-LocalUser", &command, Some(&password)).await
+}
+
+#[command]
+pub async fn delete_local_user(app: AppHandle, user_name: String) -> Result<(), String> {
+    println!("Real: delete_local_user('{}') called", user_name);
+
+    // Important: Nécessite des privilèges admin
+    let command = format!("Remove-LocalUser -Name {}", ps_quote(&user_name));
 
     let output = app.shell()
         .command("powershell")
-        .args(&["-Command", &command]) // Passer la commande complète
+        .args(["-Command", &command])
         .output()
         .await
-        .map_err(|e| format!("Erreur lors de l'exécution de New-LocalUser: {}", e))?;
+        .map_err(|e| format!("Erreur lors de l'exécution de Remove-LocalUser: {}", e))?;
 
     if !output.status.success() {
-        return Err(format!("New-LocalUser a échoué: {:?} \nErreur: {}", 
+        return Err(format!("Remove-LocalUser a échoué: {:?} \nErreur: {}", 
             output.status, String::from_utf8_lossy(&output.stderr)));
     } else {
         Ok(())
@@ -212,26 +397,685 @@ pub async fn add_local_user(
 }
 
 #[command]
-pub async fn delete_local_user(app: AppHandle, user_name: String) -> Result<(), String> {
-    println!("Real: delete_local_user('{}') called", user_name);
+pub async fn enable_local_user(app: AppHandle, user_name: String) -> Result<(), String> {
+    println!("Real: enable_local_user('{}') called", user_name);
+    // Important: Nécessite des privilèges admin
+    let command = format!("Enable-LocalUser -Name {}", ps_quote(&user_name));
+    run_local_account_command(&app, "Enable-LocalUser", &command, None).await
+}
+
+#[command]
+pub async fn disable_local_user(app: AppHandle, user_name: String) -> Result<(), String> {
+    println!("Real: disable_local_user('{}') called", user_name);
+    // Important: Nécessite des privilèges admin
+    let command = format!("Disable-LocalUser -Name {}", ps_quote(&user_name));
+    run_local_account_command(&app, "Disable-LocalUser", &command, None).await
+}
+
+#[command]
+pub async fn rename_local_user(app: AppHandle, user_name: String, new_name: String) -> Result<(), String> {
+    println!("Real: rename_local_user('{}' -> '{}') called", user_name, new_name);
+
+    if new_name.trim().is_empty() {
+        return Err("Le nouveau nom d'utilisateur ne peut pas être vide.".to_string());
+    }
+
+    // Important: Nécessite des privilèges admin
+    let command = format!("Rename-LocalUser -Name {} -NewName {}", ps_quote(&user_name), ps_quote(new_name.trim()));
+    run_local_account_command(&app, "Rename-LocalUser", &command, None).await
+}
+
+#[command]
+pub async fn set_local_user_password(app: AppHandle, user_name: String, password: String) -> Result<(), String> {
+    // Ne jamais journaliser le mot de passe
+    println!("Real: set_local_user_password('{}') called", user_name);
+
+    if password.is_empty() {
+        return Err("Le mot de passe ne peut pas être vide.".to_string());
+    }
 
     // Important: Nécessite des privilèges admin
-    let safe_user_name = user_name.replace('"', "''");
-    let command = format!("Remove-LocalUser -Name \"{safe_user_name}\"");
+    let command = format!("{} Set-LocalUser -Name {} -Password $Password", secure_password_preamble(), ps_quote(&user_name));
+    run_local_account_command(&app, "Set-LocalUser", &command, Some(&password)).await
+}
+
+#[command]
+pub async fn update_local_user(
+    app: AppHandle,
+    user_name: String,
+    full_name: Option<String>,
+    description: Option<String>,
+    account_expires: Option<String>, // Date ISO 8601 (ex: 2025-12-31), chaîne vide = n'expire jamais
+    password_never_expires: Option<bool>,
+    user_may_change_password: Option<bool>,
+) -> Result<(), String> {
+    println!("Real: update_local_user('{}') called", user_name);
+
+    // Ne transmettre à Set-LocalUser que les propriétés fournies
+    let mut parameters = Vec::new();
+    if let Some(full_name) = full_name {
+        parameters.push(format!("-FullName {}", ps_quote(&full_name)));
+    }
+    if let Some(description) = description {
+        parameters.push(format!("-Description {}", ps_quote(&description)));
+    }
+    if let Some(expires) = account_expires {
+        if expires.trim().is_empty() {
+            parameters.push("-AccountNeverExpires".to_string());
+        } else {
+            parameters.push(format!(
+                "-AccountExpires ([datetime]::Parse({}, [Globalization.CultureInfo]::InvariantCulture))",
+                ps_quote(expires.trim())
+            ));
+        }
+    }
+    if let Some(never_expires) = password_never_expires {
+        parameters.push(format!("-PasswordNeverExpires ${}", never_expires));
+    }
+    if let Some(may_change) = user_may_change_password {
+        parameters.push(format!("-UserMayChangePassword ${}", may_change));
+    }
+
+    if parameters.is_empty() {
+        return Err("Aucune propriété à modifier.".to_string());
+    }
+
+    // Important: Nécessite des privilèges admin
+    let command = format!("Set-LocalUser -Name {} {}", ps_quote(&user_name), parameters.join(" "));
+    run_local_account_command(&app, "Set-LocalUser", &command, None).await
+}
+
+// --- Commandes Groupes Locaux ---
+
+#[command]
+pub async fn get_local_group_members(app: AppHandle, group_name: String) -> Result<Vec<LocalGroupMemberInfo>, String> {
+    println!("Real: get_local_group_members('{}') called", group_name);
+
+    // PrincipalSource et ObjectClass sont convertis en texte (sinon sérialisés en entiers)
+    let command = format!(
+        "Get-LocalGroupMember -Group {} | Select-Object Name, @{{N='ObjectClass';E={{$_.ObjectClass.ToString()}}}}, @{{N='PrincipalSource';E={{$_.PrincipalSource.ToString()}}}}, SID | ConvertTo-Json -Depth 3 -Compress",
+        ps_quote(&group_name)
+    );
 
     let output = app.shell()
         .command("powershell")
-        .args(&["-Command", &command])
+        .args(["-Command", &command])
         .output()
         .await
-        .map_err(|e| format!("Erreur lors de l'exécution de Remove-LocalUser: {}", e))?;
+        .map_err(|e| format!("Erreur lors de l'exécution de Get-LocalGroupMember: {}", e))?;
 
     if !output.status.success() {
-        return Err(format!("Remove-LocalUser a échoué: {:?} \nErreur: {}", 
+        return Err(format!("Get-LocalGroupMember a échoué: {:?} \nErreur: {}",
             output.status, String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let members_json_str = String::from_utf8_lossy(&output.stdout);
+    if members_json_str.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    let parsed_members: Vec<PsLocalGroupMember> = if members_json_str.trim().starts_with('[') {
+        serde_json::from_str(&members_json_str)
+            .map_err(|e| format!("Erreur parsing JSON (tableau) membres groupe local: {}\nJSON: {}", e, members_json_str))?
     } else {
-        Ok(())
+        serde_json::from_str::<PsLocalGroupMember>(&members_json_str)
+            .map(|member| vec![member])
+            .map_err(|e| format!("Erreur parsing JSON (objet unique) membres groupe local: {}\nJSON: {}", e, members_json_str))?
+    };
+
+    let final_members = parsed_members.into_iter().map(|ps_member| {
+        let sid_string = ps_member.sid
+            .as_ref()
+            .and_then(|v| v.get("Value"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("N/A")
+            .to_string();
+
+        LocalGroupMemberInfo {
+            name: ps_member.name,
+            object_class: ps_member.object_class.unwrap_or_default(),
+            principal_source: ps_member.principal_source.unwrap_or_else(|| "Unknown".to_string()),
+            sid: sid_string,
+        }
+    }).collect();
+
+    Ok(final_members)
+}
+
+// `member` accepte un compte local ("jdupont"), un principal de domaine ("DOMAINE\jdupont",
+// "jdupont@domaine.local") ou un SID
+#[command]
+pub async fn add_local_group_member(app: AppHandle, group_name: String, member: String) -> Result<(), String> {
+    println!("Real: add_local_group_member(group: '{}', member: '{}') called", group_name, member);
+
+    if member.trim().is_empty() {
+        return Err("Le membre ne peut pas être vide.".to_string());
+    }
+
+    // Important: Nécessite des privilèges admin
+    let command = format!("Add-LocalGroupMember -Group {} -Member {}", ps_quote(&group_name), ps_quote(member.trim()));
+    run_local_account_command(&app, "Add-LocalGroupMember", &command, None).await
+}
+
+#[command]
+pub async fn remove_local_group_member(app: AppHandle, group_name: String, member: String) -> Result<(), String> {
+    println!("Real: remove_local_group_member(group: '{}', member: '{}') called", group_name, member);
+
+    if member.trim().is_empty() {
+        return Err("Le membre ne peut pas être vide.".to_string());
+    }
+
+    // Important: Nécessite des privilèges admin
+    let command = format!("Remove-LocalGroupMember -Group {} -Member {}", ps_quote(&group_name), ps_quote(member.trim()));
+    run_local_account_command(&app, "Remove-LocalGroupMember", &command, None).await
+}
+
+#[command]
+pub async fn create_local_group(app: AppHandle, group_name: String, description: Option<String>) -> Result<(), String> {
+    println!("Real: create_local_group('{}') called", group_name);
+
+    if group_name.trim().is_empty() {
+        return Err("Le nom du groupe ne peut pas être vide.".to_string());
+    }
+
+    // Important: Nécessite des privilèges admin
+    let command = format!(
+        "New-LocalGroup -Name {} -Description {}",
+        ps_quote(group_name.trim()),
+        ps_quote(&description.unwrap_or_default())
+    );
+    run_local_account_command(&app, "New-LocalGroup", &command, None).await
+}
+
+#[command]
+pub async fn delete_local_group(app: AppHandle, group_name: String) -> Result<(), String> {
+    println!("Real: delete_local_group('{}') called", group_name);
+
+    // Important: Nécessite des privilèges admin
+    let command = format!("Remove-LocalGroup -Name {}", ps_quote(&group_name));
+    run_local_account_command(&app, "Remove-LocalGroup", &command, None).await
+}
+
+// --- Droits utilisateur (User Rights Assignment) ---
+
+// Structure pour parser le JSON produit par le script secedit
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsUserRight {
+    privilege: String,
+    principals: Option<Vec<PsRightPrincipal>>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsRightPrincipal {
+    sid: Option<String>,
+    name: Option<String>,
+}
+
+// Titulaire d'un droit (SID résolu en nom de compte si possible)
+#[derive(Serialize, Debug, Clone)]
+pub struct RightPrincipal {
+    sid: String,
+    name: String,
+}
+
+// Structure finale: un privilège et les comptes qui le détiennent
+#[derive(Serialize, Debug, Clone)]
+pub struct UserRightAssignment {
+    privilege: String,
+    display_name: String,
+    principals: Vec<RightPrincipal>,
+}
+
+// Libellés de la console "Stratégie de sécurité locale" pour les constantes de privilèges
+fn privilege_display_name(privilege: &str) -> &'static str {
+    match privilege {
+        "SeAssignPrimaryTokenPrivilege" => "Remplacer un jeton de niveau processus",
+        "SeAuditPrivilege" => "Générer des audits de sécurité",
+        "SeBackupPrivilege" => "Sauvegarder des fichiers et des répertoires",
+        "SeBatchLogonRight" => "Ouvrir une session en tant que tâche",
+        "SeChangeNotifyPrivilege" => "Contourner la vérification de parcours",
+        "SeCreateGlobalPrivilege" => "Créer des objets globaux",
+        "SeCreatePagefilePrivilege" => "Créer un fichier d'échange",
+        "SeCreatePermanentPrivilege" => "Créer des objets partagés permanents",
+        "SeCreateSymbolicLinkPrivilege" => "Créer des liens symboliques",
+        "SeCreateTokenPrivilege" => "Créer un objet-jeton",
+        "SeDebugPrivilege" => "Déboguer des programmes",
+        "SeDelegateSessionUserImpersonatePrivilege" => "Obtenir un jeton d'emprunt d'identité pour un autre utilisateur de la même session",
+        "SeDenyBatchLogonRight" => "Interdire l'ouverture de session en tant que tâche",
+        "SeDenyInteractiveLogonRight" => "Interdire l'ouverture d'une session locale",
+        "SeDenyNetworkLogonRight" => "Interdire l'accès à cet ordinateur à partir du réseau",
+        "SeDenyRemoteInteractiveLogonRight" => "Interdire l'ouverture de session par les services Bureau à distance",
+        "SeDenyServiceLogonRight" => "Interdire l'ouverture de session en tant que service",
+        "SeEnableDelegationPrivilege" => "Permettre à l'ordinateur et aux comptes d'utilisateurs d'être approuvés pour la délégation",
+        "SeImpersonatePrivilege" => "Emprunter l'identité d'un client après l'authentification",
+        "SeIncreaseBasePriorityPrivilege" => "Augmenter la priorité de planification",
+        "SeIncreaseQuotaPrivilege" => "Ajuster les quotas de mémoire pour un processus",
+        "SeIncreaseWorkingSetPrivilege" => "Augmenter une plage de travail de processus",
+        "SeInteractiveLogonRight" => "Permettre l'ouverture d'une session locale",
+        "SeLoadDriverPrivilege" => "Charger et décharger les pilotes de périphériques",
+        "SeLockMemoryPrivilege" => "Verrouiller les pages en mémoire",
+        "SeMachineAccountPrivilege" => "Ajouter des stations de travail au domaine",
+        "SeManageVolumePrivilege" => "Effectuer les tâches de maintenance de volume",
+        "SeNetworkLogonRight" => "Accéder à cet ordinateur à partir du réseau",
+        "SeProfileSingleProcessPrivilege" => "Processus unique du profil",
+        "SeRelabelPrivilege" => "Modifier l'étiquette d'un objet",
+        "SeRemoteInteractiveLogonRight" => "Autoriser l'ouverture de session par les services Bureau à distance",
+        "SeRemoteShutdownPrivilege" => "Forcer l'arrêt à partir d'un système distant",
+        "SeRestorePrivilege" => "Restaurer des fichiers et des répertoires",
+        "SeSecurityPrivilege" => "Gérer le journal d'audit et de sécurité",
+        "SeServiceLogonRight" => "Ouvrir une session en tant que service",
+        "SeShutdownPrivilege" => "Arrêter le système",
+        "SeSyncAgentPrivilege" => "Synchroniser les données du service d'annuaire",
+        "SeSystemEnvironmentPrivilege" => "Modifier les valeurs de l'environnement du microprogramme",
+        "SeSystemProfilePrivilege" => "Performance système du profil",
+        "SeSystemtimePrivilege" => "Modifier l'heure système",
+        "SeTakeOwnershipPrivilege" => "Prendre possession de fichiers ou d'autres objets",
+        "SeTcbPrivilege" => "Agir en tant que partie du système d'exploitation",
+        "SeTimeZonePrivilege" => "Changer le fuseau horaire",
+        "SeTrustedCredManAccessPrivilege" => "Accéder au gestionnaire d'informations d'identification en tant qu'appelant approuvé",
+        "SeUndockPrivilege" => "Retirer l'ordinateur de la station d'accueil",
+        _ => "",
+    }
+}
+
+#[command]
+pub async fn list_user_rights(app: AppHandle) -> Result<Vec<UserRightAssignment>, String> {
+    println!("Real: list_user_rights() called");
+
+    // Important: secedit /export nécessite des privilèges admin
+    // La section [Privilege Rights] contient des lignes "SeDebugPrivilege = *S-1-5-32-544,*S-1-5-..."
+    // Les entrées préfixées par '*' sont des SID, les autres des noms de comptes non résolus à l'export
+    let command = r#"
+        $tmp = [System.IO.Path]::GetTempFileName();
+        secedit /export /cfg $tmp /areas USER_RIGHTS /quiet | Out-Null;
+        if ($LASTEXITCODE -ne 0) { Remove-Item $tmp -Force -ErrorAction SilentlyContinue; Write-Error 'secedit /export a échoué'; exit 1 }
+        $lines = Get-Content -Path $tmp -Encoding Unicode;
+        Remove-Item $tmp -Force -ErrorAction SilentlyContinue;
+        $inSection = $false;
+        $result = foreach ($line in $lines) {
+            if ($line -match '^\[(.+)\]$') { $inSection = ($Matches[1] -eq 'Privilege Rights'); continue }
+            if (-not $inSection -or $line -notmatch '^\s*(\S+)\s*=\s*(.*)$') { continue }
+            $privilege = $Matches[1];
+            $entries = $Matches[2] -split ',' | ForEach-Object { $_.Trim() } | Where-Object { $_ };
+            $principals = foreach ($entry in $entries) {
+                $sid = $null; $name = $null;
+                if ($entry.StartsWith('*')) {
+                    $sid = $entry.Substring(1);
+                    try { $name = (New-Object System.Security.Principal.SecurityIdentifier($sid)).Translate([System.Security.Principal.NTAccount]).Value } catch { }
+                } else {
+                    $name = $entry;
+                    try { $sid = (New-Object System.Security.Principal.NTAccount($entry)).Translate([System.Security.Principal.SecurityIdentifier]).Value } catch { }
+                }
+                [PSCustomObject]@{ Sid = $sid; Name = $name }
+            }
+            [PSCustomObject]@{ Privilege = $privilege; Principals = @($principals) }
+        }
+        ConvertTo-Json -InputObject @($result) -Depth 4 -Compress
+    "#;
+
+    let output = app.shell()
+        .command("powershell")
+        .args(["-Command", command])
+        .output()
+        .await
+        .map_err(|e| format!("Erreur lors de l'exécution de secedit: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("L'export des droits utilisateur a échoué: {:?} \nErreur: {}",
+            output.status, String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let rights_json_str = String::from_utf8_lossy(&output.stdout);
+    if rights_json_str.trim().is_empty() {
+        return Ok(vec![]);
     }
+
+    let parsed_rights: Vec<PsUserRight> = serde_json::from_str(&rights_json_str)
+        .map_err(|e| format!("Erreur parsing JSON droits utilisateur: {}\nJSON: {}", e, rights_json_str))?;
+
+    let final_rights = parsed_rights.into_iter().map(|ps_right| {
+        let principals = ps_right.principals.unwrap_or_default().into_iter().map(|p| {
+            let sid = p.sid.unwrap_or_else(|| "N/A".to_string());
+            RightPrincipal {
+                // SID orphelin (compte supprimé): afficher le SID brut comme nom
+                name: p.name.unwrap_or_else(|| sid.clone()),
+                sid,
+            }
+        }).collect();
+
+        UserRightAssignment {
+            display_name: privilege_display_name(&ps_right.privilege).to_string(),
+            privilege: ps_right.privilege,
+            principals,
+        }
+    }).collect();
+
+    Ok(final_rights)
+}
+
+// --- Audit des comptes locaux ---
+
+// SID bien connus utilisés par l'audit
+const ADMINISTRATORS_GROUP_SID: &str = "S-1-5-32-544";
+const BUILTIN_ADMINISTRATOR_RID: &str = "-500";
+const BUILTIN_GUEST_RID: &str = "-501";
+const DOMAIN_ADMINS_RID: &str = "-512";
+// Préfixe des SID de machine et de domaine (S-1-5-21-x-y-z)
+const ACCOUNT_DOMAIN_SID_PREFIX: &str = "S-1-5-21-";
+
+// SID du domaine joint, via le compte ordinateur (DOMAINE\PC$); rien si la machine n'est pas
+// dans un domaine ou si aucun contrôleur n'est joignable
+const JOINED_DOMAIN_SID_SCRIPT: &str = "if ((Get-CimInstance Win32_ComputerSystem).PartOfDomain) { try { ([Security.Principal.NTAccount]\"$env:COMPUTERNAME$\").Translate([Security.Principal.SecurityIdentifier]).AccountDomainSid.Value } catch {} }";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AccountAuditFinding {
+    account: String,
+    sid: String,
+    category: String, // StaleAccount, NeverLoggedOn, PasswordNeverExpires, BuiltinAccountEnabled, UnexpectedAdministrator
+    severity: String, // Low, Medium, High
+    detail: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LocalAccountAuditReport {
+    generated_at: String,
+    stale_days: u32,
+    users_checked: usize,
+    administrators: Vec<String>,
+    findings: Vec<AccountAuditFinding>,
+}
+
+fn audit_finding(account: &str, sid: &str, category: &str, severity: &str, detail: String) -> AccountAuditFinding {
+    AccountAuditFinding {
+        account: account.to_string(),
+        sid: sid.to_string(),
+        category: category.to_string(),
+        severity: severity.to_string(),
+        detail,
+    }
+}
+
+// Partie domaine d'un SID de compte (sans le RID), pour les SID de machine ou de domaine uniquement
+fn account_domain_sid(sid: &str) -> Option<&str> {
+    let (domain, _rid) = sid.rsplit_once('-')?;
+    if domain.starts_with(ACCOUNT_DOMAIN_SID_PREFIX) { Some(domain) } else { None }
+}
+
+// Un membre est attendu s'il correspond (nom complet "PC\nom", nom court ou SID) à la liste fournie,
+// ou s'il s'agit de l'Administrateur intégré de cette machine ou du groupe "Admins du domaine" joint.
+// Un RID 500/512 d'une autre machine ou d'un domaine approuvé reste signalé.
+fn is_expected_admin(
+    member: &LocalGroupMemberInfo,
+    expected_admins: &[String],
+    machine_sid: Option<&str>,
+    domain_sid: Option<&str>,
+) -> bool {
+    let member_domain = account_domain_sid(&member.sid);
+    if (member.sid.ends_with(BUILTIN_ADMINISTRATOR_RID) && member_domain.is_some() && member_domain == machine_sid)
+        || (member.sid.ends_with(DOMAIN_ADMINS_RID) && member_domain.is_some() && member_domain == domain_sid)
+    {
+        return true;
+    }
+    // Le nom court n'identifie un compte que sur cette machine ou dans le domaine joint: ailleurs,
+    // "AUTREDOMAINE\jdupont" n'est pas "jdupont" et doit figurer en nom complet ou par SID
+    let short_name_matches = member_domain.is_some() && (member_domain == machine_sid || member_domain == domain_sid);
+    let short_name = member.name.rsplit('\\').next().unwrap_or(&member.name);
+    expected_admins.iter().any(|expected| {
+        let expected = expected.trim();
+        expected.eq_ignore_ascii_case(&member.name)
+            || (short_name_matches && expected.eq_ignore_ascii_case(short_name))
+            || expected.eq_ignore_ascii_case(&member.sid)
+    })
+}
+
+fn evaluate_local_accounts(
+    users: &[LocalUserInfo],
+    administrators: &[LocalGroupMemberInfo],
+    stale_days: u32,
+    expected_admins: &[String],
+    domain_sid: Option<&str>,
+    now_ms: i64,
+) -> Vec<AccountAuditFinding> {
+    let mut findings = Vec::new();
+    let stale_threshold_ms = stale_days as i64 * 86_400_000;
+    // Tous les comptes locaux partagent le SID de la machine
+    let machine_sid = users.iter().find_map(|u| account_domain_sid(&u.sid));
+
+    for user in users.iter().filter(|u| u.enabled) {
+        // Comptes actifs inutilisés
+        match user.last_logon.as_deref().and_then(parse_iso8601_ms) {
+            Some(last_logon_ms) if now_ms - last_logon_ms > stale_threshold_ms => {
+                let days = (now_ms - last_logon_ms) / 86_400_000;
+                findings.push(audit_finding(&user.name, &user.sid, "StaleAccount", "Medium",
+                    format!("Compte actif sans connexion depuis {} jours", days)));
+            }
+            // Compte jamais utilisé (souvent créé puis oublié): distinct d'un compte devenu inactif
+            None => {
+                let detail = match user.password_last_set.as_deref() {
+                    Some(set) => format!("Compte actif qui ne s'est jamais connecté (mot de passe défini le {})", set),
+                    None => "Compte actif qui ne s'est jamais connecté".to_string(),
+                };
+                findings.push(audit_finding(&user.name, &user.sid, "NeverLoggedOn", "Low", detail));
+            }
+            _ => {}
+        }
+
+        // Mots de passe sans expiration (PasswordExpires absent)
+        if user.password_expires.is_none() {
+            findings.push(audit_finding(&user.name, &user.sid, "PasswordNeverExpires", "Low",
+                "Le mot de passe n'expire jamais".to_string()));
+        }
+
+        // Comptes intégrés activés (identifiés par RID, ils peuvent avoir été renommés)
+        if user.sid.ends_with(BUILTIN_ADMINISTRATOR_RID) {
+            findings.push(audit_finding(&user.name, &user.sid, "BuiltinAccountEnabled", "High",
+                "Le compte Administrateur intégré est activé".to_string()));
+        } else if user.sid.ends_with(BUILTIN_GUEST_RID) {
+            findings.push(audit_finding(&user.name, &user.sid, "BuiltinAccountEnabled", "High",
+                "Le compte Invité intégré est activé".to_string()));
+        }
+    }
+
+    // Membres inattendus du groupe Administrateurs
+    for member in administrators.iter().filter(|m| !is_expected_admin(m, expected_admins, machine_sid, domain_sid)) {
+        findings.push(audit_finding(&member.name, &member.sid, "UnexpectedAdministrator", "High",
+            format!("Membre inattendu du groupe Administrateurs ({}, source: {})", member.object_class, member.principal_source)));
+    }
+
+    findings
+}
+
+async fn joined_domain_sid(app: &AppHandle) -> Option<String> {
+    let output = app.shell()
+        .command("powershell")
+        .args(["-Command", JOINED_DOMAIN_SID_SCRIPT])
+        .output()
+        .await
+        .ok()?;
+    let sid = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() && sid.starts_with(ACCOUNT_DOMAIN_SID_PREFIX) { Some(sid) } else { None }
+}
+
+fn audit_report_to_csv(report: &LocalAccountAuditReport) -> String {
+    let mut csv = String::from("Account,SID,Category,Severity,Detail\r\n");
+    for finding in &report.findings {
+        let row = [&finding.account, &finding.sid, &finding.category, &finding.severity, &finding.detail]
+            .iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<_>>()
+            .join(",");
+        csv.push_str(&row);
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+#[command]
+pub async fn audit_local_accounts(
+    app: AppHandle,
+    stale_days: Option<u32>,                // 90 jours par défaut
+    expected_admins: Option<Vec<String>>,   // Membres légitimes du groupe Administrateurs
+) -> Result<LocalAccountAuditReport, String> {
+    let stale_days = stale_days.unwrap_or(90);
+    let expected_admins = expected_admins.unwrap_or_default();
+    println!("Real: audit_local_accounts(stale_days: {}, expected_admins: {:?}) called", stale_days, expected_admins);
+
+    let users = list_local_users(app.clone()).await?;
+
+    // Le nom du groupe Administrateurs dépend de la langue du système: le retrouver par SID
+    let groups = list_local_groups(app.clone()).await?;
+    let administrators = match groups.iter().find(|g| g.sid == ADMINISTRATORS_GROUP_SID) {
+        Some(group) => get_local_group_members(app.clone(), group.name.clone()).await?,
+        None => vec![],
+    };
+
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Horloge système invalide: {}", e))?
+        .as_millis() as i64;
+
+    let domain_sid = joined_domain_sid(&app).await;
+    let findings = evaluate_local_accounts(&users, &administrators, stale_days, &expected_admins, domain_sid.as_deref(), now_ms);
+
+    Ok(LocalAccountAuditReport {
+        generated_at: format_iso8601_ms(now_ms),
+        stale_days,
+        users_checked: users.len(),
+        administrators: administrators.into_iter().map(|m| m.name).collect(),
+        findings,
+    })
+}
+
+#[command]
+pub async fn export_local_account_audit(report: LocalAccountAuditReport, file_path: String) -> Result<(), String> {
+    println!("Real: export_local_account_audit('{}') called", file_path);
+
+    // BOM UTF-8 pour qu'Excel reconnaisse les accents
+    let content = format!("\u{feff}{}", audit_report_to_csv(&report));
+    std::fs::write(&file_path, content)
+        .map_err(|e| format!("Erreur lors de l'écriture du rapport CSV '{}': {}", file_path, e))
+}
+
+// --- Profils utilisateur ---
+
+// Structure pour parser le JSON de Win32_UserProfile
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PsUserProfile {
+    #[serde(rename = "SID")]
+    sid: String,
+    account_name: Option<String>,
+    local_path: Option<String>,
+    last_use_time: Option<Value>,
+    loaded: Option<bool>,
+    special: Option<bool>,
+    size_bytes: Option<u64>,
+}
+
+// Structure finale pour un profil utilisateur
+#[derive(Serialize, Debug, Clone)]
+pub struct UserProfileInfo {
+    sid: String,
+    account_name: String, // SID brut si le compte n'existe plus
+    local_path: String,
+    last_use_time: Option<String>, // ISO 8601 UTC
+    loaded: bool,
+    special: bool, // Profils système (SYSTEM, LocalService, NetworkService...)
+    size_bytes: Option<u64>, // Calculée seulement si demandé (parcours complet du dossier)
+}
+
+fn is_valid_sid(sid: &str) -> bool {
+    regex::Regex::new(r"^S-1-\d+(-\d+)+$").map(|re| re.is_match(sid)).unwrap_or(false)
+}
+
+async fn query_user_profiles(app: &AppHandle, wmi_filter: Option<&str>, include_size: bool) -> Result<Vec<UserProfileInfo>, String> {
+    let filter = wmi_filter.map(|f| format!(" -Filter {}", ps_quote(f))).unwrap_or_default();
+    let size_property = if include_size {
+        ", @{N='SizeBytes';E={ if ($_.LocalPath -and (Test-Path -LiteralPath $_.LocalPath)) { [uint64](Get-ChildItem -LiteralPath $_.LocalPath -Recurse -Force -File -ErrorAction SilentlyContinue | Measure-Object -Property Length -Sum).Sum } }}"
+    } else {
+        ""
+    };
+    let command = format!(
+        "Get-CimInstance -ClassName Win32_UserProfile{} | Select-Object SID, @{{N='AccountName';E={{ try {{ (New-Object System.Security.Principal.SecurityIdentifier($_.SID)).Translate([System.Security.Principal.NTAccount]).Value }} catch {{ $null }} }}}}, LocalPath, LastUseTime, Loaded, Special{} | ConvertTo-Json -Depth 3 -Compress",
+        filter, size_property
+    );
+
+    let output = app.shell()
+        .command("powershell")
+        .args(["-Command", &command])
+        .output()
+        .await
+        .map_err(|e| format!("Erreur lors de l'exécution de Win32_UserProfile: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Win32_UserProfile a échoué: {:?} \nErreur: {}",
+            output.status, String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let profiles_json_str = String::from_utf8_lossy(&output.stdout);
+    if profiles_json_str.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    let parsed_profiles: Vec<PsUserProfile> = if profiles_json_str.trim().starts_with('[') {
+        serde_json::from_str(&profiles_json_str)
+            .map_err(|e| format!("Erreur parsing JSON (tableau) profils: {}\nJSON: {}", e, profiles_json_str))?
+    } else {
+        serde_json::from_str::<PsUserProfile>(&profiles_json_str)
+            .map(|profile| vec![profile])
+            .map_err(|e| format!("Erreur parsing JSON (objet unique) profils: {}\nJSON: {}", e, profiles_json_str))?
+    };
+
+    let final_profiles = parsed_profiles.into_iter().map(|ps_profile| UserProfileInfo {
+        account_name: ps_profile.account_name.unwrap_or_else(|| ps_profile.sid.clone()),
+        sid: ps_profile.sid,
+        local_path: ps_profile.local_path.unwrap_or_default(),
+        last_use_time: parse_ps_json_date(ps_profile.last_use_time.as_ref()).map(format_iso8601_ms),
+        loaded: ps_profile.loaded.unwrap_or(false),
+        special: ps_profile.special.unwrap_or(false),
+        size_bytes: ps_profile.size_bytes,
+    }).collect();
+
+    Ok(final_profiles)
+}
+
+#[command]
+pub async fn list_user_profiles(app: AppHandle, include_size: Option<bool>) -> Result<Vec<UserProfileInfo>, String> {
+    println!("Real: list_user_profiles(include_size: {:?}) called", include_size);
+    query_user_profiles(&app, None, include_size.unwrap_or(false)).await
+}
+
+#[command]
+pub async fn delete_user_profile(app: AppHandle, sid: String) -> Result<(), String> {
+    println!("Real: delete_user_profile('{}') called", sid);
+
+    // Le SID est injecté dans un filtre WQL: n'accepter que le format S-1-...
+    if !is_valid_sid(&sid) {
+        return Err(format!("SID invalide: {}", sid));
+    }
+
+    let wmi_filter = format!("SID='{}'", sid);
+    let profile = query_user_profiles(&app, Some(&wmi_filter), false).await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Aucun profil trouvé pour le SID {}", sid))?;
+
+    if profile.special {
+        return Err(format!("Le profil {} est un profil système et ne peut pas être supprimé.", profile.account_name));
+    }
+    if profile.loaded {
+        return Err(format!("Le profil {} est actuellement chargé (session ouverte ou processus actif).", profile.account_name));
+    }
+
+    // Important: Nécessite des privilèges admin
+    // Remove-CimInstance supprime le dossier du profil et la clé ProfileList du registre
+    let command = format!(
+        "Get-CimInstance -ClassName Win32_UserProfile -Filter {} | Where-Object {{ -not $_.Loaded -and -not $_.Special }} | Remove-CimInstance -ErrorAction Stop",
+        ps_quote(&wmi_filter)
+    );
+    run_local_account_command(&app, "Remove-CimInstance (Win32_UserProfile)", &command, None).await
 }
 
 // Supprimer l'ancien placeholder
@@ -241,4 +1085,198 @@ pub async fn users_placeholder() -> Result<(), String> {
     println!("Placeholder: users command called");
     Err("Placeholder non utilisé".to_string())
 }
-*/ 
\ No newline at end of file
+*/ 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Date telle que désérialisée depuis la sortie brute de ConvertTo-Json
+    fn json_date(json: &str) -> Option<i64> {
+        let value: Value = serde_json::from_str(json).unwrap();
+        parse_ps_json_date(Some(&value))
+    }
+
+    #[test]
+    fn decodes_windows_powershell_dates() {
+        assert_eq!(json_date(r#""\/Date(1599214542137)\/""#), Some(1_599_214_542_137));
+        // Le décalage est informatif: les millisecondes sont déjà en UTC
+        assert_eq!(json_date(r#""\/Date(1599214542137+0200)\/""#), Some(1_599_214_542_137));
+        assert_eq!(json_date(r#""\/Date(1599214542137-0500)\/""#), Some(1_599_214_542_137));
+        // Avant 1970
+        assert_eq!(json_date(r#""\/Date(-86400000)\/""#), Some(-86_400_000));
+        assert_eq!(json_date(r#""\/Date(-62135596800000)\/""#), Some(-62_135_596_800_000)); // DateTime.MinValue
+    }
+
+    #[test]
+    fn unwraps_extended_date_objects() {
+        let wrapped = r#"{"value":"\/Date(1599214542137)\/","DisplayHint":2,"DateTime":"vendredi 4 septembre 2020 12:15:42"}"#;
+        assert_eq!(json_date(wrapped), Some(1_599_214_542_137));
+        assert_eq!(json_date(r#"{"value":null}"#), None);
+        assert_eq!(json_date("null"), None);
+        assert_eq!(parse_ps_json_date(None), None);
+    }
+
+    #[test]
+    fn parses_iso8601_variants() {
+        assert_eq!(parse_iso8601_ms("2020-09-04T10:15:42.137Z"), Some(1_599_214_542_137));
+        assert_eq!(parse_iso8601_ms("2020-09-04T12:15:42.137+02:00"), Some(1_599_214_542_137));
+        assert_eq!(parse_iso8601_ms("2020-09-04T05:15:42.137-05:00"), Some(1_599_214_542_137));
+        // Précision de 100 ns (EventRecord.ToXml, PowerShell 7): tronquée à la milliseconde
+        assert_eq!(parse_iso8601_ms("2020-09-04T10:15:42.1379999Z"), Some(1_599_214_542_137));
+        assert_eq!(parse_iso8601_ms("2020-09-04T10:15:42.5Z"), Some(1_599_214_542_500));
+        assert_eq!(parse_iso8601_ms("2020-09-04 10:15:42"), Some(1_599_214_542_000));
+        assert_eq!(parse_iso8601_ms("2020-09-04"), Some(1_599_177_600_000));
+        assert_eq!(json_date(r#""2020-09-04T12:15:42.137+02:00""#), Some(1_599_214_542_137));
+    }
+
+    #[test]
+    fn handles_dates_before_epoch_and_leap_days() {
+        assert_eq!(parse_iso8601_ms("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_iso8601_ms("1969-12-31T23:59:59Z"), Some(-1000));
+        assert_eq!(parse_iso8601_ms("1601-01-01T00:00:00Z"), Some(-11_644_473_600_000)); // Origine FILETIME
+        assert_eq!(parse_iso8601_ms("2024-02-29T00:00:00Z"), Some(1_709_164_800_000));
+        assert_eq!(parse_iso8601_ms("2000-02-29"), Some(951_782_400_000));
+        assert_eq!(parse_iso8601_ms("2023-02-29"), None);
+        assert_eq!(parse_iso8601_ms("1900-02-29"), None);
+        assert_eq!(parse_iso8601_ms("2024-04-31"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_dates() {
+        assert_eq!(parse_iso8601_ms(""), None);
+        assert_eq!(parse_iso8601_ms("04/09/2020"), None);
+        assert_eq!(parse_iso8601_ms("2020-13-01"), None);
+        assert_eq!(parse_iso8601_ms("2020-09-04T24:00:00Z"), None);
+        assert_eq!(parse_iso8601_ms("2020-09-04T10:15"), None);
+        assert_eq!(parse_iso8601_ms("2020-09-04T10:15:42 UTC"), None);
+        assert_eq!(json_date(r#""\/Date(abc)\/""#), None);
+    }
+
+    #[test]
+    fn formats_and_round_trips() {
+        assert_eq!(format_iso8601_ms(1_599_214_542_137), "2020-09-04T10:15:42Z");
+        assert_eq!(format_iso8601_ms(-1), "1969-12-31T23:59:59Z");
+        assert_eq!(format_iso8601_ms(1_709_164_800_000), "2024-02-29T00:00:00Z");
+        for ms in [0, -1000, 951_782_400_000, 1_709_251_199_000, -11_644_473_600_000, 253_402_300_799_000] {
+            assert_eq!(parse_iso8601_ms(&format_iso8601_ms(ms)), Some(ms));
+        }
+        // Chaque jour sur quatre siècles autour de l'epoch
+        for days in -73_000..73_000 {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    const MACHINE: &str = "S-1-5-21-1111111111-2222222222-3333333333";
+    const DOMAIN: &str = "S-1-5-21-4444444444-5555555555-6666666666";
+    const OTHER_DOMAIN: &str = "S-1-5-21-7777777777-8888888888-9999999999";
+    // 2024-06-01T00:00:00Z
+    const NOW_MS: i64 = 1_717_200_000_000;
+
+    fn user(name: &str, rid: u32, last_logon: Option<&str>, password_expires: Option<&str>) -> LocalUserInfo {
+        LocalUserInfo {
+            name: name.to_string(),
+            full_name: String::new(),
+            description: String::new(),
+            enabled: true,
+            sid: format!("{}-{}", MACHINE, rid),
+            last_logon: last_logon.map(str::to_string),
+            password_last_set: Some("2024-01-15T09:00:00Z".to_string()),
+            password_expires: password_expires.map(str::to_string),
+            account_expires: None,
+            password_required: true,
+            principal_source: "Local".to_string(),
+        }
+    }
+
+    fn member(name: &str, sid: String) -> LocalGroupMemberInfo {
+        LocalGroupMemberInfo {
+            name: name.to_string(),
+            object_class: "Group".to_string(),
+            principal_source: "ActiveDirectory".to_string(),
+            sid,
+        }
+    }
+
+    fn categories(findings: &[AccountAuditFinding], account: &str) -> Vec<String> {
+        findings.iter().filter(|f| f.account == account).map(|f| f.category.clone()).collect()
+    }
+
+    #[test]
+    fn separates_stale_and_never_used_accounts() {
+        let users = vec![
+            user("ancien", 1001, Some("2024-01-02T08:00:00Z"), Some("2024-09-01T00:00:00Z")),
+            user("jamais", 1002, None, Some("2024-09-01T00:00:00Z")),
+            user("actif", 1003, Some("2024-05-30T08:00:00Z"), Some("2024-09-01T00:00:00Z")),
+        ];
+        let findings = evaluate_local_accounts(&users, &[], 90, &[], None, NOW_MS);
+        assert_eq!(categories(&findings, "ancien"), vec!["StaleAccount"]);
+        assert_eq!(categories(&findings, "jamais"), vec!["NeverLoggedOn"]);
+        assert!(categories(&findings, "actif").is_empty());
+
+        let mut disabled = user("desactive", 1004, None, None);
+        disabled.enabled = false;
+        assert!(evaluate_local_accounts(&[disabled], &[], 90, &[], None, NOW_MS).is_empty());
+    }
+
+    #[test]
+    fn flags_non_expiring_passwords_and_builtin_accounts() {
+        let users = vec![
+            user("svc_backup", 1005, Some("2024-05-30T08:00:00Z"), None),
+            user("Admin-renomme", 500, Some("2024-05-30T08:00:00Z"), Some("2024-09-01T00:00:00Z")),
+            user("Invité", 501, Some("2024-05-30T08:00:00Z"), Some("2024-09-01T00:00:00Z")),
+        ];
+        let findings = evaluate_local_accounts(&users, &[], 90, &[], None, NOW_MS);
+        assert_eq!(categories(&findings, "svc_backup"), vec!["PasswordNeverExpires"]);
+        assert_eq!(categories(&findings, "Admin-renomme"), vec!["BuiltinAccountEnabled"]);
+        assert_eq!(categories(&findings, "Invité"), vec!["BuiltinAccountEnabled"]);
+    }
+
+    #[test]
+    fn whitelists_builtin_admins_of_this_machine_and_joined_domain_only() {
+        let users = vec![user("Administrateur", 500, Some("2024-05-30T08:00:00Z"), Some("2024-09-01T00:00:00Z"))];
+        let administrators = vec![
+            member("PC01\\Administrateur", format!("{}-500", MACHINE)),
+            member("CORP\\Admins du domaine", format!("{}-512", DOMAIN)),
+            member("PARTENAIRE\\Domain Admins", format!("{}-512", OTHER_DOMAIN)),
+            member("PC02\\Administrator", format!("{}-500", OTHER_DOMAIN)),
+            member("CORP\\helpdesk", format!("{}-1108", DOMAIN)),
+            member("CORP\\jdupont", format!("{}-1203", DOMAIN)),
+        ];
+        let expected = vec!["helpdesk".to_string()];
+        let findings = evaluate_local_accounts(&users, &administrators, 90, &expected, Some(DOMAIN), NOW_MS);
+        let unexpected: Vec<&str> = findings.iter()
+            .filter(|f| f.category == "UnexpectedAdministrator")
+            .map(|f| f.account.as_str())
+            .collect();
+        assert_eq!(unexpected, vec!["PARTENAIRE\\Domain Admins", "PC02\\Administrator", "CORP\\jdupont"]);
+
+        // Domaine joint inconnu: "Admins du domaine" n'est plus considéré comme attendu
+        let findings = evaluate_local_accounts(&users, &administrators, 90, &expected, None, NOW_MS);
+        assert!(findings.iter().any(|f| f.account == "CORP\\Admins du domaine" && f.category == "UnexpectedAdministrator"));
+    }
+
+    #[test]
+    fn matches_short_names_only_in_trusted_domains() {
+        let users = vec![user("Administrateur", 500, Some("2024-05-30T08:00:00Z"), Some("2024-09-01T00:00:00Z"))];
+        let administrators = vec![
+            member("PC01\\jdupont", format!("{}-1001", MACHINE)),
+            member("CORP\\jdupont", format!("{}-1203", DOMAIN)),
+            member("PARTENAIRE\\jdupont", format!("{}-1450", OTHER_DOMAIN)),
+            member("PARTENAIRE\\mmartin", format!("{}-1451", OTHER_DOMAIN)),
+        ];
+        // Homonyme d'un domaine tiers: accepté seulement en nom complet ou par SID
+        let expected = vec!["jdupont".to_string(), "partenaire\\mmartin".to_string()];
+        let findings = evaluate_local_accounts(&users, &administrators, 90, &expected, Some(DOMAIN), NOW_MS);
+        let unexpected: Vec<&str> = findings.iter()
+            .filter(|f| f.category == "UnexpectedAdministrator")
+            .map(|f| f.account.as_str())
+            .collect();
+        assert_eq!(unexpected, vec!["PARTENAIRE\\jdupont"]);
+
+        let expected = vec![format!("{}-1450", OTHER_DOMAIN), "jdupont".to_string(), "PARTENAIRE\\mmartin".to_string()];
+        let findings = evaluate_local_accounts(&users, &administrators, 90, &expected, Some(DOMAIN), NOW_MS);
+        assert!(!findings.iter().any(|f| f.category == "UnexpectedAdministrator"));
+    }
+}
@@ -51,6 +51,7 @@ import DataUsageIcon from '@mui/icons-material/DataUsage';
 
 // Interface pour une entrée de log
 interface EventLogEntry {
+    record_id: number;
     event_id: number;
     level: string;
     provider_name: string;
@@ -58,6 +59,21 @@ interface EventLogEntry {
     message: string;
 }
 
+// Requête structurée envoyée à get_events (voir event_viewer/query.rs)
+interface EventQuery {
+    logs: string[];
+    event_ids?: (number | { from: number; to: number })[];
+    exclude_event_ids?: (number | { from: number; to: number })[];
+    levels?: number[];
+    providers?: string[];
+    keywords?: string[];
+    user_sid?: string | null;
+    start_time?: string | null;
+    end_time?: string | null;
+    within_ms?: number | null;
+    event_data?: { name: string; value: string }[];
+}
+
 const LOG_NAMES = ["Application", "System", "Security", "Setup"];
 const LEVELS = [
     { value: 0, label: "Tous" }, 
@@ -76,6 +92,7 @@ const EventViewerPage: React.FC = () => {
     const [providerFilter, setProviderFilter] = useState<string>("");
     const [idFilter, setIdFilter] = useState<string>(""); // Garder comme string pour l'input
     const [openDeleteDialog, setOpenDeleteDialog] = useState<boolean>(false);
+    const [archivePath, setArchivePath] = useState<string>(""); // Archive .evtx optionnelle avant vidage
 
     // États affichage
     const [events, setEvents] = useState<EventLogEntry[]>([]);
@@ -89,15 +106,17 @@ const EventViewerPage: React.FC = () => {
         setActionMessage(null);
         setEvents([]);
 
-        // Préparer les arguments pour l'invoke
-        const invokeArgs = {
-            logName,
-            maxEvents,
-            level: levelFilter === 0 ? null : levelFilter, // Envoyer null si "Tous"
-            providerNameFilter: providerFilter.trim() === "" ? null : providerFilter.trim(),
-            eventIdFilter: idFilter.trim() === "" ? null : parseInt(idFilter, 10) || null, // Tenter de parser l'ID
+        // Requête structurée (EventQuery côté Rust, compilée en XPath)
+        const eventId = parseInt(idFilter, 10);
+        const query: EventQuery = {
+            logs: [logName],
+            levels: levelFilter === 0 ? [] : [levelFilter], // Vide si "Tous"
+            providers: providerFilter.trim() === "" ? [] : [providerFilter.trim()], // Nom exact du fournisseur
+            event_ids: isNaN(eventId) || eventId < 0 ? [] : [eventId],
+            exclude_event_ids: isNaN(eventId) || eventId >= 0 ? [] : [-eventId], // ID négatif = exclusion
             // TODO: Ajouter filtres de date si besoin
         };
+        const invokeArgs = { query, maxEvents };
 
         invoke<EventLogEntry[]>('get_events', invokeArgs)
             .then(data => setEvents(data))
@@ -114,9 +133,13 @@ const EventViewerPage: React.FC = () => {
         setIsLoading(true); // Utiliser isLoading pour le bouton Vider aussi
         setError(null);
         setActionMessage(null);
-        invoke<void>('clear_event_log', { logName })
+        const backupPath = archivePath.trim() || null;
+        invoke<void>('clear_event_log', { logName, backupPath })
             .then(() => {
-                 setActionMessage({ type: 'success', message: `Journal '${logName}' vidé avec succès.` });
+                 setActionMessage({ type: 'success', message: backupPath
+                     ? `Journal '${logName}' archivé dans '${backupPath}' puis vidé.`
+                     : `Journal '${logName}' vidé avec succès.` });
+                 setArchivePath("");
                  fetchEvents(); // Recharger
             })
             .catch(err => {
@@ -521,6 +544,16 @@ const EventViewerPage: React.FC = () => {
                             Cette action est irréversible et nécessite des privilèges administrateur.
                         </Alert>
                     </DialogContentText>
+                    <TextField
+                        label="Archiver avant de vider (optionnel)"
+                        placeholder="C:\Archives\journal.evtx"
+                        value={archivePath}
+                        onChange={(e) => setArchivePath(e.target.value)}
+                        fullWidth
+                        size="small"
+                        helperText="Fichier .evtx; le journal n'est vidé que si l'archive a été écrite."
+                        sx={{ mt: 1 }}
+                    />
                 </DialogContent>
                 <DialogActions sx={{ p: 2 }}>
                     <Button onClick={() => setOpenDeleteDialog(false)} color="primary" variant="outlined" sx={{ borderRadius: '8px' }}>
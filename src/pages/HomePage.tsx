@@ -37,6 +37,68 @@ interface HardwareInfo {
     motherboard_manufacturer: string | null;
     motherboard_product: string | null;
     gpus: GpuInfo[]; // Nouvelle interface simplifiée
+    bios: BiosInfo | null;
+    chassis_types: string[];
+    memory_modules: MemoryModuleInfo[];
+    physical_disks: PhysicalDiskInfo[];
+    batteries: BatteryInfo[];
+    monitors: MonitorInfo[];
+    tpm: TpmInfo | null; // null: absent ou illisible sans droits admin
+}
+
+interface BiosInfo {
+    manufacturer: string | null;
+    version: string | null;
+    release_date: string | null;
+    serial_number: string | null;
+}
+
+interface MemoryModuleInfo {
+    slot: string | null;
+    bank: string | null;
+    capacity_gb: number | null;
+    memory_type: string | null;
+    speed_mhz: number | null;
+    configured_speed_mhz: number | null;
+    manufacturer: string | null;
+    part_number: string | null;
+    serial_number: string | null;
+}
+
+interface PhysicalDiskInfo {
+    model: string;
+    serial_number: string | null;
+    size_gb: number | null;
+    media_type: string;
+    bus_type: string;
+    health_status: string;
+}
+
+interface BatteryInfo {
+    name: string | null;
+    manufacturer: string | null;
+    design_capacity_mwh: number | null;
+    full_charge_capacity_mwh: number | null;
+    health_percent: number | null;
+    charge_percent: number | null;
+    on_ac_power: boolean | null;
+}
+
+interface MonitorInfo {
+    manufacturer_code: string | null;
+    manufacturer: string | null;
+    model: string | null;
+    product_code: string | null;
+    serial_number: string | null;
+    year_of_manufacture: number | null;
+}
+
+interface TpmInfo {
+    spec_version: string | null;
+    manufacturer: string | null;
+    manufacturer_version: string | null;
+    enabled: boolean | null;
+    activated: boolean | null;
 }
 
 // Interface pour le GPU simplifiée (correspondant à la structure Rust)
@@ -759,6 +821,17 @@ const HomePage: React.FC = () => {
                                     <Typography variant="body2" sx={{ fontWeight: 'medium' }}>
                                         {hardwareInfo.motherboard_manufacturer} {hardwareInfo.motherboard_product}
                                     </Typography>
+                                    {hardwareInfo.bios && (
+                                        <Typography variant="caption" sx={{ display: 'block', mt: 1 }}>
+                                            BIOS {hardwareInfo.bios.manufacturer} {hardwareInfo.bios.version}
+                                            {hardwareInfo.bios.release_date ? ` (${hardwareInfo.bios.release_date})` : ''}
+                                            {hardwareInfo.bios.serial_number ? ` · N° série ${hardwareInfo.bios.serial_number}` : ''}
+                                        </Typography>
+                                    )}
+                                    <Typography variant="caption" sx={{ display: 'block' }}>
+                                        {hardwareInfo.chassis_types.length > 0 ? `Châssis: ${hardwareInfo.chassis_types.join(', ')} · ` : ''}
+                                        TPM: {hardwareInfo.tpm ? `${hardwareInfo.tpm.spec_version ?? '?'}${hardwareInfo.tpm.manufacturer ? ` (${hardwareInfo.tpm.manufacturer})` : ''}` : 'non détecté'}
+                                    </Typography>
                                 </Box>
                             </Box>
                             
@@ -794,6 +867,13 @@ const HomePage: React.FC = () => {
                                     <Typography variant="caption" sx={{ display: 'block', textAlign: 'center', mt: 1 }}>
                                         {hardwareInfo.ram_modules_count} module(s) installé(s)
                                     </Typography>
+                                    {hardwareInfo.memory_modules.map((module, index) => (
+                                        <Typography key={index} variant="caption" sx={{ display: 'block', color: 'text.secondary' }}>
+                                            {module.slot ?? `Module ${index + 1}`}: {module.capacity_gb?.toFixed(0)} GB {module.memory_type ?? ''}
+                                            {(module.configured_speed_mhz ?? module.speed_mhz) ? ` ${module.configured_speed_mhz ?? module.speed_mhz} MHz` : ''}
+                                            {module.manufacturer ? ` · ${module.manufacturer}` : ''}{module.part_number ? ` ${module.part_number}` : ''}
+                                        </Typography>
+                                    ))}
                                 </Box>
                                 
                                 {/* GPU */}
@@ -853,6 +933,28 @@ const HomePage: React.FC = () => {
                                 </Box>
                             </Box>
                         </Box>
+
+                        {/* Disques, batteries et écrans */}
+                        <Box sx={{ mt: 3 }}>
+                            {hardwareInfo.physical_disks.map((disk, index) => (
+                                <Typography key={`disk-${index}`} variant="caption" sx={{ display: 'block' }}>
+                                    Disque: {disk.model} · {disk.size_gb?.toFixed(0)} GB · {disk.media_type} {disk.bus_type} · {disk.health_status}
+                                </Typography>
+                            ))}
+                            {hardwareInfo.batteries.map((battery, index) => (
+                                <Typography key={`battery-${index}`} variant="caption" sx={{ display: 'block' }}>
+                                    Batterie: {battery.name ?? 'Inconnue'} · santé {battery.health_percent ?? '?'} %
+                                    {battery.charge_percent !== null ? ` · charge ${battery.charge_percent} %` : ''}
+                                </Typography>
+                            ))}
+                            {hardwareInfo.monitors.map((monitor, index) => (
+                                <Typography key={`monitor-${index}`} variant="caption" sx={{ display: 'block' }}>
+                                    Écran: {monitor.manufacturer ?? '?'} {monitor.model ?? monitor.product_code ?? ''}
+                                    {monitor.serial_number ? ` · N° série ${monitor.serial_number}` : ''}
+                                    {monitor.year_of_manufacture ? ` · ${monitor.year_of_manufacture}` : ''}
+                                </Typography>
+                            ))}
+                        </Box>
                     </Box>
                 )}
             </HomeCard>
@@ -40,11 +40,26 @@ import ShareIcon from '@mui/icons-material/Share';
 import RefreshIcon from '@mui/icons-material/Refresh';
 import DriveNetworkIcon from '@mui/icons-material/DriveFileMove';
 import FolderSpecialIcon from '@mui/icons-material/FolderSpecial';
+import LockOpenIcon from '@mui/icons-material/LockOpen';
+import SearchIcon from '@mui/icons-material/Search';
 
 interface ShareInfo {
     name: string;
     path: string;
     description: string;
+    current_users: number;
+    caching_mode: string;
+    folder_enumeration_mode: string;
+}
+
+interface SmbOpenFileInfo {
+    file_id: number;
+    session_id: number;
+    path: string;
+    share_relative_path: string;
+    client_computer_name: string;
+    client_user_name: string;
+    locks: number;
 }
 
 const SharesPage: React.FC = () => {
@@ -63,6 +78,13 @@ const SharesPage: React.FC = () => {
     const [shareToDelete, setShareToDelete] = useState<string | null>(null);
     const [isDeleting, setIsDeleting] = useState(false);
 
+    // États fichiers ouverts
+    const [openFiles, setOpenFiles] = useState<SmbOpenFileInfo[]>([]);
+    const [openFilesFilter, setOpenFilesFilter] = useState("");
+    const [isLoadingOpenFiles, setIsLoadingOpenFiles] = useState(false);
+    const [openFilesError, setOpenFilesError] = useState<string | null>(null);
+    const [closingFileId, setClosingFileId] = useState<number | null>(null);
+
     const fetchShares = useCallback(() => {
         setIsLoading(true); 
         setError(null); 
@@ -78,6 +100,29 @@ const SharesPage: React.FC = () => {
         fetchShares(); 
     }, [fetchShares]);
 
+    const fetchOpenFiles = useCallback(() => {
+        setIsLoadingOpenFiles(true);
+        setOpenFilesError(null);
+
+        invoke<SmbOpenFileInfo[]>('list_open_files', { pathFilter: openFilesFilter.trim() || null })
+            .then(data => setOpenFiles(data))
+            .catch(err => setOpenFilesError(typeof err === 'string' ? err : 'Erreur inconnue.'))
+            .finally(() => setIsLoadingOpenFiles(false));
+    }, [openFilesFilter]);
+
+    const handleCloseOpenFile = (file: SmbOpenFileInfo) => {
+        setClosingFileId(file.file_id);
+        setActionMessage(null);
+
+        invoke<void>('close_open_file', { fileId: file.file_id })
+            .then(() => {
+                setActionMessage({ type: 'success', message: `Fichier '${file.path}' fermé pour ${file.client_user_name}.` });
+                fetchOpenFiles();
+            })
+            .catch(err => setOpenFilesError(`Erreur lors de la fermeture du fichier: ${typeof err === 'string' ? err : 'Erreur inconnue.'}`))
+            .finally(() => setClosingFileId(null));
+    };
+
     const handleCreateShare = (e: React.FormEvent) => {
         e.preventDefault();
         setIsCreating(true); 
@@ -397,6 +442,89 @@ const SharesPage: React.FC = () => {
                     </HomeCard>
                 </Grid>
 
+                {/* Section Fichiers ouverts */}
+                <Grid item xs={12}>
+                    <HomeCard 
+                        title="Fichiers ouverts à distance" 
+                        icon={<LockOpenIcon />}
+                        variant="standard"
+                        error={openFilesError}
+                    >
+                        <Box sx={{ p: 2 }}>
+                            <Box component="form" onSubmit={(e) => { e.preventDefault(); fetchOpenFiles(); }} sx={{ display: 'flex', gap: 2, mb: 2 }}>
+                                <TextField
+                                    fullWidth
+                                    size="small"
+                                    label="Filtrer par chemin (optionnel)"
+                                    value={openFilesFilter}
+                                    onChange={(e) => setOpenFilesFilter(e.target.value)}
+                                    placeholder="Ex: compta.xlsx"
+                                    InputProps={{
+                                        startAdornment: (
+                                            <InputAdornment position="start">
+                                                <SearchIcon color="primary" />
+                                            </InputAdornment>
+                                        )
+                                    }}
+                                    sx={{ '& .MuiOutlinedInput-root': { borderRadius: '12px' } }}
+                                />
+                                <Button
+                                    type="submit"
+                                    variant="contained"
+                                    disabled={isLoadingOpenFiles}
+                                    startIcon={isLoadingOpenFiles ? <CircularProgress size={20} color="inherit" /> : <RefreshIcon />}
+                                    sx={{ borderRadius: 2, px: 3, whiteSpace: 'nowrap' }}
+                                >
+                                    Rechercher
+                                </Button>
+                            </Box>
+                            {openFiles.length === 0 ? (
+                                <Typography variant="body2" color="text.secondary" sx={{ p: 2, textAlign: 'center' }}>
+                                    Aucun fichier ouvert à afficher. Lancez une recherche pour voir qui verrouille un fichier.
+                                </Typography>
+                            ) : (
+                                <TableContainer component={Paper} elevation={0} sx={{ borderRadius: '12px' }}>
+                                    <Table size="small">
+                                        <TableHead>
+                                            <TableRow sx={{ '& th': { fontWeight: 'bold' } }}>
+                                                <TableCell>Fichier</TableCell>
+                                                <TableCell>Utilisateur</TableCell>
+                                                <TableCell>Poste client</TableCell>
+                                                <TableCell align="right">Verrous</TableCell>
+                                                <TableCell align="center">Actions</TableCell>
+                                            </TableRow>
+                                        </TableHead>
+                                        <TableBody>
+                                            {openFiles.map((file) => (
+                                                <TableRow key={file.file_id} hover>
+                                                    <TableCell>{file.path}</TableCell>
+                                                    <TableCell>{file.client_user_name}</TableCell>
+                                                    <TableCell>{file.client_computer_name}</TableCell>
+                                                    <TableCell align="right">{file.locks}</TableCell>
+                                                    <TableCell align="center">
+                                                        <Tooltip title="Fermer le fichier (les modifications non enregistrées du client seront perdues)">
+                                                            <span>
+                                                                <IconButton
+                                                                    size="small"
+                                                                    color="error"
+                                                                    disabled={closingFileId === file.file_id}
+                                                                    onClick={() => handleCloseOpenFile(file)}
+                                                                >
+                                                                    {closingFileId === file.file_id ? <CircularProgress size={16} /> : <LockOpenIcon fontSize="small" />}
+                                                                </IconButton>
+                                                            </span>
+                                                        </Tooltip>
+                                                    </TableCell>
+                                                </TableRow>
+                                            ))}
+                                        </TableBody>
+                                    </Table>
+                                </TableContainer>
+                            )}
+                        </Box>
+                    </HomeCard>
+                </Grid>
+
                 {/* Information complémentaire */}
                 <Grid item xs={12}>
                     <Box sx={{ mt: 2 }}>
@@ -8,6 +8,15 @@ interface TaskInfo {
     last_run_time: string;
     next_run_time: string;
     last_result: string;
+    last_result_info: TaskResultInfo | null;
+}
+
+interface TaskResultInfo {
+    code: number;
+    hex: string;
+    name: string;
+    message: string;
+    severity: string; // Success, Info, Error
 }
 
 const TasksPage: React.FC = () => {
@@ -15,13 +24,13 @@ const TasksPage: React.FC = () => {
     const [isLoading, setIsLoading] = useState<boolean>(true);
     const [error, setError] = useState<string | null>(null);
     const [actionMessage, setActionMessage] = useState<{ path: string, type: 'success' | 'error', message: string } | null>(null);
-    const [actionLoading, setActionLoading] = useState<string | null>(null); // Task Path en cours d'action
+    const [actionLoading, setActionLoading] = useState<string | null>(null); // Chemin + nom de la tâche en cours d'action
 
     const fetchTasks = useCallback(() => {
         setIsLoading(true); setError(null); setActionMessage(null);
         invoke<TaskInfo[]>('list_scheduled_tasks')
             .then(data => {
-                data.sort((a,b) => a.path.localeCompare(b.path));
+                data.sort((a,b) => (a.path + a.name).localeCompare(b.path + b.name));
                 setTasks(data);
             })
             .catch(err => setError(typeof err === 'string' ? err : 'Erreur inconnue.'))
@@ -30,15 +39,16 @@ const TasksPage: React.FC = () => {
 
     useEffect(() => { fetchTasks(); }, [fetchTasks]);
 
-    const handleTaskAction = (action: 'enable' | 'disable' | 'run', taskPath: string) => {
-        setActionLoading(taskPath); setActionMessage(null);
+    const handleTaskAction = (action: 'enable' | 'disable' | 'run' | 'stop', task: TaskInfo) => {
+        const taskKey = task.path + task.name;
+        setActionLoading(taskKey); setActionMessage(null);
         const command = `${action}_task`;
-        invoke<void>(command, { taskPath })
+        invoke<void>(command, { taskPath: task.path, taskName: task.name })
             .then(() => {
-                setActionMessage({ path: taskPath, type: 'success', message: `Action ${action} réussie pour ${taskPath}` });
+                setActionMessage({ path: taskKey, type: 'success', message: `Action ${action} réussie pour ${taskKey}` });
                 fetchTasks(); // Rafraîchir
             })
-            .catch(err => setActionMessage({ path: taskPath, type: 'error', message: `Erreur ${action}: ${typeof err === 'string' ? err : 'Erreur inconnue.'}` }))
+            .catch(err => setActionMessage({ path: taskKey, type: 'error', message: `Erreur ${action}: ${typeof err === 'string' ? err : 'Erreur inconnue.'}` }))
             .finally(() => setActionLoading(null));
     };
 
@@ -47,14 +57,15 @@ const TasksPage: React.FC = () => {
             <h2>Tâches Planifiées</h2>
              {actionMessage && <p style={{ color: actionMessage.type === 'error' ? 'red' : 'green' }}>{actionMessage.message}</p>}
              {isLoading ? <p>Chargement...</p> : error ? <p style={{color: 'red'}}>Erreur: {error}</p> : (
-                <table style={{width: '100%'}}><thead><tr><th>Chemin</th><th>État</th><th>Dern. Exé.</th><th>Proch. Exé.</th><th>Résultat Dern.</th><th>Actions</th></tr></thead><tbody>
+                <table style={{width: '100%'}}><thead><tr><th>Chemin</th><th>Nom</th><th>État</th><th>Dern. Exé.</th><th>Proch. Exé.</th><th>Résultat Dern.</th><th>Actions</th></tr></thead><tbody>
                     {tasks.map(t => (
-                        <tr key={t.path}>
-                            <td>{t.path}</td><td>{t.state}</td><td>{t.last_run_time}</td><td>{t.next_run_time}</td><td>{t.last_result}</td>
+                        <tr key={t.path + t.name}>
+                            <td>{t.path}</td><td>{t.name}</td><td>{t.state}</td><td>{t.last_run_time}</td><td>{t.next_run_time}</td><td title={t.last_result_info?.message}>{t.last_result_info ? `${t.last_result_info.hex} ${t.last_result_info.name}` : t.last_result}</td>
                             <td>
-                                <button onClick={() => handleTaskAction('run', t.path)} disabled={actionLoading === t.path}>Exécuter</button>
-                                <button onClick={() => handleTaskAction('enable', t.path)} disabled={t.state === 'Ready' || actionLoading === t.path}>Activer</button>
-                                <button onClick={() => handleTaskAction('disable', t.path)} disabled={t.state === 'Disabled' || actionLoading === t.path}>Désactiver</button>
+                                <button onClick={() => handleTaskAction('run', t)} disabled={actionLoading === t.path + t.name}>Exécuter</button>
+                                <button onClick={() => handleTaskAction('stop', t)} disabled={t.state !== 'Running' || actionLoading === t.path + t.name}>Arrêter</button>
+                                <button onClick={() => handleTaskAction('enable', t)} disabled={t.state === 'Ready' || actionLoading === t.path + t.name}>Activer</button>
+                                <button onClick={() => handleTaskAction('disable', t)} disabled={t.state === 'Disabled' || actionLoading === t.path + t.name}>Désactiver</button>
                              </td>
                         </tr>
                     ))}
@@ -49,6 +49,12 @@ interface LocalUserInfo {
     description: string;
     enabled: boolean;
     sid: string;
+    last_logon: string | null;
+    password_last_set: string | null;
+    password_expires: string | null;
+    account_expires: string | null;
+    password_required: boolean;
+    principal_source: string;
 }
 
 // Interface pour les informations groupe local
@@ -56,6 +62,7 @@ interface LocalGroupInfo {
     name: string;
     description: string;
     sid: string;
+    member_count: number;
 }
 
 const UsersPage: React.FC = () => {